@@ -0,0 +1,326 @@
+/* Copyright 2025 Mario Finelli
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Random (but valid) puzzle inputs.
+//!
+//! The tests for each puzzle only cover the example(s) from the prompt and
+//! my actual input, which means that a lot of edge cases never get
+//! exercised. The generators here build random inputs that follow the rules
+//! from the prompt (and nothing more) so that the solutions can be checked
+//! against some simple properties and against brute-force versions of
+//! themselves.
+//!
+//! Every generator takes a random number generator and a `size` which
+//! roughly controls how big the input is (how many lines, how long the
+//! lines are, how big the numbers are, etc.). A size of `0` produces the
+//! smallest input that the puzzle allows, which is often the most
+//! interesting one. Grids come in any shape (including a single row or
+//! column) and numbers are occasionally as big as the solution's types can
+//! handle.
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// A function that generates a random input of (roughly) the given size.
+pub type Generator = fn(&mut StdRng, usize) -> String;
+
+/// Returns the generator for the given puzzle (e.g., `y15d01`), if there is
+/// one.
+pub fn generator(id: &str) -> Option<Generator> {
+    match id {
+        "y15d01" => Some(y15d01),
+        "y15d02" => Some(y15d02),
+        "y15d18" => Some(y15d18),
+        "y22d01" => Some(y22d01),
+        "y22d02" => Some(y22d02),
+        "y22d04" => Some(y22d04),
+        "y22d06" => Some(y22d06),
+        "y22d08" => Some(y22d08),
+        "y23d09" => Some(y23d09),
+        "y23d11" => Some(y23d11),
+        "y24d01" => Some(y24d01),
+        "y24d02" => Some(y24d02),
+        "y25d02" => Some(y25d02),
+        _ => None,
+    }
+}
+
+/// Generates the input for the given puzzle from the given seed; the same
+/// seed always generates the same input.
+///
+/// # Example
+/// ```rust
+/// # use aoc::generate::input;
+/// assert_eq!(input("y15d01", 42, 10), input("y15d01", 42, 10));
+/// assert!(input("y15d00", 42, 10).is_none());
+/// ```
+pub fn input(id: &str, seed: u64, size: usize) -> Option<String> {
+    let generate = generator(id)?;
+    Some(generate(&mut StdRng::seed_from_u64(seed), size))
+}
+
+/// Generates `count` inputs for the given puzzle, each from a different seed
+/// and with sizes that cycle from `0` up to `max_size`.
+///
+/// # Panics
+/// If there isn't a generator for the puzzle.
+pub fn cases(
+    id: &str,
+    count: u64,
+    max_size: usize,
+) -> impl Iterator<Item = String> + '_ {
+    (0..count).map(move |seed| {
+        let size = seed as usize % (max_size + 1);
+        input(id, seed, size).expect("no generator for puzzle")
+    })
+}
+
+/// Parentheses (up to `size` of them, the input may be empty).
+pub fn y15d01(rng: &mut StdRng, size: usize) -> String {
+    let length = rng.gen_range(0..=size);
+    (0..length)
+        .map(|_| if rng.gen_bool(0.5) { '(' } else { ')' })
+        .collect()
+}
+
+/// Present dimensions (`LxWxH`) for `1` to `size + 1` presents, the
+/// dimensions go up to `size + 1`.
+pub fn y15d02(rng: &mut StdRng, size: usize) -> String {
+    let presents = rng.gen_range(1..=size + 1);
+    let mut dimension = || rng.gen_range(1..=size as u32 + 1);
+
+    (0..presents)
+        .map(|_| format!("{}x{}x{}\n", dimension(), dimension(), dimension()))
+        .collect()
+}
+
+/// A grid of lights (up to `size + 1` on each side, so that there's always
+/// at least a single light).
+pub fn y15d18(rng: &mut StdRng, size: usize) -> String {
+    grid(rng, size, |rng| if rng.gen_bool(0.4) { '#' } else { '.' })
+}
+
+/// Groups of calories separated by blank lines; there are always at least
+/// three elves since part two needs the top three. Every so often the
+/// calories are as big as they can be without the total overflowing.
+pub fn y22d01(rng: &mut StdRng, size: usize) -> String {
+    let elves = rng.gen_range(3..=size + 3);
+    let items: Vec<_> =
+        (0..elves).map(|_| rng.gen_range(1..=size + 1)).collect();
+    let max = if rng.gen_bool(0.25) {
+        u32::MAX / items.iter().sum::<usize>() as u32
+    } else {
+        100000
+    };
+
+    items
+        .into_iter()
+        .map(|items| {
+            (0..items)
+                .map(|_| format!("{}\n", rng.gen_range(1..=max)))
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Rounds of rock paper scissors (the input may be empty).
+pub fn y22d02(rng: &mut StdRng, size: usize) -> String {
+    let rounds = rng.gen_range(0..=size);
+
+    (0..rounds)
+        .map(|_| {
+            let theirs = (b'A' + rng.gen_range(0..3)) as char;
+            let ours = (b'X' + rng.gen_range(0..3)) as char;
+            format!("{} {}\n", theirs, ours)
+        })
+        .collect()
+}
+
+/// Pairs of section assignments, including single-section assignments.
+pub fn y22d04(rng: &mut StdRng, size: usize) -> String {
+    let pairs = rng.gen_range(0..=size);
+    let max = size as u32 + 1;
+    let mut range = || {
+        let start = rng.gen_range(1..=max);
+        format!("{}-{}", start, rng.gen_range(start..=max))
+    };
+
+    (0..pairs)
+        .map(|_| format!("{},{}\n", range(), range()))
+        .collect()
+}
+
+/// A datastream of lowercase letters; small sizes use fewer distinct
+/// letters to make markers rare.
+pub fn y22d06(rng: &mut StdRng, size: usize) -> String {
+    let letters = rng.gen_range(1..=26);
+    let length = rng.gen_range(0..=size * 4);
+
+    (0..length)
+        .map(|_| (b'a' + rng.gen_range(0..letters)) as char)
+        .chain(std::iter::once('\n'))
+        .collect()
+}
+
+/// A grid of tree heights (up to `size + 1` on each side).
+pub fn y22d08(rng: &mut StdRng, size: usize) -> String {
+    grid(rng, size, |rng| (b'0' + rng.gen_range(0..10)) as char)
+}
+
+/// Sequences of `1` to `size + 2` values that all come from polynomials of
+/// (at most) the third degree with small coefficients.
+pub fn y23d09(rng: &mut StdRng, size: usize) -> String {
+    let sequences = rng.gen_range(1..=size + 1);
+
+    (0..sequences)
+        .map(|_| {
+            let length = rng.gen_range(1..=size + 2) as i64;
+            let coefficients: Vec<i64> = (0..rng.gen_range(1..=4))
+                .map(|_| rng.gen_range(-9..=9))
+                .collect();
+            let values: Vec<_> = (0..length)
+                .map(|x| {
+                    let value: i64 = coefficients
+                        .iter()
+                        .rev()
+                        .fold(0, |value, coefficient| value * x + coefficient);
+                    value.to_string()
+                })
+                .collect();
+            values.join(" ") + "\n"
+        })
+        .collect()
+}
+
+/// An image of galaxies (up to `size + 1` on each side), which might not have
+/// any galaxies at all.
+pub fn y23d11(rng: &mut StdRng, size: usize) -> String {
+    let density = rng.gen_range(0.0..0.5);
+    grid(
+        rng,
+        size,
+        |rng| if rng.gen_bool(density) { '#' } else { '.' },
+    )
+}
+
+/// Two columns of location ids, small sizes make repeated ids likely. Every
+/// so often the ids are as big as they can be without the answers
+/// overflowing.
+pub fn y24d01(rng: &mut StdRng, size: usize) -> String {
+    let lines = rng.gen_range(1..=size + 1);
+    let max = if rng.gen_bool(0.25) {
+        i32::MAX as u32 / (lines * lines) as u32
+    } else {
+        size as u32 * 10 + 1
+    };
+
+    (0..lines)
+        .map(|_| {
+            format!("{}   {}\n", rng.gen_range(1..=max), rng.gen_range(1..=max))
+        })
+        .collect()
+}
+
+/// Reports of `3` to `size + 5` levels that are nearly safe: most steps are
+/// between one and three in the same direction, with the occasional bad
+/// step (or two) thrown in.
+pub fn y24d02(rng: &mut StdRng, size: usize) -> String {
+    let reports = rng.gen_range(1..=size + 1);
+
+    (0..reports)
+        .map(|_| {
+            let levels = rng.gen_range(3..=size + 5);
+            let direction = if rng.gen_bool(0.5) { 1 } else { -1 };
+            let mut level: i64 = rng.gen_range(20..=80);
+            let mut report = vec![level];
+            for _ in 1..levels {
+                let step = if rng.gen_bool(0.1) {
+                    rng.gen_range(-5..=5)
+                } else {
+                    direction * rng.gen_range(1..=3)
+                };
+                level = (level + step).clamp(1, 99);
+                report.push(level);
+            }
+
+            report
+                .iter()
+                .map(|l| l.to_string())
+                .collect::<Vec<_>>()
+                .join(" ")
+                + "\n"
+        })
+        .collect()
+}
+
+/// Comma-separated product id ranges, including zero-length (single id)
+/// ranges.
+pub fn y25d02(rng: &mut StdRng, size: usize) -> String {
+    let ranges = rng.gen_range(1..=size + 1);
+    let digits = rng.gen_range(1..=(size as u32 / 2).clamp(1, 9));
+
+    (0..ranges)
+        .map(|_| {
+            let start = rng.gen_range(1..10u64.pow(digits));
+            let end = start + rng.gen_range(0..=size as u64 * 10);
+            format!("{}-{}", start, end)
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+        + "\n"
+}
+
+/// A grid with `1` to `size + 1` rows and columns (so single rows and
+/// columns are possible), filling in each cell with the given function.
+fn grid(
+    rng: &mut StdRng,
+    size: usize,
+    mut cell: impl FnMut(&mut StdRng) -> char,
+) -> String {
+    let width = rng.gen_range(1..=size + 1);
+    let height = rng.gen_range(1..=size + 1);
+
+    (0..height)
+        .map(|_| {
+            let mut line: String = (0..width).map(|_| cell(rng)).collect();
+            line.push('\n');
+            line
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        for id in [
+            "y15d01", "y15d02", "y15d18", "y22d01", "y22d02", "y22d04",
+            "y22d06", "y22d08", "y23d09", "y23d11", "y24d01", "y24d02",
+            "y25d02",
+        ] {
+            for (a, b) in cases(id, 20, 10).zip(cases(id, 20, 10)) {
+                assert_eq!(a, b);
+            }
+        }
+
+        assert_eq!(input("y15d18", 1, 0).unwrap().len(), 2);
+        assert!(cases("y22d08", 50, 5).any(|grid| grid.lines().count() == 1));
+        assert!(cases("y22d08", 50, 5).any(|grid| grid.find('\n') == Some(1)));
+        assert!(input("y22d01", 7, 0).unwrap().lines().count() >= 5);
+    }
+}
@@ -17,6 +17,10 @@
 
 //! Advent of Code solutions
 
+pub mod generate;
+pub mod registry;
+pub mod render;
+pub mod serve;
 pub mod title;
 pub mod util;
 
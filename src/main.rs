@@ -13,28 +13,56 @@
  * limitations under the License.
  */
 
+use aoc::registry::{self, Answers, Options};
 use aoc::*;
 use peak_alloc::PeakAlloc;
-use std::{env, fs, io, io::IsTerminal, io::Read, time::Instant};
+use std::io::{IsTerminal, Read, Write};
+use std::net::TcpListener;
+use std::{env, fs, io, time::Instant};
 use termcolor::{ColorChoice, StandardStream};
 
 #[global_allocator]
 static PEAK_ALLOC: PeakAlloc = PeakAlloc;
 
+/// The port that the server listens on if one isn't given.
+const DEFAULT_PORT: u16 = 8025;
+
 fn main() {
     let start = Instant::now();
-    let args: Vec<String> = env::args().collect();
+    let mut args: Vec<String> = Vec::new();
+    let mut implementation = None;
+    let mut check = false;
+    let mut bench = false;
+    let mut explain = false;
+    let mut options = Options::default();
+
+    let mut argv = env::args();
+    while let Some(arg) = argv.next() {
+        match arg.as_str() {
+            "--impl" => implementation = argv.next(),
+            "--check" => check = true,
+            "--bench" => bench = true,
+            "--render" => options.render = argv.next(),
+            "--every" => match argv.next().and_then(|n| n.parse().ok()) {
+                Some(every) => options.every = every,
+                None => return usage(),
+            },
+            "--explain" => explain = true,
+            _ => args.push(arg),
+        }
+    }
 
-    if args.len() != 4 {
-        println!("usage: {} YYYY DD ./path/to/input", args[0]);
+    if args.get(1).map(String::as_str) == Some("serve") {
+        let port = args.get(2).map_or(DEFAULT_PORT, |p| p.parse().unwrap());
+        let listener = TcpListener::bind(("127.0.0.1", port)).unwrap();
+        println!("listening on http://{}", listener.local_addr().unwrap());
+        serve::serve(listener);
         return;
     }
 
-    let input = if args[3] == "-" {
-        read_from_stdin()
-    } else {
-        fs::read_to_string(&args[3]).unwrap()
-    };
+    if args.len() != 4 {
+        return usage();
+    }
 
     let color_choice = if !io::stdout().is_terminal() {
         ColorChoice::Never
@@ -46,290 +74,109 @@ fn main() {
     let day: u8 = args[2].parse().unwrap();
     title::print_title(&mut stdout, &args[1], &format!("{:02}", day));
 
-    let part1: String;
-    let part2: String;
-    let part1_sep = false;
-    let mut part2_sep = false;
-
     let problem_lookup = {
         let split_pos = args[1].char_indices().nth_back(1).unwrap().0;
         let yr = &args[1][split_pos..];
         format!("y{}d{:02}", yr, day)
     };
 
-    match problem_lookup.as_str() {
-        // 2015
-        "y15d01" => {
-            part1 = format!("{}", y15d01::y15d01p1(&input));
-            part2 = format!("{}", y15d01::y15d01p2(&input).unwrap());
-        }
-        "y15d02" => {
-            part1 = format!("{}", y15d02::y15d02(&input, 1));
-            part2 = format!("{}", y15d02::y15d02(&input, 2));
-        }
-        "y15d03" => {
-            part1 = format!("{}", y15d03::y15d03(&input, 1));
-            part2 = format!("{}", y15d03::y15d03(&input, 2));
-        }
-        "y15d04" => {
-            part1 = format!("{}", y15d04::y15d04(input.clone(), 5).unwrap());
-            part2 = format!("{}", y15d04::y15d04(input, 6).unwrap());
-        }
-        "y15d05" => {
-            part1 = format!("{}", y15d05::y15d05(&input, 1));
-            part2 = format!("{}", y15d05::y15d05(&input, 2));
-        }
-        "y15d06" => {
-            part1 = format!("{}", y15d06::y15d06p1(&input));
-            part2 = format!("{}", y15d06::y15d06p2(&input));
-        }
-        "y15d07" => {
-            part1 = format!("{}", y15d07::y15d07(&input, "a", 1));
-            part2 = format!("{}", y15d07::y15d07(&input, "a", 2));
-        }
-        "y15d08" => {
-            part1 = format!("{}", y15d08::y15d08p1(&input));
-            part2 = format!("{}", y15d08::y15d08p2(&input));
-        }
-        "y15d09" => {
-            part1 = format!("{}", y15d09::y15d09(&input, 1));
-            part2 = format!("{}", y15d09::y15d09(&input, 2));
-        }
-        "y15d10" => {
-            part1 = format!("{}", y15d10::y15d10(&input, 40));
-            part2 = format!("{}", y15d10::y15d10(&input, 50));
-        }
-        "y15d11" => {
-            part1 = y15d11::y15d11(&input, 1).to_string();
-            part2 = y15d11::y15d11(&input, 2).to_string();
-        }
-        "y15d12" => {
-            part1 = format!("{}", y15d12::y15d12(&input, false));
-            part2 = format!("{}", y15d12::y15d12(&input, true));
-        }
-        "y15d13" => {
-            part1 = format!("{}", y15d13::y15d13(&input, false));
-            part2 = format!("{}", y15d13::y15d13(&input, true));
-        }
-        "y15d14" => {
-            part1 = format!("{}", y15d14::y15d14(&input, 2503, 1));
-            part2 = format!("{}", y15d14::y15d14(&input, 2503, 2));
-        }
-        "y15d15" => {
-            part1 = format!("{}", y15d15::y15d15(&input, 1));
-            part2 = format!("{}", y15d15::y15d15(&input, 2));
-        }
-        "y15d16" => {
-            part1 = format!("{}", y15d16::y15d16(&input, 1));
-            part2 = format!("{}", y15d16::y15d16(&input, 2));
-        }
-        "y15d17" => {
-            part1 = format!("{}", y15d17::y15d17(&input, 150, 1));
-            part2 = format!("{}", y15d17::y15d17(&input, 150, 2));
-        }
-        "y15d18" => {
-            part1 = format!("{}", y15d18::y15d18(&input, 100, 1));
-            part2 = format!("{}", y15d18::y15d18(&input, 100, 2));
-        }
-        "y15d19" => {
-            part1 = format!("{}", y15d19::y15d19p1(&input));
-            part2 = format!("{}", y15d19::y15d19p2(&input));
-        }
+    let puzzle = registry::find(&problem_lookup)
+        .expect("Unable to find year/day match.");
 
-        // 2022
-        "y22d01" => {
-            part1 = format!("{}", y22d01::y22d01(&input, 1));
-            part2 = format!("{}", y22d01::y22d01(&input, 3));
-        }
-        "y22d02" => {
-            part1 = format!("{}", y22d02::y22d02(&input, 1));
-            part2 = format!("{}", y22d02::y22d02(&input, 2));
-        }
-        "y22d03" => {
-            part1 = format!("{}", y22d03::y22d03(&input, 1));
-            part2 = format!("{}", y22d03::y22d03(&input, 2));
-        }
-        "y22d04" => {
-            part1 = format!("{}", y22d04::y22d04(&input, 1));
-            part2 = format!("{}", y22d04::y22d04(&input, 2));
-        }
-        "y22d05" => {
-            part1 = y22d05::y22d05(&input, 1).to_string();
-            part2 = y22d05::y22d05(&input, 2).to_string();
-        }
-        "y22d06" => {
-            part1 = format!("{}", y22d06::y22d06(&input, 4).unwrap());
-            part2 = format!("{}", y22d06::y22d06(&input, 14).unwrap());
-        }
-        "y22d07" => {
-            part1 = format!("{}", y22d07::y22d07(&input, 1));
-            part2 = format!("{}", y22d07::y22d07(&input, 2));
-        }
-        "y22d08" => {
-            part1 = format!("{}", y22d08::y22d08(&input, 1));
-            part2 = format!("{}", y22d08::y22d08(&input, 2));
-        }
-        "y22d09" => {
-            part1 = format!("{}", y22d09::y22d09(&input, 2));
-            part2 = format!("{}", y22d09::y22d09(&input, 10));
-        }
-        "y22d10" => {
-            part1 = format!("{}", y22d10::y22d10p1(&input));
-            part2 = y22d10::y22d10p2(&input).to_string();
-            part2_sep = true;
-        }
-        "y22d11" => {
-            part1 = format!("{}", y22d11::y22d11(&input, 20, true));
-            part2 = format!("{}", y22d11::y22d11(&input, 10000, false));
-        }
-        "y22d12" => {
-            part1 = format!("{}", y22d12::y22d12(&input, 1).unwrap());
-            part2 = format!("{}", y22d12::y22d12(&input, 2).unwrap());
-        }
-        "y22d13" => {
-            part1 = format!("{}", y22d13::y22d13(&input, 1));
-            part2 = format!("{}", y22d13::y22d13(&input, 2));
-        }
-        "y22d14" => {
-            part1 = format!("{}", y22d14::y22d14(&input, 1));
-            part2 = format!("{}", y22d14::y22d14(&input, 2));
-        }
-        "y22d15" => {
-            part1 = format!("{}", y22d15::y22d15p1(&input, 2000000));
-            part2 = format!("{}", y22d15::y22d15p2(&input, 4000000));
-        }
-        "y22d16" => {
-            part1 = format!("{}", y22d16::y22d16(&input, 1));
-            part2 = format!("{}", y22d16::y22d16(&input, 2));
-        }
+    // piped input can be solved as it comes in (if the puzzle supports it)
+    // unless we need to run more than one implementation over it
+    let stdin = args[3] == "-";
+    let streaming = registry::find_streaming(&problem_lookup)
+        .filter(|_| stdin && !bench && !check && implementation.is_none());
 
-        // 2023
-        "y23d01" => {
-            part1 = format!("{}", y23d01::y23d01(&input, 1));
-            part2 = format!("{}", y23d01::y23d01(&input, 2));
-        }
-        "y23d02" => {
-            part1 = format!("{}", y23d02::y23d02(&input, 1));
-            part2 = format!("{}", y23d02::y23d02(&input, 2));
-        }
-        "y23d03" => {
-            part1 = format!("{}", y23d03::y23d03p1(&input));
-            part2 = format!("{}", y23d03::y23d03p2(&input));
-        }
-        "y23d04" => {
-            part1 = format!("{}", y23d04::y23d04(&input, 1));
-            part2 = format!("{}", y23d04::y23d04(&input, 2));
-        }
-        "y23d05" => {
-            part1 = format!("{}", y23d05::y23d05(&input, 1));
-            part2 = format!("{}", y23d05::y23d05(&input, 2));
-        }
-        "y23d06" => {
-            part1 = format!("{}", y23d06::y23d06(&input, 1));
-            part2 = format!("{}", y23d06::y23d06(&input, 2));
-        }
-        "y23d07" => {
-            part1 = format!("{}", y23d07::y23d07(&input, 1));
-            part2 = format!("{}", y23d07::y23d07(&input, 2));
-        }
-        "y23d08" => {
-            part1 = format!("{}", y23d08::y23d08(&input, 1));
-            part2 = format!("{}", y23d08::y23d08(&input, 2));
-        }
-        "y23d09" => {
-            part1 = format!("{}", y23d09::y23d09(&input, 1));
-            part2 = format!("{}", y23d09::y23d09(&input, 2));
-        }
-        "y23d10" => {
-            part1 = format!("{}", y23d10::y23d10(&input, 1));
-            part2 = format!("{}", y23d10::y23d10(&input, 2));
-        }
-        "y23d11" => {
-            part1 = format!("{}", y23d11::y23d11(&input, 1));
-            part2 = format!("{}", y23d11::y23d11(&input, 1000000));
-        }
-        "y23d12" => {
-            part1 = format!("{}", y23d12::y23d12(&input, 1));
-            part2 = format!("{}", y23d12::y23d12(&input, 5));
-        }
-        "y23d13" => {
-            part1 = format!("{}", y23d13::y23d13(&input, 1));
-            part2 = format!("{}", y23d13::y23d13(&input, 2));
-        }
-        "y23d14" => {
-            part1 = format!("{}", y23d14::y23d14(&input, 1));
-            part2 = format!("{}", y23d14::y23d14(&input, 2));
-        }
-        "y23d15" => {
-            part1 = format!("{}", y23d15::y23d15(&input, 1));
-            part2 = format!("{}", y23d15::y23d15(&input, 2));
-        }
-        "y23d16" => {
-            part1 = format!("{}", y23d16::y23d16(&input, 1));
-            part2 = format!("{}", y23d16::y23d16(&input, 2));
-        }
+    if let Some(streaming) = streaming {
+        let answers = (streaming.solve)(&mut io::stdin().lock(), &options);
+        finish(&mut stdout, &answers, start);
+        return;
+    }
 
-        // 2024
-        "y24d01" => {
-            part1 = format!("{}", y24d01::y24d01p1(&input));
-            part2 = format!("{}", y24d01::y24d01p2(&input));
-        }
-        "y24d02" => {
-            part1 = format!("{}", y24d02::y24d02(&input, 1));
-            part2 = format!("{}", y24d02::y24d02(&input, 2));
-        }
-        "y24d03" => {
-            part1 = format!("{}", y24d03::y24d03(&input, 1));
-            part2 = format!("{}", y24d03::y24d03(&input, 2));
-        }
-        "y24d04" => {
-            part1 = format!("{}", y24d04::y24d04p1(&input));
-            part2 = format!("{}", y24d04::y24d04p2(&input));
-        }
-        "y24d05" => {
-            part1 = format!("{}", y24d05::y24d05(&input, 1));
-            part2 = format!("{}", y24d05::y24d05(&input, 2));
-        }
-        "y24d06" => {
-            part1 = format!("{}", y24d06::y24d06(&input, 1));
-            part2 = format!("{}", y24d06::y24d06(&input, 2));
-        }
+    let input = if stdin {
+        read_from_stdin()
+    } else {
+        fs::read_to_string(&args[3]).unwrap()
+    };
 
-        // 2025
-        "y25d01" => {
-            part1 = format!("{}", y25d01::y25d01(&input, 1));
-            part2 = format!("{}", y25d01::y25d01(&input, 2));
-        }
-        "y25d02" => {
-            part1 = format!("{}", y25d02::y25d02(&input, 1));
-            part2 = format!("{}", y25d02::y25d02(&input, 2));
-        }
-        "y25d03" => {
-            part1 = format!("{}", y25d03::y25d03(&input, 2));
-            part2 = format!("{}", y25d03::y25d03(&input, 12));
+    let answers = if bench {
+        let mut answers = Vec::new();
+
+        for implementation in puzzle.implementations {
+            let start = Instant::now();
+            answers.push((implementation.solve)(&input, &options));
+            title::print_timing(
+                &mut stdout,
+                implementation.name,
+                start.elapsed(),
+            );
+        }
+
+        writeln!(stdout).unwrap();
+        answers.swap_remove(0)
+    } else if check {
+        match puzzle.cross_check(&input, &options) {
+            Ok(answers) => answers,
+            Err(disagreement) => panic!(
+                "Implementations disagree: {} says {:?} but {} says {:?}",
+                disagreement.expected.0,
+                disagreement.expected.1,
+                disagreement.actual.0,
+                disagreement.actual.1,
+            ),
         }
-        "y25d04" => {
-            part1 = format!("{}", y25d04::y25d04(&input, 1));
-            part2 = format!("{}", y25d04::y25d04(&input, 2));
-        }
-        "y25d05" => {
-            part1 = format!("{}", y25d05::y25d05p1(&input));
-            part2 = format!("{}", y25d05::y25d05p2(&input));
-        }
-        "y25d06" => {
-            part1 = format!("{}", y25d06::y25d06(&input));
-            part2 = "".to_string();
+    } else {
+        let implementation = puzzle
+            .implementation(implementation.as_deref())
+            .expect("Unknown implementation.");
+        (implementation.solve)(&input, &options)
+    };
+
+    // explaining is kept out of the solvers so that it isn't benchmarked
+    if explain {
+        match registry::find_explaining(&problem_lookup) {
+            Some(explaining) => {
+                write!(stdout, "{}", (explaining.explain)(&input)).unwrap();
+            }
+            None => eprintln!(
+                "There's no explanation for {} (yet), only the answers.",
+                problem_lookup
+            ),
         }
+    }
 
-        _ => panic!("Unable to find year/day match."),
-    };
+    finish(&mut stdout, &answers, start);
+}
+
+/// Prints how to run the program.
+fn usage() {
+    let program = env::args().next().unwrap_or_else(|| "aoc".to_string());
+    println!("usage: {} YYYY DD ./path/to/input [options]", program);
+    println!("       {} serve [PORT]", program);
+    println!();
+    println!("options:");
+    println!("  --impl NAME      use the named implementation");
+    println!("  --check          run all implementations and compare them");
+    println!("  --bench          run all implementations and time them");
+    println!("  --render TARGET  render simulations (e.g., terminal)");
+    println!("  --every N        only render every Nth frame");
+    println!("  --explain        explain the answers (where supported)");
+}
 
-    title::print_answer(&mut stdout, 1, &part1, part1_sep);
-    title::print_answer(&mut stdout, 2, &part2, part2_sep);
+/// Prints the answers and how long it took (and how much memory it used) to
+/// come up with them.
+fn finish(stdout: &mut StandardStream, answers: &Answers, start: Instant) {
+    // answers that are drawings need to start on their own line
+    let part1_sep = answers.part1.contains('\n');
+    let part2_sep = answers.part2.contains('\n');
+    title::print_answer(stdout, 1, &answers.part1, part1_sep);
+    title::print_answer(stdout, 2, &answers.part2, part2_sep);
 
     let elapsed = start.elapsed();
     let peak_usage = PEAK_ALLOC.peak_usage();
-    title::print_stats(&mut stdout, elapsed, peak_usage);
+    title::print_stats(stdout, elapsed, peak_usage);
 }
 
 fn read_from_stdin() -> String {
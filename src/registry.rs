@@ -0,0 +1,1068 @@
+/* Copyright 2025 Mario Finelli
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! The registry of all of the puzzle solutions.
+//!
+//! Every puzzle has at least one [`Implementation`] (the first one is the
+//! default), but some of them also keep around the other approaches that I
+//! tried while solving the puzzle (e.g., the [Dijkstra's
+//! algorithm](crate::y22d12::y22d12_dijkstra) version of day twelve in 2022).
+//! Having them all in one place lets the runner pick one by name, run all of
+//! them to make sure that they agree with each other, or run all of them to
+//! compare how long they take.
+
+use crate::render::{self, NoRender, Render, Sampled};
+use crate::*;
+use std::fmt::Display;
+use std::io::BufRead;
+
+/// The name of the default implementation of a puzzle.
+pub const DEFAULT: &str = "default";
+
+/// The answers to both parts of a puzzle.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Answers {
+    /// The answer to part one.
+    pub part1: String,
+    /// The answer to part two.
+    pub part2: String,
+}
+
+impl Answers {
+    /// Creates the answers from anything that can be displayed.
+    pub fn new(part1: impl Display, part2: impl Display) -> Answers {
+        Answers {
+            part1: part1.to_string(),
+            part2: part2.to_string(),
+        }
+    }
+}
+
+/// Options that change how (some of) the solvers run.
+#[derive(Clone, Debug, Default)]
+pub struct Options {
+    /// Where to render the simulations that support it (see
+    /// [`render::from_spec`]), if anywhere.
+    pub render: Option<String>,
+    /// Only render every `n`th frame.
+    pub every: usize,
+}
+
+impl Options {
+    /// Builds the renderer for a simulation run; it's a no-op unless we were
+    /// asked to render something.
+    pub fn renderer(&self, label: &str) -> Box<dyn Render> {
+        match &self.render {
+            None => Box::new(NoRender),
+            Some(spec) => {
+                let render = render::from_spec(spec, label)
+                    .expect("Unknown render target.");
+                Box::new(Sampled::new(render, self.every))
+            }
+        }
+    }
+}
+
+/// A function that solves both parts of a puzzle.
+pub type Solver = fn(&str, &Options) -> Answers;
+
+/// A function that solves both parts of a puzzle while reading the input as
+/// it goes (so the input never has to fit in memory).
+pub type StreamSolver = fn(&mut dyn BufRead, &Options) -> Answers;
+
+/// A puzzle that can also be solved from a stream of input.
+pub struct Streaming {
+    /// The puzzle identifier, e.g., `y15d01`.
+    pub id: &'static str,
+    /// Solves the puzzle.
+    pub solve: StreamSolver,
+}
+
+/// A function that explains how the answers to both parts of a puzzle were
+/// found.
+pub type Explainer = fn(&str) -> String;
+
+/// A puzzle that can explain its answers.
+pub struct Explaining {
+    /// The puzzle identifier, e.g., `y15d05`.
+    pub id: &'static str,
+    /// Explains the answers.
+    pub explain: Explainer,
+}
+
+/// One way of solving a puzzle.
+pub struct Implementation {
+    /// The name used to pick the implementation.
+    pub name: &'static str,
+    /// Solves the puzzle.
+    pub solve: Solver,
+}
+
+/// A puzzle and all of the ways that we have to solve it.
+pub struct Puzzle {
+    /// The puzzle identifier, e.g., `y15d01`.
+    pub id: &'static str,
+    /// The implementations, the first one is the default.
+    pub implementations: &'static [Implementation],
+}
+
+impl Puzzle {
+    /// Returns the implementation with the given name, or the default one if
+    /// no name is given.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use aoc::registry;
+    /// let puzzle = registry::find("y22d12").unwrap();
+    /// assert_eq!(puzzle.implementation(None).unwrap().name, "default");
+    /// assert!(puzzle.implementation(Some("dijkstra")).is_some());
+    /// assert!(puzzle.implementation(Some("magic")).is_none());
+    /// ```
+    pub fn implementation(
+        &self,
+        name: Option<&str>,
+    ) -> Option<&Implementation> {
+        match name {
+            None => self.implementations.first(),
+            Some(name) => self.implementations.iter().find(|i| i.name == name),
+        }
+    }
+
+    /// Runs all of the implementations and makes sure that they all come up
+    /// with the same answers. Returns the answers if they do, or the names of
+    /// the first two implementations that disagree (and their answers) if
+    /// they don't.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use aoc::registry::{self, Answers, Options};
+    /// let puzzle = registry::find("y15d02").unwrap();
+    /// assert_eq!(
+    ///     puzzle.cross_check("2x3x4\n", &Options::default()),
+    ///     Ok(Answers::new(58, 34))
+    /// );
+    /// ```
+    pub fn cross_check(
+        &self,
+        input: &str,
+        options: &Options,
+    ) -> Result<Answers, Box<Disagreement>> {
+        let mut results = self.implementations.iter().map(|implementation| {
+            (implementation.name, (implementation.solve)(input, options))
+        });
+
+        let (name, answers) = results.next().unwrap();
+        for (other_name, other_answers) in results {
+            if other_answers != answers {
+                return Err(Box::new(Disagreement {
+                    expected: (name, answers),
+                    actual: (other_name, other_answers),
+                }));
+            }
+        }
+
+        Ok(answers)
+    }
+}
+
+/// Two implementations of the same puzzle that came up with different
+/// answers.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Disagreement {
+    /// The name and answers of the default implementation.
+    pub expected: (&'static str, Answers),
+    /// The name and answers of the implementation that disagreed.
+    pub actual: (&'static str, Answers),
+}
+
+/// Returns the puzzle with the given identifier (e.g., `y15d01`).
+pub fn find(id: &str) -> Option<&'static Puzzle> {
+    PUZZLES.iter().find(|puzzle| puzzle.id == id)
+}
+
+/// Returns the streaming solver for the puzzle with the given identifier, if
+/// it has one.
+///
+/// # Example
+/// ```rust
+/// # use aoc::registry::{self, Answers, Options};
+/// let streaming = registry::find_streaming("y15d02").unwrap();
+/// let mut input = "2x3x4\n".as_bytes();
+/// assert_eq!(
+///     (streaming.solve)(&mut input, &Options::default()),
+///     Answers::new(58, 34)
+/// );
+/// assert!(registry::find_streaming("y22d12").is_none());
+/// ```
+pub fn find_streaming(id: &str) -> Option<&'static Streaming> {
+    STREAMING.iter().find(|streaming| streaming.id == id)
+}
+
+/// The puzzles that can be solved from a stream of input.
+pub const STREAMING: &[Streaming] = &[
+    Streaming {
+        id: "y15d01",
+        solve: |input, _| {
+            let (floor, basement) = y15d01::y15d01_stream(input).unwrap();
+            let basement =
+                basement.map_or("none".to_string(), |b| b.to_string());
+            Answers::new(floor, basement)
+        },
+    },
+    Streaming {
+        id: "y15d02",
+        solve: |input, _| {
+            let (paper, ribbon) = y15d02::y15d02_stream(input).unwrap();
+            Answers::new(paper, ribbon)
+        },
+    },
+];
+
+/// Returns the explainer for the puzzle with the given identifier, if it has
+/// one. Explaining is separate from solving so that the explanation isn't
+/// part of any timings (and only shows up once when benchmarking).
+///
+/// # Example
+/// ```rust
+/// # use aoc::registry;
+/// let explaining = registry::find_explaining("y15d05").unwrap();
+/// let explanation = (explaining.explain)("ugknbfddgicrmopn\n");
+/// assert!(explanation.starts_with("Part 1 verdicts:\n"));
+/// assert!(explanation.contains("nice"));
+/// assert!(explanation.contains("\nPart 2 verdicts:\n"));
+/// assert!(registry::find_explaining("y22d12").is_none());
+/// ```
+pub fn find_explaining(id: &str) -> Option<&'static Explaining> {
+    EXPLAINING.iter().find(|explaining| explaining.id == id)
+}
+
+/// The puzzles that can explain their answers.
+pub const EXPLAINING: &[Explaining] = &[Explaining {
+    id: "y15d05",
+    explain: |input| {
+        format!(
+            "Part 1 verdicts:\n{}\nPart 2 verdicts:\n{}\n",
+            y15d05::y15d05_explain(input, 1),
+            y15d05::y15d05_explain(input, 2)
+        )
+    },
+}];
+
+/// All of the puzzles that we have solutions for.
+pub const PUZZLES: &[Puzzle] = &[
+    // 2015
+    Puzzle {
+        id: "y15d01",
+        implementations: &[Implementation {
+            name: DEFAULT,
+            solve: |input, _| {
+                // santa might never get to the basement
+                let basement = y15d01::y15d01p2(input)
+                    .map_or("none".to_string(), |b| b.to_string());
+                Answers::new(y15d01::y15d01p1(input), basement)
+            },
+        }],
+    },
+    Puzzle {
+        id: "y15d02",
+        implementations: &[
+            Implementation {
+                name: DEFAULT,
+                solve: |input, _| {
+                    Answers::new(
+                        y15d02::y15d02(input, 1),
+                        y15d02::y15d02(input, 2),
+                    )
+                },
+            },
+            Implementation {
+                name: "naive",
+                solve: |input, _| {
+                    Answers::new(
+                        y15d02::y15d02_naive(input, 1),
+                        y15d02::y15d02_naive(input, 2),
+                    )
+                },
+            },
+        ],
+    },
+    Puzzle {
+        id: "y15d03",
+        implementations: &[Implementation {
+            name: DEFAULT,
+            solve: |input, _| {
+                Answers::new(y15d03::y15d03(input, 1), y15d03::y15d03(input, 2))
+            },
+        }],
+    },
+    Puzzle {
+        id: "y15d04",
+        implementations: &[Implementation {
+            name: DEFAULT,
+            solve: |input, _| {
+                let answers = y15d04::y15d04_mine(input, &[5, 6]);
+                Answers::new(answers[0].unwrap(), answers[1].unwrap())
+            },
+        }],
+    },
+    Puzzle {
+        id: "y15d05",
+        implementations: &[Implementation {
+            name: DEFAULT,
+            solve: |input, _| {
+                Answers::new(y15d05::y15d05(input, 1), y15d05::y15d05(input, 2))
+            },
+        }],
+    },
+    Puzzle {
+        id: "y15d06",
+        implementations: &[Implementation {
+            name: DEFAULT,
+            solve: |input, _| {
+                Answers::new(y15d06::y15d06p1(input), y15d06::y15d06p2(input))
+            },
+        }],
+    },
+    Puzzle {
+        id: "y15d07",
+        implementations: &[Implementation {
+            name: DEFAULT,
+            solve: |input, _| {
+                Answers::new(
+                    y15d07::y15d07(input, "a", 1),
+                    y15d07::y15d07(input, "a", 2),
+                )
+            },
+        }],
+    },
+    Puzzle {
+        id: "y15d08",
+        implementations: &[Implementation {
+            name: DEFAULT,
+            solve: |input, _| {
+                Answers::new(y15d08::y15d08p1(input), y15d08::y15d08p2(input))
+            },
+        }],
+    },
+    Puzzle {
+        id: "y15d09",
+        implementations: &[
+            Implementation {
+                name: DEFAULT,
+                solve: |input, _| {
+                    Answers::new(
+                        y15d09::y15d09(input, 1),
+                        y15d09::y15d09(input, 2),
+                    )
+                },
+            },
+            Implementation {
+                name: "permutations",
+                solve: |input, _| {
+                    Answers::new(
+                        y15d09::y15d09_permutations(input, 1),
+                        y15d09::y15d09_permutations(input, 2),
+                    )
+                },
+            },
+        ],
+    },
+    Puzzle {
+        id: "y15d10",
+        implementations: &[
+            Implementation {
+                name: DEFAULT,
+                solve: |input, _| {
+                    Answers::new(
+                        y15d10::y15d10(input, 40),
+                        y15d10::y15d10(input, 50),
+                    )
+                },
+            },
+            Implementation {
+                name: "direct",
+                solve: |input, _| {
+                    Answers::new(
+                        y15d10::y15d10_direct(input, 40),
+                        y15d10::y15d10_direct(input, 50),
+                    )
+                },
+            },
+        ],
+    },
+    Puzzle {
+        id: "y15d11",
+        implementations: &[Implementation {
+            name: DEFAULT,
+            solve: |input, _| {
+                Answers::new(y15d11::y15d11(input, 1), y15d11::y15d11(input, 2))
+            },
+        }],
+    },
+    Puzzle {
+        id: "y15d12",
+        implementations: &[Implementation {
+            name: DEFAULT,
+            solve: |input, _| {
+                Answers::new(
+                    y15d12::y15d12(input, false),
+                    y15d12::y15d12(input, true),
+                )
+            },
+        }],
+    },
+    Puzzle {
+        id: "y15d13",
+        implementations: &[
+            Implementation {
+                name: DEFAULT,
+                solve: |input, _| {
+                    Answers::new(
+                        y15d13::y15d13(input, false),
+                        y15d13::y15d13(input, true),
+                    )
+                },
+            },
+            Implementation {
+                name: "permutations",
+                solve: |input, _| {
+                    Answers::new(
+                        y15d13::y15d13_permutations(input, false),
+                        y15d13::y15d13_permutations(input, true),
+                    )
+                },
+            },
+        ],
+    },
+    Puzzle {
+        id: "y15d14",
+        implementations: &[Implementation {
+            name: DEFAULT,
+            solve: |input, _| {
+                Answers::new(
+                    y15d14::y15d14(input, 2503, 1),
+                    y15d14::y15d14(input, 2503, 2),
+                )
+            },
+        }],
+    },
+    Puzzle {
+        id: "y15d15",
+        implementations: &[Implementation {
+            name: DEFAULT,
+            solve: |input, _| {
+                Answers::new(y15d15::y15d15(input, 1), y15d15::y15d15(input, 2))
+            },
+        }],
+    },
+    Puzzle {
+        id: "y15d16",
+        implementations: &[Implementation {
+            name: DEFAULT,
+            solve: |input, _| {
+                Answers::new(y15d16::y15d16(input, 1), y15d16::y15d16(input, 2))
+            },
+        }],
+    },
+    Puzzle {
+        id: "y15d17",
+        implementations: &[Implementation {
+            name: DEFAULT,
+            solve: |input, _| {
+                Answers::new(
+                    y15d17::y15d17(input, 150, 1),
+                    y15d17::y15d17(input, 150, 2),
+                )
+            },
+        }],
+    },
+    Puzzle {
+        id: "y15d18",
+        implementations: &[Implementation {
+            name: DEFAULT,
+            solve: |input, options| {
+                Answers::new(
+                    y15d18::y15d18_render(
+                        input,
+                        100,
+                        1,
+                        &mut options.renderer("part1"),
+                    ),
+                    y15d18::y15d18_render(
+                        input,
+                        100,
+                        2,
+                        &mut options.renderer("part2"),
+                    ),
+                )
+            },
+        }],
+    },
+    Puzzle {
+        id: "y15d19",
+        implementations: &[Implementation {
+            name: DEFAULT,
+            solve: |input, _| {
+                Answers::new(y15d19::y15d19p1(input), y15d19::y15d19p2(input))
+            },
+        }],
+    },
+    // 2022
+    Puzzle {
+        id: "y22d01",
+        implementations: &[Implementation {
+            name: DEFAULT,
+            solve: |input, _| {
+                Answers::new(y22d01::y22d01(input, 1), y22d01::y22d01(input, 3))
+            },
+        }],
+    },
+    Puzzle {
+        id: "y22d02",
+        implementations: &[Implementation {
+            name: DEFAULT,
+            solve: |input, _| {
+                Answers::new(y22d02::y22d02(input, 1), y22d02::y22d02(input, 2))
+            },
+        }],
+    },
+    Puzzle {
+        id: "y22d03",
+        implementations: &[Implementation {
+            name: DEFAULT,
+            solve: |input, _| {
+                Answers::new(y22d03::y22d03(input, 1), y22d03::y22d03(input, 2))
+            },
+        }],
+    },
+    Puzzle {
+        id: "y22d04",
+        implementations: &[Implementation {
+            name: DEFAULT,
+            solve: |input, _| {
+                Answers::new(y22d04::y22d04(input, 1), y22d04::y22d04(input, 2))
+            },
+        }],
+    },
+    Puzzle {
+        id: "y22d05",
+        implementations: &[Implementation {
+            name: DEFAULT,
+            solve: |input, _| {
+                Answers::new(y22d05::y22d05(input, 1), y22d05::y22d05(input, 2))
+            },
+        }],
+    },
+    Puzzle {
+        id: "y22d06",
+        implementations: &[Implementation {
+            name: DEFAULT,
+            solve: |input, _| {
+                Answers::new(
+                    y22d06::y22d06(input, 4).unwrap(),
+                    y22d06::y22d06(input, 14).unwrap(),
+                )
+            },
+        }],
+    },
+    Puzzle {
+        id: "y22d07",
+        implementations: &[Implementation {
+            name: DEFAULT,
+            solve: |input, _| {
+                Answers::new(y22d07::y22d07(input, 1), y22d07::y22d07(input, 2))
+            },
+        }],
+    },
+    Puzzle {
+        id: "y22d08",
+        implementations: &[Implementation {
+            name: DEFAULT,
+            solve: |input, _| {
+                Answers::new(y22d08::y22d08(input, 1), y22d08::y22d08(input, 2))
+            },
+        }],
+    },
+    Puzzle {
+        id: "y22d09",
+        implementations: &[Implementation {
+            name: DEFAULT,
+            solve: |input, options| {
+                Answers::new(
+                    y22d09::y22d09_render(
+                        input,
+                        2,
+                        &mut options.renderer("part1"),
+                    ),
+                    y22d09::y22d09_render(
+                        input,
+                        10,
+                        &mut options.renderer("part2"),
+                    ),
+                )
+            },
+        }],
+    },
+    Puzzle {
+        id: "y22d10",
+        implementations: &[Implementation {
+            name: DEFAULT,
+            solve: |input, _| {
+                Answers::new(
+                    y22d10::y22d10p1(input),
+                    y22d10::y22d10p2_ocr(input)
+                        .unwrap_or_else(|| y22d10::y22d10p2(input)),
+                )
+            },
+        }],
+    },
+    Puzzle {
+        id: "y22d11",
+        implementations: &[Implementation {
+            name: DEFAULT,
+            solve: |input, _| {
+                Answers::new(
+                    y22d11::y22d11(input, 20, true),
+                    y22d11::y22d11(input, 10000, false),
+                )
+            },
+        }],
+    },
+    Puzzle {
+        id: "y22d12",
+        implementations: &[
+            Implementation {
+                name: DEFAULT,
+                solve: |input, _| {
+                    Answers::new(
+                        y22d12::y22d12(input, 1).unwrap(),
+                        y22d12::y22d12(input, 2).unwrap(),
+                    )
+                },
+            },
+            Implementation {
+                name: "dijkstra",
+                solve: |input, _| {
+                    Answers::new(
+                        y22d12::y22d12_dijkstra(input, 1).unwrap(),
+                        y22d12::y22d12_dijkstra(input, 2).unwrap(),
+                    )
+                },
+            },
+            Implementation {
+                name: "dfs",
+                solve: |input, _| {
+                    Answers::new(
+                        y22d12::y22d12_dfs(input, 1).unwrap(),
+                        y22d12::y22d12_dfs(input, 2).unwrap(),
+                    )
+                },
+            },
+        ],
+    },
+    Puzzle {
+        id: "y22d13",
+        implementations: &[Implementation {
+            name: DEFAULT,
+            solve: |input, _| {
+                Answers::new(y22d13::y22d13(input, 1), y22d13::y22d13(input, 2))
+            },
+        }],
+    },
+    Puzzle {
+        id: "y22d14",
+        implementations: &[Implementation {
+            name: DEFAULT,
+            solve: |input, options| {
+                Answers::new(
+                    y22d14::y22d14_render(
+                        input,
+                        1,
+                        &mut options.renderer("part1"),
+                    ),
+                    y22d14::y22d14_render(
+                        input,
+                        2,
+                        &mut options.renderer("part2"),
+                    ),
+                )
+            },
+        }],
+    },
+    Puzzle {
+        id: "y22d15",
+        implementations: &[Implementation {
+            name: DEFAULT,
+            solve: |input, _| {
+                Answers::new(
+                    y22d15::y22d15p1(input, 2000000),
+                    y22d15::y22d15p2(input, 4000000),
+                )
+            },
+        }],
+    },
+    Puzzle {
+        id: "y22d16",
+        implementations: &[Implementation {
+            name: DEFAULT,
+            solve: |input, _| {
+                Answers::new(y22d16::y22d16(input, 1), y22d16::y22d16(input, 2))
+            },
+        }],
+    },
+    // 2023
+    Puzzle {
+        id: "y23d01",
+        implementations: &[Implementation {
+            name: DEFAULT,
+            solve: |input, _| {
+                Answers::new(y23d01::y23d01(input, 1), y23d01::y23d01(input, 2))
+            },
+        }],
+    },
+    Puzzle {
+        id: "y23d02",
+        implementations: &[Implementation {
+            name: DEFAULT,
+            solve: |input, _| {
+                Answers::new(y23d02::y23d02(input, 1), y23d02::y23d02(input, 2))
+            },
+        }],
+    },
+    Puzzle {
+        id: "y23d03",
+        implementations: &[Implementation {
+            name: DEFAULT,
+            solve: |input, _| {
+                Answers::new(y23d03::y23d03p1(input), y23d03::y23d03p2(input))
+            },
+        }],
+    },
+    Puzzle {
+        id: "y23d04",
+        implementations: &[Implementation {
+            name: DEFAULT,
+            solve: |input, _| {
+                Answers::new(y23d04::y23d04(input, 1), y23d04::y23d04(input, 2))
+            },
+        }],
+    },
+    Puzzle {
+        id: "y23d05",
+        implementations: &[Implementation {
+            name: DEFAULT,
+            solve: |input, _| {
+                Answers::new(y23d05::y23d05(input, 1), y23d05::y23d05(input, 2))
+            },
+        }],
+    },
+    Puzzle {
+        id: "y23d06",
+        implementations: &[Implementation {
+            name: DEFAULT,
+            solve: |input, _| {
+                Answers::new(y23d06::y23d06(input, 1), y23d06::y23d06(input, 2))
+            },
+        }],
+    },
+    Puzzle {
+        id: "y23d07",
+        implementations: &[Implementation {
+            name: DEFAULT,
+            solve: |input, _| {
+                Answers::new(y23d07::y23d07(input, 1), y23d07::y23d07(input, 2))
+            },
+        }],
+    },
+    Puzzle {
+        id: "y23d08",
+        implementations: &[Implementation {
+            name: DEFAULT,
+            solve: |input, _| {
+                Answers::new(y23d08::y23d08(input, 1), y23d08::y23d08(input, 2))
+            },
+        }],
+    },
+    Puzzle {
+        id: "y23d09",
+        implementations: &[Implementation {
+            name: DEFAULT,
+            solve: |input, _| {
+                Answers::new(y23d09::y23d09(input, 1), y23d09::y23d09(input, 2))
+            },
+        }],
+    },
+    Puzzle {
+        id: "y23d10",
+        implementations: &[Implementation {
+            name: DEFAULT,
+            solve: |input, _| {
+                Answers::new(y23d10::y23d10(input, 1), y23d10::y23d10(input, 2))
+            },
+        }],
+    },
+    Puzzle {
+        id: "y23d11",
+        implementations: &[Implementation {
+            name: DEFAULT,
+            solve: |input, _| {
+                Answers::new(
+                    y23d11::y23d11(input, 1),
+                    y23d11::y23d11(input, 1000000),
+                )
+            },
+        }],
+    },
+    Puzzle {
+        id: "y23d12",
+        implementations: &[Implementation {
+            name: DEFAULT,
+            solve: |input, _| {
+                Answers::new(y23d12::y23d12(input, 1), y23d12::y23d12(input, 5))
+            },
+        }],
+    },
+    Puzzle {
+        id: "y23d13",
+        implementations: &[Implementation {
+            name: DEFAULT,
+            solve: |input, _| {
+                Answers::new(y23d13::y23d13(input, 1), y23d13::y23d13(input, 2))
+            },
+        }],
+    },
+    Puzzle {
+        id: "y23d14",
+        implementations: &[Implementation {
+            name: DEFAULT,
+            solve: |input, options| {
+                Answers::new(
+                    y23d14::y23d14_render(
+                        input,
+                        1,
+                        &mut options.renderer("part1"),
+                    ),
+                    y23d14::y23d14_render(
+                        input,
+                        2,
+                        &mut options.renderer("part2"),
+                    ),
+                )
+            },
+        }],
+    },
+    Puzzle {
+        id: "y23d15",
+        implementations: &[Implementation {
+            name: DEFAULT,
+            solve: |input, _| {
+                Answers::new(y23d15::y23d15(input, 1), y23d15::y23d15(input, 2))
+            },
+        }],
+    },
+    Puzzle {
+        id: "y23d16",
+        implementations: &[
+            Implementation {
+                name: DEFAULT,
+                solve: |input, options| {
+                    Answers::new(
+                        y23d16::y23d16_render(
+                            input,
+                            1,
+                            &mut options.renderer("part1"),
+                        ),
+                        y23d16::y23d16_render(
+                            input,
+                            2,
+                            &mut options.renderer("part2"),
+                        ),
+                    )
+                },
+            },
+            Implementation {
+                name: "recursive",
+                solve: |input, _| {
+                    Answers::new(
+                        y23d16::y23d16_recursive(input, 1),
+                        y23d16::y23d16_recursive(input, 2),
+                    )
+                },
+            },
+        ],
+    },
+    // 2024
+    Puzzle {
+        id: "y24d01",
+        implementations: &[Implementation {
+            name: DEFAULT,
+            solve: |input, _| {
+                Answers::new(y24d01::y24d01p1(input), y24d01::y24d01p2(input))
+            },
+        }],
+    },
+    Puzzle {
+        id: "y24d02",
+        implementations: &[Implementation {
+            name: DEFAULT,
+            solve: |input, _| {
+                Answers::new(y24d02::y24d02(input, 1), y24d02::y24d02(input, 2))
+            },
+        }],
+    },
+    Puzzle {
+        id: "y24d03",
+        implementations: &[Implementation {
+            name: DEFAULT,
+            solve: |input, _| {
+                Answers::new(y24d03::y24d03(input, 1), y24d03::y24d03(input, 2))
+            },
+        }],
+    },
+    Puzzle {
+        id: "y24d04",
+        implementations: &[Implementation {
+            name: DEFAULT,
+            solve: |input, _| {
+                Answers::new(y24d04::y24d04p1(input), y24d04::y24d04p2(input))
+            },
+        }],
+    },
+    Puzzle {
+        id: "y24d05",
+        implementations: &[Implementation {
+            name: DEFAULT,
+            solve: |input, _| {
+                Answers::new(y24d05::y24d05(input, 1), y24d05::y24d05(input, 2))
+            },
+        }],
+    },
+    Puzzle {
+        id: "y24d06",
+        implementations: &[Implementation {
+            name: DEFAULT,
+            solve: |input, _| {
+                Answers::new(y24d06::y24d06(input, 1), y24d06::y24d06(input, 2))
+            },
+        }],
+    },
+    // 2025
+    Puzzle {
+        id: "y25d01",
+        implementations: &[Implementation {
+            name: DEFAULT,
+            solve: |input, _| {
+                Answers::new(y25d01::y25d01(input, 1), y25d01::y25d01(input, 2))
+            },
+        }],
+    },
+    Puzzle {
+        id: "y25d02",
+        implementations: &[Implementation {
+            name: DEFAULT,
+            solve: |input, _| {
+                Answers::new(y25d02::y25d02(input, 1), y25d02::y25d02(input, 2))
+            },
+        }],
+    },
+    Puzzle {
+        id: "y25d03",
+        implementations: &[Implementation {
+            name: DEFAULT,
+            solve: |input, _| {
+                Answers::new(
+                    y25d03::y25d03(input, 2),
+                    y25d03::y25d03(input, 12),
+                )
+            },
+        }],
+    },
+    Puzzle {
+        id: "y25d04",
+        implementations: &[Implementation {
+            name: DEFAULT,
+            solve: |input, _| {
+                Answers::new(y25d04::y25d04(input, 1), y25d04::y25d04(input, 2))
+            },
+        }],
+    },
+    Puzzle {
+        id: "y25d05",
+        implementations: &[Implementation {
+            name: DEFAULT,
+            solve: |input, _| {
+                Answers::new(y25d05::y25d05p1(input), y25d05::y25d05p2(input))
+            },
+        }],
+    },
+    Puzzle {
+        id: "y25d06",
+        implementations: &[Implementation {
+            name: DEFAULT,
+            solve: |input, _| Answers::new(y25d06::y25d06(input), ""),
+        }],
+    },
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_find() {
+        assert_eq!(find("y15d01").unwrap().id, "y15d01");
+        assert!(find("y15d00").is_none());
+
+        for puzzle in PUZZLES {
+            assert_eq!(puzzle.implementations[0].name, DEFAULT);
+        }
+    }
+
+    #[test]
+    fn test_cross_check() {
+        let options = Options::default();
+
+        for (id, path) in [
+            ("y15d02", "input/2015/day02.txt"),
+            ("y22d12", "input/2022/day12.txt"),
+        ] {
+            let contents = fs::read_to_string(path).unwrap();
+            assert!(find(id).unwrap().cross_check(&contents, &options).is_ok());
+        }
+
+        let puzzle = Puzzle {
+            id: "y00d00",
+            implementations: &[
+                Implementation {
+                    name: DEFAULT,
+                    solve: |input, _| Answers::new(input.len(), 0),
+                },
+                Implementation {
+                    name: "wrong",
+                    solve: |_, _| Answers::new(0, 0),
+                },
+            ],
+        };
+
+        assert_eq!(
+            puzzle.cross_check("abc", &options),
+            Err(Box::new(Disagreement {
+                expected: (DEFAULT, Answers::new(3, 0)),
+                actual: ("wrong", Answers::new(0, 0)),
+            }))
+        );
+    }
+}
@@ -0,0 +1,667 @@
+/* Copyright 2025 Mario Finelli
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Frame-based visualizations for Advent of Code.
+//!
+//! Some of the puzzles are simulations that are a lot more fun to watch than
+//! to just get a number out of. Solvers that support it take a [`Render`]
+//! implementation and offer it a [`Frame`] every time that something
+//! interesting happens, but they only build the frame if the renderer
+//! [wants it](Render::wants_frame). The regular solver functions just pass
+//! [`NoRender`] which never wants any, and since the solvers are generic the
+//! compiler can throw the whole thing away. The command line always hands
+//! out a `Box<dyn Render>` though (it doesn't know which renderer it needs
+//! until it runs), so there each check is a (cheap) dynamic call but the
+//! frames still never get built for nothing.
+//!
+//! There are a few backends: [`Terminal`] animates the frames in the terminal
+//! (using half-block characters so that each character cell is two pixels),
+//! [`PpmSequence`] and [`PngSequence`] write each frame to its own file, and
+//! [`Gif`] writes all of the frames to a single animated image. Some of the
+//! simulations produce a _lot_ of frames so [`Sampled`] can be used to only
+//! keep every `n`th one.
+
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant};
+use termcolor::{
+    Buffer, BufferWriter, Color, ColorChoice, ColorSpec, WriteColor,
+};
+
+/// A single pixel color as red, green, and blue components.
+pub type Rgb = [u8; 3];
+
+/// A single image to be rendered.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Frame {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Frame {
+    /// Creates a new frame with every pixel set to the given color.
+    pub fn new(width: usize, height: usize, background: Rgb) -> Frame {
+        Frame {
+            width,
+            height,
+            pixels: vec![background; width * height],
+        }
+    }
+
+    /// Creates a new frame by calling the given function for each pixel.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use aoc::render::Frame;
+    /// let frame = Frame::from_fn(2, 2, |x, y| [x as u8, y as u8, 0]);
+    /// assert_eq!(frame.get(1, 0), Some([1, 0, 0]));
+    /// ```
+    pub fn from_fn<F>(width: usize, height: usize, f: F) -> Frame
+    where
+        F: Fn(usize, usize) -> Rgb,
+    {
+        let mut pixels = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                pixels.push(f(x, y));
+            }
+        }
+
+        Frame {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    /// Returns the width of the frame in pixels.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the height of the frame in pixels.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns the color of the given pixel or `None` if it's out of bounds.
+    pub fn get(&self, x: usize, y: usize) -> Option<Rgb> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+
+        Some(self.pixels[y * self.width + x])
+    }
+
+    /// Sets the color of the given pixel, pixels that are out of bounds are
+    /// silently ignored.
+    pub fn set(&mut self, x: usize, y: usize, color: Rgb) {
+        if x < self.width && y < self.height {
+            self.pixels[y * self.width + x] = color;
+        }
+    }
+
+    /// Returns the frame as a (binary) `PPM` image with each pixel blown up
+    /// into a `scale` by `scale` square.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use aoc::render::Frame;
+    /// let ppm = Frame::new(2, 1, [1, 2, 3]).to_ppm(1);
+    /// assert_eq!(ppm, b"P6\n2 1\n255\n\x01\x02\x03\x01\x02\x03");
+    /// ```
+    pub fn to_ppm(&self, scale: usize) -> Vec<u8> {
+        let scale = scale.max(1);
+        let mut ppm = format!(
+            "P6\n{} {}\n255\n",
+            self.width * scale,
+            self.height * scale
+        )
+        .into_bytes();
+        ppm.extend(self.to_rgb_bytes(scale));
+        ppm
+    }
+
+    /// Returns the raw `RGB` bytes of the frame with each pixel blown up into
+    /// a `scale` by `scale` square.
+    fn to_rgb_bytes(&self, scale: usize) -> Vec<u8> {
+        let mut bytes =
+            Vec::with_capacity(self.width * self.height * scale * scale * 3);
+
+        for y in 0..self.height * scale {
+            for x in 0..self.width * scale {
+                bytes.extend_from_slice(
+                    &self.pixels[y / scale * self.width + x / scale],
+                );
+            }
+        }
+
+        bytes
+    }
+}
+
+/// Something that can display (or save) the frames of a simulation.
+pub trait Render {
+    /// Returns whether the frames are actually going anywhere. Solvers should
+    /// check this before doing any setup that's only needed for the frames
+    /// (see [`Render::wants_frame`] for the frames themselves).
+    fn enabled(&self) -> bool {
+        true
+    }
+
+    /// Returns whether the next frame is actually going to be used (e.g.,
+    /// [`Sampled`] only keeps some of them). Solvers should check this
+    /// before building each frame and call [`Render::skip`] instead of
+    /// [`Render::frame`] if it isn't, [`Render::frame_with`] does both.
+    fn wants_frame(&self) -> bool {
+        self.enabled()
+    }
+
+    /// Displays (or saves) the next frame.
+    fn frame(&mut self, frame: &Frame);
+
+    /// Called instead of [`Render::frame`] when the next frame wasn't wanted
+    /// so it wasn't built.
+    fn skip(&mut self) {}
+
+    /// Called once there are no more frames to make sure that everything has
+    /// been written out. `last` builds the final state of the simulation, for
+    /// renderers that skipped it but still want to show it.
+    fn finish(&mut self, _last: &dyn Fn() -> Frame) {}
+
+    /// Builds the next frame and displays it, but only if it's wanted.
+    fn frame_with(&mut self, build: impl FnOnce() -> Frame)
+    where
+        Self: Sized,
+    {
+        if self.wants_frame() {
+            self.frame(&build());
+        } else {
+            self.skip();
+        }
+    }
+}
+
+impl<R: Render + ?Sized> Render for Box<R> {
+    fn enabled(&self) -> bool {
+        (**self).enabled()
+    }
+
+    fn wants_frame(&self) -> bool {
+        (**self).wants_frame()
+    }
+
+    fn frame(&mut self, frame: &Frame) {
+        (**self).frame(frame)
+    }
+
+    fn skip(&mut self) {
+        (**self).skip()
+    }
+
+    fn finish(&mut self, last: &dyn Fn() -> Frame) {
+        (**self).finish(last)
+    }
+}
+
+/// The renderer that doesn't render anything (used for normal solving).
+pub struct NoRender;
+
+impl Render for NoRender {
+    fn enabled(&self) -> bool {
+        false
+    }
+
+    fn frame(&mut self, _frame: &Frame) {}
+}
+
+/// Only passes every `n`th frame on to another renderer.
+///
+/// It only [wants](Render::wants_frame) the frames that it's going to keep
+/// so the rest are never even built. The final state of the simulation is
+/// never lost though: if the last frame was skipped then it's built (by the
+/// solver) and passed on when [`Render::finish`] is called.
+pub struct Sampled<R: Render> {
+    inner: R,
+    every: usize,
+    count: usize,
+    behind: bool,
+}
+
+impl<R: Render> Sampled<R> {
+    /// Wraps the given renderer to only receive every `n`th frame.
+    pub fn new(inner: R, every: usize) -> Sampled<R> {
+        Sampled {
+            inner,
+            every: every.max(1),
+            count: 0,
+            behind: false,
+        }
+    }
+
+    /// Returns the renderer that the frames are passed on to.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Render> Render for Sampled<R> {
+    fn enabled(&self) -> bool {
+        self.inner.enabled()
+    }
+
+    fn wants_frame(&self) -> bool {
+        self.inner.enabled() && self.count.is_multiple_of(self.every)
+    }
+
+    fn frame(&mut self, frame: &Frame) {
+        if self.count.is_multiple_of(self.every) {
+            self.inner.frame(frame);
+            self.behind = false;
+        } else {
+            self.behind = true;
+        }
+
+        self.count += 1;
+    }
+
+    fn skip(&mut self) {
+        self.behind = true;
+        self.count += 1;
+    }
+
+    fn finish(&mut self, last: &dyn Fn() -> Frame) {
+        if self.behind && self.inner.enabled() {
+            self.inner.frame(&last());
+            self.behind = false;
+        }
+
+        self.inner.finish(last);
+    }
+}
+
+/// Animates the frames in the terminal.
+///
+/// Each character cell is used for two pixels (one on top of the other) by
+/// printing the "upper half block" character with the top pixel as the
+/// foreground color and the bottom pixel as the background color. Each frame
+/// is drawn over the previous one.
+pub struct Terminal {
+    writer: BufferWriter,
+    delay: Duration,
+    last: Option<Instant>,
+    lines: usize,
+}
+
+impl Terminal {
+    /// Creates a new terminal renderer that draws at most `fps` frames per
+    /// second.
+    pub fn new(fps: u32) -> Terminal {
+        Terminal {
+            writer: BufferWriter::stdout(ColorChoice::Auto),
+            delay: Duration::from_secs(1) / fps.max(1),
+            last: None,
+            lines: 0,
+        }
+    }
+
+    /// Draws the frame into the given buffer.
+    fn draw(frame: &Frame, buffer: &mut Buffer) -> std::io::Result<()> {
+        let mut spec = ColorSpec::new();
+
+        for y in (0..frame.height()).step_by(2) {
+            for x in 0..frame.width() {
+                let [r, g, b] = frame.get(x, y).unwrap();
+                spec.set_fg(Some(Color::Rgb(r, g, b)));
+                spec.set_bg(
+                    frame.get(x, y + 1).map(|[r, g, b]| Color::Rgb(r, g, b)),
+                );
+                buffer.set_color(&spec)?;
+                write!(buffer, "▀")?;
+            }
+
+            buffer.reset()?;
+            writeln!(buffer)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Render for Terminal {
+    fn frame(&mut self, frame: &Frame) {
+        let mut buffer = self.writer.buffer();
+
+        if self.lines > 0 {
+            // move back up to draw over the previous frame
+            write!(buffer, "\x1b[{}A", self.lines).unwrap();
+        }
+
+        Terminal::draw(frame, &mut buffer).unwrap();
+        self.lines = frame.height().div_ceil(2);
+
+        if let Some(last) = self.last {
+            let elapsed = last.elapsed();
+            if elapsed < self.delay {
+                thread::sleep(self.delay - elapsed);
+            }
+        }
+
+        self.writer.print(&buffer).unwrap();
+        self.last = Some(Instant::now());
+    }
+}
+
+/// Writes each frame to its own (binary) `PPM` file.
+///
+/// The files are named `{prefix}-{frame}.ppm` (with the frame number padded
+/// to five digits) in the given directory.
+pub struct PpmSequence {
+    dir: PathBuf,
+    prefix: String,
+    scale: usize,
+    count: usize,
+}
+
+impl PpmSequence {
+    /// Creates a new `PPM` sequence writer, creating the output directory if
+    /// it doesn't already exist.
+    pub fn new(dir: &Path, prefix: &str, scale: usize) -> PpmSequence {
+        fs::create_dir_all(dir).unwrap();
+
+        PpmSequence {
+            dir: dir.to_path_buf(),
+            prefix: prefix.to_string(),
+            scale: scale.max(1),
+            count: 0,
+        }
+    }
+}
+
+impl Render for PpmSequence {
+    fn frame(&mut self, frame: &Frame) {
+        let path = sequence_path(&self.dir, &self.prefix, self.count, "ppm");
+        fs::write(path, frame.to_ppm(self.scale)).unwrap();
+
+        self.count += 1;
+    }
+}
+
+/// Writes each frame to its own `PNG` file.
+///
+/// The files are named `{prefix}-{frame}.png` (with the frame number padded
+/// to five digits) in the given directory.
+pub struct PngSequence {
+    dir: PathBuf,
+    prefix: String,
+    scale: usize,
+    count: usize,
+}
+
+impl PngSequence {
+    /// Creates a new `PNG` sequence writer, creating the output directory if
+    /// it doesn't already exist.
+    pub fn new(dir: &Path, prefix: &str, scale: usize) -> PngSequence {
+        fs::create_dir_all(dir).unwrap();
+
+        PngSequence {
+            dir: dir.to_path_buf(),
+            prefix: prefix.to_string(),
+            scale: scale.max(1),
+            count: 0,
+        }
+    }
+}
+
+impl Render for PngSequence {
+    fn frame(&mut self, frame: &Frame) {
+        let path = sequence_path(&self.dir, &self.prefix, self.count, "png");
+        let file = BufWriter::new(File::create(path).unwrap());
+
+        let mut encoder = png::Encoder::new(
+            file,
+            (frame.width() * self.scale).try_into().unwrap(),
+            (frame.height() * self.scale).try_into().unwrap(),
+        );
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        let mut writer = encoder.write_header().unwrap();
+        writer
+            .write_image_data(&frame.to_rgb_bytes(self.scale))
+            .unwrap();
+
+        self.count += 1;
+    }
+}
+
+/// Writes all of the frames into a single (looping) animated `GIF`.
+///
+/// The size of the animation is determined by the first frame, all of the
+/// frames after that need to be the same size.
+pub struct Gif {
+    path: PathBuf,
+    delay: u16,
+    scale: usize,
+    encoder: Option<gif::Encoder<BufWriter<File>>>,
+}
+
+impl Gif {
+    /// Creates a new animated `GIF` writer that plays at (roughly) the given
+    /// number of frames per second.
+    pub fn new(path: &Path, fps: u32, scale: usize) -> Gif {
+        Gif {
+            path: path.to_path_buf(),
+            // the frame delay is in hundredths of a second
+            delay: (100 / fps.clamp(1, 100)).try_into().unwrap(),
+            scale: scale.max(1),
+            encoder: None,
+        }
+    }
+}
+
+impl Render for Gif {
+    fn frame(&mut self, frame: &Frame) {
+        let width: u16 = (frame.width() * self.scale).try_into().unwrap();
+        let height: u16 = (frame.height() * self.scale).try_into().unwrap();
+
+        let encoder = self.encoder.get_or_insert_with(|| {
+            let file = BufWriter::new(File::create(&self.path).unwrap());
+            let mut encoder =
+                gif::Encoder::new(file, width, height, &[]).unwrap();
+            encoder.set_repeat(gif::Repeat::Infinite).unwrap();
+            encoder
+        });
+
+        let mut gif_frame = gif::Frame::from_rgb_speed(
+            width,
+            height,
+            &frame.to_rgb_bytes(self.scale),
+            10,
+        );
+        gif_frame.delay = self.delay;
+        encoder.write_frame(&gif_frame).unwrap();
+    }
+
+    fn finish(&mut self, _last: &dyn Fn() -> Frame) {
+        // dropping the encoder writes the trailer
+        self.encoder = None;
+    }
+}
+
+/// Builds a renderer from a textual description (e.g., from the command
+/// line).
+///
+/// The description is one of `terminal[:FPS]`, `ppm:DIR`, `png:DIR`, or
+/// `gif:FILE`. The `label` is used to keep the output of different runs
+/// apart: it's the filename prefix for the image sequences and it's added to
+/// the name of the `GIF` file. Returns `None` if the description isn't
+/// understood.
+///
+/// # Example
+/// ```rust
+/// # use aoc::render::from_spec;
+/// assert!(from_spec("terminal:30", "part1").is_some());
+/// assert!(from_spec("mp4:out.mp4", "part1").is_none());
+/// ```
+pub fn from_spec(spec: &str, label: &str) -> Option<Box<dyn Render>> {
+    let (kind, arg) = match spec.split_once(':') {
+        Some((kind, arg)) => (kind, Some(arg)),
+        None => (spec, None),
+    };
+
+    match (kind, arg) {
+        ("terminal", None) => Some(Box::new(Terminal::new(DEFAULT_FPS))),
+        ("terminal", Some(fps)) => {
+            Some(Box::new(Terminal::new(fps.parse().ok()?)))
+        }
+        ("ppm", Some(dir)) => {
+            Some(Box::new(PpmSequence::new(Path::new(dir), label, SCALE)))
+        }
+        ("png", Some(dir)) => {
+            Some(Box::new(PngSequence::new(Path::new(dir), label, SCALE)))
+        }
+        ("gif", Some(file)) => {
+            let path = Path::new(file);
+            let stem = path.file_stem()?.to_string_lossy();
+            let path = path.with_file_name(format!("{}-{}.gif", stem, label));
+            Some(Box::new(Gif::new(&path, DEFAULT_FPS, SCALE)))
+        }
+        _ => None,
+    }
+}
+
+/// The default number of frames per second for animations.
+const DEFAULT_FPS: u32 = 30;
+
+/// The default scale for image output (grid puzzles tend to be quite small).
+const SCALE: usize = 4;
+
+/// Returns the path of the given frame number in an image sequence.
+fn sequence_path(dir: &Path, prefix: &str, count: usize, ext: &str) -> PathBuf {
+    dir.join(format!("{}-{:05}.{}", prefix, count, ext))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::{env, process};
+
+    /// Collects all of the frames that it receives.
+    struct Collect(Vec<Frame>);
+
+    impl Render for Collect {
+        fn frame(&mut self, frame: &Frame) {
+            self.0.push(frame.clone());
+        }
+    }
+
+    #[test]
+    fn test_frame() {
+        let mut frame = Frame::new(2, 1, [0, 0, 0]);
+        frame.set(1, 0, [1, 2, 3]);
+        frame.set(5, 5, [1, 2, 3]);
+
+        assert_eq!(frame.get(0, 0), Some([0, 0, 0]));
+        assert_eq!(frame.get(1, 0), Some([1, 2, 3]));
+        assert_eq!(frame.get(0, 1), None);
+        assert_eq!(
+            frame.to_rgb_bytes(2),
+            vec![
+                0, 0, 0, 0, 0, 0, 1, 2, 3, 1, 2, 3, //
+                0, 0, 0, 0, 0, 0, 1, 2, 3, 1, 2, 3,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sampled() {
+        let built = Cell::new(0);
+        let frame = |i| {
+            built.set(built.get() + 1);
+            Frame::new(1, 1, [i, i, i])
+        };
+
+        for (count, expected, frames) in
+            [(5, vec![0, 3, 4], 3), (7, vec![0, 3, 6], 3), (0, vec![], 0)]
+        {
+            built.set(0);
+            let mut render = Sampled::new(Collect(Vec::new()), 3);
+            for i in 0..count {
+                render.frame_with(|| frame(i));
+            }
+            render.finish(&|| frame(count - 1));
+
+            let seen: Vec<_> = render
+                .inner
+                .0
+                .iter()
+                .map(|f| f.get(0, 0).unwrap()[0])
+                .collect();
+            assert_eq!(seen, expected);
+            // only the frames that are kept are ever built
+            assert_eq!(built.get(), frames);
+        }
+
+        // frames that are passed in anyway are still sampled
+        let mut render = Sampled::new(Collect(Vec::new()), 2);
+        for i in 0..4 {
+            render.frame(&frame(i));
+        }
+        render.finish(&|| frame(9));
+        assert_eq!(render.inner.0.len(), 3);
+
+        // nothing is built when the frames aren't going anywhere
+        built.set(0);
+        let mut render = Sampled::new(NoRender, 1);
+        render.frame_with(|| frame(0));
+        render.finish(&|| frame(0));
+        assert_eq!(built.get(), 0);
+    }
+
+    #[test]
+    fn test_files() {
+        // use the process id so that concurrent test runs don't trample each
+        // other's files
+        let dir = env::temp_dir().join(format!("aoc-render-{}", process::id()));
+        let frame = Frame::from_fn(3, 2, |x, y| [x as u8 * 100, y as u8, 0]);
+
+        let mut ppm = PpmSequence::new(&dir, "test", 1);
+        ppm.frame(&frame);
+        ppm.frame(&frame);
+        let contents = fs::read(dir.join("test-00001.ppm")).unwrap();
+        assert!(contents.starts_with(b"P6\n3 2\n255\n"));
+        assert_eq!(contents.len(), 11 + 3 * 2 * 3);
+
+        let mut png = PngSequence::new(&dir, "test", 2);
+        png.frame(&frame);
+        let contents = fs::read(dir.join("test-00000.png")).unwrap();
+        assert!(contents.starts_with(b"\x89PNG"));
+
+        let mut gif = Gif::new(&dir.join("test.gif"), 10, 1);
+        gif.frame(&frame);
+        gif.frame(&frame);
+        gif.finish(&|| frame.clone());
+        let contents = fs::read(dir.join("test.gif")).unwrap();
+        assert!(contents.starts_with(b"GIF89a"));
+        assert_eq!(contents.last(), Some(&0x3b));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,510 @@
+/* Copyright 2025 Mario Finelli
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! A tiny HTTP server to solve puzzles over a REST API.
+//!
+//! This lets other tools call the solutions without shelling out to the
+//! binary and parsing its (colored) output. It's built directly on
+//! [`std::net`] and only understands just enough HTTP/1.1 for the two
+//! endpoints that it offers:
+//!
+//! - `GET /puzzles` lists all of the puzzles in the
+//!   [registry](crate::registry) with their titles and implementations.
+//! - `POST /solve/{year}/{day}` solves the puzzle using the request body as
+//!   the input and returns the answers and how long it took to solve them.
+//!   An implementation other than the default one can be picked with
+//!   `?impl=NAME`.
+//!
+//! Every connection is handled on its own thread and only answers a single
+//! request. Responses are always JSON, errors look like
+//! `{"error": "message"}`. So that nobody can tie up the server there are
+//! limits on everything that a client controls: lines longer than
+//! [`MAX_LINE`], more than [`MAX_HEADERS`] headers, and bodies larger than
+//! [`MAX_BODY`] are refused, clients that stop sending for longer than
+//! [`TIMEOUT`] or take longer than [`DEADLINE`] for the whole request are
+//! dropped, and at most [`MAX_CONNECTIONS`] connections are handled at once
+//! (the rest are turned away right away).
+
+use crate::registry::{self, Options};
+use crate::title;
+use serde_json::{json, Value};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{Shutdown, TcpListener, TcpStream};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// The largest request body (in bytes) that we're willing to read.
+pub const MAX_BODY: usize = 8 * 1024 * 1024;
+
+/// The longest request line or header (in bytes) that we're willing to read.
+pub const MAX_LINE: u64 = 8 * 1024;
+
+/// The most headers that we're willing to read.
+pub const MAX_HEADERS: usize = 100;
+
+/// How long we wait for a client to send more of its request.
+pub const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How long a client has to send its whole request.
+pub const DEADLINE: Duration = Duration::from_secs(30);
+
+/// The most connections that we handle at the same time.
+pub const MAX_CONNECTIONS: usize = 64;
+
+/// How long we keep reading (and throwing away) what a client is still
+/// sending after we've responded.
+const LINGER: Duration = Duration::from_secs(1);
+
+/// Reads from a stream until the deadline for the request passes, waiting at
+/// most [`TIMEOUT`] for each read.
+struct Deadline<'a> {
+    stream: &'a TcpStream,
+    until: Instant,
+}
+
+/// Frees up a connection slot when the connection is done (even if handling
+/// it panicked).
+struct Slot(Arc<AtomicUsize>);
+
+/// Accepts connections on the given listener forever, handling each one on
+/// its own thread.
+pub fn serve(listener: TcpListener) {
+    let active = Arc::new(AtomicUsize::new(0));
+
+    for stream in listener.incoming() {
+        match stream {
+            Ok(mut stream) => {
+                if let Err(e) = stream.set_write_timeout(Some(TIMEOUT)) {
+                    eprintln!("failed to set write timeout: {}", e);
+                    continue;
+                }
+
+                if active.fetch_add(1, Ordering::SeqCst) >= MAX_CONNECTIONS {
+                    active.fetch_sub(1, Ordering::SeqCst);
+                    let error = json!({"error": "too many connections"});
+                    respond(&mut stream, 503, &error);
+                    continue;
+                }
+
+                let slot = Slot(Arc::clone(&active));
+                thread::spawn(move || {
+                    handle(stream);
+                    drop(slot);
+                });
+            }
+            Err(e) => eprintln!("failed to accept connection: {}", e),
+        }
+    }
+}
+
+/// Reads a single request from the stream and writes back the response.
+fn handle(mut stream: TcpStream) {
+    let (status, body) = match read_request(&stream) {
+        Ok((method, path, body)) => route(&method, &path, &body),
+        Err(408) => (408, json!({"error": "request timed out"})),
+        Err(413) => (413, json!({"error": "request body too large"})),
+        Err(431) => (431, json!({"error": "request headers too large"})),
+        Err(status) => (status, json!({"error": "malformed request"})),
+    };
+
+    respond(&mut stream, status, &body);
+
+    // if we gave up on the request part way through then the client might
+    // still be sending it, and closing with unread data would reset the
+    // connection (possibly before the client gets to read our response)
+    let _ = stream.shutdown(Shutdown::Write);
+    let rest = Deadline {
+        stream: &stream,
+        until: Instant::now() + LINGER,
+    };
+    let _ = io::copy(&mut rest.take(MAX_BODY as u64), &mut io::sink());
+}
+
+/// Writes the response with the given status code and body.
+fn respond(stream: &mut TcpStream, status: u16, body: &Value) {
+    let body = body.to_string();
+    let response = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\n\
+         Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        reason(status),
+        body.len(),
+        body
+    );
+
+    // there's nothing that we can do if the client already went away
+    let _ = stream.write_all(response.as_bytes());
+}
+
+/// Reads the request line, headers and body of a request and returns the
+/// method, path, and body.
+///
+/// # Errors
+/// Returns the status code to respond with if the request is malformed
+/// (`400`), the client is too slow (`408`), its body is larger than
+/// [`MAX_BODY`] (`413`), or a line is longer than [`MAX_LINE`] or there are
+/// more than [`MAX_HEADERS`] headers (`431`). The sizes are checked before
+/// anything is allocated for them.
+fn read_request(stream: &TcpStream) -> Result<(String, String, String), u16> {
+    let mut reader = BufReader::new(Deadline {
+        stream,
+        until: Instant::now() + DEADLINE,
+    });
+
+    let request_line = read_line(&mut reader)?;
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().ok_or(400u16)?.to_string();
+    let path = parts.next().ok_or(400u16)?.to_string();
+
+    let mut content_length = 0;
+    for headers in 0.. {
+        let header = read_line(&mut reader)?;
+        let header = header.trim_end();
+
+        if header.is_empty() {
+            break;
+        }
+
+        if headers == MAX_HEADERS {
+            return Err(431);
+        }
+
+        let (name, value) = header.split_once(':').ok_or(400u16)?;
+        if name.eq_ignore_ascii_case("content-length") {
+            content_length = value.trim().parse().map_err(|_| 400u16)?;
+        }
+    }
+
+    if content_length > MAX_BODY {
+        return Err(413);
+    }
+
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body).map_err(status)?;
+
+    let body = String::from_utf8(body).map_err(|_| 400u16)?;
+    Ok((method, path, body))
+}
+
+/// Reads a single line (including the newline) of at most [`MAX_LINE`]
+/// bytes.
+///
+/// # Errors
+/// Returns the status code to respond with if the line is too long (`431`),
+/// the client is too slow (`408`), or the line isn't finished or isn't valid
+/// UTF-8 (`400`).
+fn read_line(reader: &mut impl BufRead) -> Result<String, u16> {
+    let mut line = Vec::new();
+    reader
+        .by_ref()
+        .take(MAX_LINE)
+        .read_until(b'\n', &mut line)
+        .map_err(status)?;
+
+    if line.last() != Some(&b'\n') {
+        return Err(if line.len() as u64 == MAX_LINE {
+            431
+        } else {
+            400
+        });
+    }
+
+    String::from_utf8(line).map_err(|_| 400)
+}
+
+/// Returns the status code to respond with when reading the request fails.
+fn status(error: io::Error) -> u16 {
+    match error.kind() {
+        io::ErrorKind::TimedOut | io::ErrorKind::WouldBlock => 408,
+        _ => 400,
+    }
+}
+
+impl Read for Deadline<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let left = self.until.saturating_duration_since(Instant::now());
+        if left.is_zero() {
+            return Err(io::ErrorKind::TimedOut.into());
+        }
+
+        self.stream.set_read_timeout(Some(left.min(TIMEOUT)))?;
+        self.stream.read(buf)
+    }
+}
+
+impl Drop for Slot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Figures out what to do with a request and returns the status code and
+/// response body.
+fn route(method: &str, path: &str, body: &str) -> (u16, Value) {
+    let (path, query) = path.split_once('?').unwrap_or((path, ""));
+    let segments: Vec<_> = path.trim_matches('/').split('/').collect();
+
+    match (method, segments.as_slice()) {
+        ("GET", ["puzzles"]) => (200, puzzles()),
+        ("POST", ["solve", year, day]) => {
+            let implementation = query
+                .split('&')
+                .find_map(|param| param.strip_prefix("impl="));
+            solve(year, day, implementation, body)
+        }
+        (_, ["puzzles"]) | (_, ["solve", _, _]) => {
+            (405, json!({"error": "method not allowed"}))
+        }
+        _ => (404, json!({"error": "not found"})),
+    }
+}
+
+/// Lists all of the puzzles that we know how to solve.
+fn puzzles() -> Value {
+    registry::PUZZLES
+        .iter()
+        .map(|puzzle| {
+            json!({
+                "id": puzzle.id,
+                "year": 2000 + puzzle.id[1..3].parse::<u32>().unwrap(),
+                "day": puzzle.id[4..6].parse::<u32>().unwrap(),
+                "title": title::title(puzzle.id),
+                "implementations": puzzle
+                    .implementations
+                    .iter()
+                    .map(|i| i.name)
+                    .collect::<Vec<_>>(),
+            })
+        })
+        .collect()
+}
+
+/// Solves the given puzzle and returns the answers along with how long it
+/// took (in microseconds).
+fn solve(
+    year: &str,
+    day: &str,
+    implementation: Option<&str>,
+    input: &str,
+) -> (u16, Value) {
+    let (Ok(year), Ok(day)) = (year.parse::<u32>(), day.parse::<u32>()) else {
+        return (400, json!({"error": "invalid year or day"}));
+    };
+
+    // the puzzles are all from this century, anything else would just
+    // happen to match one of them
+    if !(2000..2100).contains(&year) {
+        return (404, json!({"error": "unknown puzzle"}));
+    }
+
+    let id = format!("y{:02}d{:02}", year - 2000, day);
+    let Some(puzzle) = registry::find(&id) else {
+        return (404, json!({"error": "unknown puzzle"}));
+    };
+
+    let Some(implementation) = puzzle.implementation(implementation) else {
+        return (404, json!({"error": "unknown implementation"}));
+    };
+
+    let start = Instant::now();
+    let answers = panic::catch_unwind(AssertUnwindSafe(|| {
+        (implementation.solve)(input, &Options::default())
+    }));
+    let elapsed = start.elapsed();
+
+    match answers {
+        Ok(answers) => (
+            200,
+            json!({
+                "id": id,
+                "implementation": implementation.name,
+                "part1": answers.part1,
+                "part2": answers.part2,
+                "elapsed_us": elapsed.as_micros() as u64,
+            }),
+        ),
+        Err(_) => (500, json!({"error": "solver panicked"})),
+    }
+}
+
+/// Returns the reason phrase for the status codes that we use.
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        408 => "Request Timeout",
+        413 => "Content Too Large",
+        431 => "Request Header Fields Too Large",
+        503 => "Service Unavailable",
+        _ => "Internal Server Error",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Sends a request to the server and returns the status code and the
+    /// parsed response body.
+    fn request(
+        addr: &str,
+        method: &str,
+        path: &str,
+        body: &str,
+    ) -> (u16, Value) {
+        let mut stream = TcpStream::connect(addr).unwrap();
+        write!(
+            stream,
+            "{} {} HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\n\r\n{}",
+            method,
+            path,
+            body.len(),
+            body
+        )
+        .unwrap();
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+
+        let (head, body) = response.split_once("\r\n\r\n").unwrap();
+        let status = head.split_whitespace().nth(1).unwrap().parse().unwrap();
+        (status, serde_json::from_str(body).unwrap())
+    }
+
+    /// Sends a body-less request with the given header and returns the status
+    /// code and the parsed response body.
+    fn raw(addr: &str, header: &str) -> (u16, Value) {
+        let mut stream = TcpStream::connect(addr).unwrap();
+        write!(stream, "POST /solve/2015/1 HTTP/1.1\r\n{}\r\n\r\n", header)
+            .unwrap();
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+
+        let (head, body) = response.split_once("\r\n\r\n").unwrap();
+        let status = head.split_whitespace().nth(1).unwrap().parse().unwrap();
+        (status, serde_json::from_str(body).unwrap())
+    }
+
+    #[test]
+    fn it_works() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        thread::spawn(move || serve(listener));
+
+        let (status, body) = request(&addr, "GET", "/puzzles", "");
+        assert_eq!(status, 200);
+        assert_eq!(body[0]["id"], "y15d01");
+        assert_eq!(body[0]["year"], 2015);
+        assert_eq!(body[0]["day"], 1);
+        assert_eq!(body[0]["title"], "Not Quite Lisp");
+
+        let (status, body) = request(&addr, "POST", "/solve/2015/1", "(()))");
+        assert_eq!(status, 200);
+        assert_eq!(body["part1"], "-1");
+        assert_eq!(body["part2"], "5");
+        assert!(body["elapsed_us"].is_u64());
+
+        let (status, body) =
+            request(&addr, "POST", "/solve/2015/02?impl=naive", "2x3x4\n");
+        assert_eq!(status, 200);
+        assert_eq!(body["implementation"], "naive");
+        assert_eq!(body["part1"], "58");
+
+        let (status, _) = request(&addr, "POST", "/solve/2015/1?impl=x", "");
+        assert_eq!(status, 404);
+
+        let (status, _) = request(&addr, "POST", "/solve/1999/1", "");
+        assert_eq!(status, 404);
+
+        let (status, _) = request(&addr, "POST", "/solve/1915/1", "");
+        assert_eq!(status, 404);
+
+        let (status, _) = request(&addr, "POST", "/solve/2115/1", "");
+        assert_eq!(status, 404);
+
+        let (status, _) = request(&addr, "POST", "/solve/twenty/1", "");
+        assert_eq!(status, 400);
+
+        let (status, _) = request(&addr, "GET", "/solve/2015/1", "");
+        assert_eq!(status, 405);
+
+        let (status, _) = request(&addr, "GET", "/", "");
+        assert_eq!(status, 404);
+
+        let (status, _) = raw(&addr, "Content-Length: 99999999999999999999");
+        assert_eq!(status, 400);
+
+        let huge = format!("Content-Length: {}", usize::MAX);
+        let (status, body) = raw(&addr, &huge);
+        assert_eq!(status, 413);
+        assert_eq!(body["error"], "request body too large");
+
+        let long = format!("X-Long: {}", "a".repeat(MAX_LINE as usize));
+        let (status, body) = raw(&addr, &long);
+        assert_eq!(status, 431);
+        assert_eq!(body["error"], "request headers too large");
+
+        let many = vec!["X-Many: a"; MAX_HEADERS + 1].join("\r\n");
+        let (status, _) = raw(&addr, &many);
+        assert_eq!(status, 431);
+
+        let enough = vec!["X-Many: a"; MAX_HEADERS].join("\r\n");
+        let (status, _) = raw(&addr, &enough);
+        assert_eq!(status, 200);
+
+        // a present without any dimensions can't be wrapped
+        let (status, body) = request(&addr, "POST", "/solve/2015/2", "x\n");
+        assert_eq!(status, 500);
+        assert_eq!(body["error"], "solver panicked");
+    }
+
+    #[test]
+    fn test_max_connections() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        thread::spawn(move || serve(listener));
+
+        // these never send anything so they hold on to their slots
+        let idle: Vec<_> = (0..MAX_CONNECTIONS)
+            .map(|_| TcpStream::connect(&addr).unwrap())
+            .collect();
+
+        // the server answers without reading anything so don't send a
+        // request (closing with unread data would reset the connection)
+        let mut response = String::new();
+        let mut stream = TcpStream::connect(&addr).unwrap();
+        stream.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 503 Service Unavailable"));
+        assert!(response.ends_with(r#"{"error":"too many connections"}"#));
+
+        // hanging up frees the slots again
+        drop(idle);
+        let mut status = 503;
+        for _ in 0..100 {
+            (status, _) = request(&addr, "GET", "/puzzles", "");
+            if status != 503 {
+                break;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(status, 200);
+    }
+}
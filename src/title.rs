@@ -99,7 +99,6 @@ pub fn print_stats(
 ) {
     let dimmed_red = dimmed_red_colorspec();
     let dimmed_white = dimmed_white_colorspec();
-    let time = duration.as_micros();
 
     writeln!(stdout).unwrap();
 
@@ -107,17 +106,7 @@ pub fn print_stats(
     write!(stdout, "Elapsed time: ").unwrap();
     stdout.reset().unwrap();
     stdout.set_color(&dimmed_red).unwrap();
-
-    if time < 10000 {
-        write!(stdout, "{}μs", time).unwrap();
-    } else if time <= 5000000 {
-        write!(stdout, "{}ms", duration.as_millis()).unwrap();
-    } else if time <= 600000000 {
-        write!(stdout, "{}s", duration.as_secs()).unwrap();
-    } else {
-        write!(stdout, "{}m", duration.as_secs() / 60).unwrap();
-    }
-
+    write!(stdout, "{}", format_duration(duration)).unwrap();
     stdout.reset().unwrap();
     writeln!(stdout).unwrap();
 
@@ -140,8 +129,46 @@ pub fn print_stats(
     writeln!(stdout).unwrap();
 }
 
-/// Returns the title of the puzzle for the given year and day.
-fn title(yd: &str) -> &str {
+/// Prints how long a single implementation of a puzzle took to run (for
+/// comparing implementations against each other).
+pub fn print_timing(
+    stdout: &mut StandardStream,
+    name: &str,
+    duration: Duration,
+) {
+    let dimmed_red = dimmed_red_colorspec();
+    let dimmed_white = dimmed_white_colorspec();
+
+    stdout.set_color(&dimmed_white).unwrap();
+    write!(stdout, "{:>12}: ", name).unwrap();
+    stdout.reset().unwrap();
+    stdout.set_color(&dimmed_red).unwrap();
+    write!(stdout, "{}", format_duration(duration)).unwrap();
+    stdout.reset().unwrap();
+    writeln!(stdout).unwrap();
+}
+
+/// Formats a duration using the most appropriate unit.
+fn format_duration(duration: Duration) -> String {
+    let time = duration.as_micros();
+
+    if time < 10000 {
+        format!("{}μs", time)
+    } else if time <= 5000000 {
+        format!("{}ms", duration.as_millis())
+    } else if time <= 600000000 {
+        format!("{}s", duration.as_secs())
+    } else {
+        format!("{}m", duration.as_secs() / 60)
+    }
+}
+
+/// Returns the title of the puzzle for the given year and day (e.g.,
+/// `y15d01`).
+///
+/// # Panics
+/// If the puzzle is unknown.
+pub fn title(yd: &str) -> &str {
     match yd {
         // 2015
         "y15d01" => "Not Quite Lisp",
@@ -15,6 +15,10 @@
 
 //! Utility functions for Advent of Code.
 
+pub mod big;
+pub mod ocr;
+pub mod tour;
+
 use std::ops::{Div, Mul, Rem};
 
 /// Compute the greatest common divisor.
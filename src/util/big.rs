@@ -0,0 +1,257 @@
+/* Copyright 2025 Mario Finelli
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Natural numbers that can get as big as we need them to.
+//!
+//! A few puzzles (e.g., [`crate::y15d10`] and [`crate::y15d17`]) count things
+//! that outgrow even a `u128` as soon as the input gets a little bigger than
+//! the one in the prompt. All that we ever need to do with those counts is
+//! add them up and multiply them by small numbers, so instead of pulling in a
+//! whole big integer library we just store the digits in base `2^64` (least
+//! significant first) and do it by hand like on paper.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{AddAssign, MulAssign};
+
+/// A natural number (including zero) of any size.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Natural {
+    // base 2^64 digits, least significant first and without any zeros at
+    // the end (so zero doesn't have any digits)
+    digits: Vec<u64>,
+}
+
+/// The largest power of ten that fits in a digit, for printing the number
+/// in decimal.
+const DECIMAL: u64 = 10_000_000_000_000_000_000;
+
+impl Natural {
+    /// Returns whether the number is zero.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use aoc::util::big::Natural;
+    /// assert!(Natural::default().is_zero());
+    /// assert!(!Natural::from(1).is_zero());
+    /// ```
+    pub fn is_zero(&self) -> bool {
+        self.digits.is_empty()
+    }
+
+    /// Returns the number as a `u128` if it fits in one.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use aoc::util::big::Natural;
+    /// let mut n = Natural::from(u64::MAX);
+    /// n *= u64::MAX;
+    /// assert_eq!(n.to_u128(), Some(u64::MAX as u128 * u64::MAX as u128));
+    /// n *= 2;
+    /// assert_eq!(n.to_u128(), None);
+    /// ```
+    pub fn to_u128(&self) -> Option<u128> {
+        match self.digits[..] {
+            [] => Some(0),
+            [low] => Some(low.into()),
+            [low, high] => Some(u128::from(high) << 64 | u128::from(low)),
+            _ => None,
+        }
+    }
+
+    /// Divides the number by a (non-zero) digit in place and returns the
+    /// remainder.
+    fn div_rem(&mut self, divisor: u64) -> u64 {
+        let mut remainder = 0u128;
+
+        for digit in self.digits.iter_mut().rev() {
+            let current = remainder << 64 | u128::from(*digit);
+            *digit = (current / u128::from(divisor)) as u64;
+            remainder = current % u128::from(divisor);
+        }
+
+        while self.digits.last() == Some(&0) {
+            self.digits.pop();
+        }
+
+        remainder as u64
+    }
+}
+
+impl From<u64> for Natural {
+    fn from(n: u64) -> Self {
+        Self {
+            digits: if n == 0 { Vec::new() } else { vec![n] },
+        }
+    }
+}
+
+impl AddAssign<&Natural> for Natural {
+    fn add_assign(&mut self, other: &Natural) {
+        if self.digits.len() < other.digits.len() {
+            self.digits.resize(other.digits.len(), 0);
+        }
+
+        let mut carry = false;
+        for (i, digit) in self.digits.iter_mut().enumerate() {
+            if !carry && i >= other.digits.len() {
+                break;
+            }
+
+            let (sum, over) = digit
+                .overflowing_add(other.digits.get(i).copied().unwrap_or(0));
+            let (sum, carried) = sum.overflowing_add(carry.into());
+            *digit = sum;
+            carry = over || carried;
+        }
+
+        if carry {
+            self.digits.push(1);
+        }
+    }
+}
+
+impl MulAssign<u64> for Natural {
+    fn mul_assign(&mut self, n: u64) {
+        if n == 0 {
+            self.digits.clear();
+            return;
+        }
+
+        let mut carry = 0;
+        for digit in &mut self.digits {
+            let product = u128::from(*digit) * u128::from(n) + carry;
+            *digit = product as u64;
+            carry = product >> 64;
+        }
+
+        if carry != 0 {
+            self.digits.push(carry as u64);
+        }
+    }
+}
+
+impl<'a> Sum<&'a Natural> for Natural {
+    fn sum<I: Iterator<Item = &'a Natural>>(iter: I) -> Self {
+        let mut total = Natural::default();
+        for n in iter {
+            total += n;
+        }
+        total
+    }
+}
+
+impl Ord for Natural {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // there aren't any zeros at the end so more digits is bigger
+        self.digits.len().cmp(&other.digits.len()).then_with(|| {
+            self.digits.iter().rev().cmp(other.digits.iter().rev())
+        })
+    }
+}
+
+impl PartialOrd for Natural {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for Natural {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // split it into chunks of decimal digits, least significant first
+        let mut n = self.clone();
+        let mut chunks = Vec::new();
+        while !n.is_zero() {
+            chunks.push(n.div_rem(DECIMAL));
+        }
+
+        match chunks.pop() {
+            None => write!(f, "0"),
+            Some(first) => {
+                write!(f, "{}", first)?;
+                for chunk in chunks.iter().rev() {
+                    write!(f, "{:019}", chunk)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    #[test]
+    fn test_against_u128() {
+        let mut rng = StdRng::seed_from_u64(10);
+
+        for _ in 0..1000 {
+            let a: u128 = rng.gen::<u64>().into();
+            let b: u64 = rng.gen();
+            let c: u128 = rng.gen::<u64>().into();
+
+            let mut n = Natural::from(a as u64);
+            n *= b;
+            n += &Natural::from(c as u64);
+
+            let expected = a * u128::from(b) + c;
+            assert_eq!(n.to_u128(), Some(expected));
+            assert_eq!(n.to_string(), expected.to_string());
+            assert_eq!(n.cmp(&Natural::from(c as u64)), expected.cmp(&c),);
+        }
+    }
+
+    #[test]
+    fn test_big() {
+        // 2^128 (just past a u128) and then 2^192
+        let mut n = Natural::from(1 << 63);
+        n *= 1 << 63;
+        n *= 4;
+        assert_eq!(n.to_u128(), None);
+        assert_eq!(n.to_string(), "340282366920938463463374607431768211456");
+
+        let mut m = n.clone();
+        m *= u64::MAX;
+        m += &n;
+        assert_eq!(
+            m.to_string(),
+            "6277101735386680763835789423207666416102355444464034512896"
+        );
+        assert!(m > n && n > Natural::from(u64::MAX));
+
+        // carrying all the way through
+        let mut all = Natural::from(u64::MAX);
+        all *= u64::MAX;
+        all += &Natural::from(u64::MAX);
+        all += &Natural::from(u64::MAX);
+        all += &Natural::from(1);
+        assert_eq!(all, n);
+
+        let mut zero = m;
+        zero *= 0;
+        assert!(zero.is_zero());
+        assert_eq!(zero.to_string(), "0");
+        assert!([n.clone(), n.clone()].iter().sum::<Natural>() > n);
+
+        // zeros in the middle of the decimal digits are kept
+        let mut ten = Natural::from(DECIMAL);
+        ten *= 10;
+        assert_eq!(ten.to_string(), format!("1{}", "0".repeat(20)));
+    }
+}
@@ -0,0 +1,310 @@
+/* Copyright 2025 Mario Finelli
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Optical character recognition for Advent of Code block-letter output.
+//!
+//! Some puzzles (e.g., [`crate::y22d10`]) don't produce a number or a string
+//! as their answer, but rather "draw" some letters on a screen that then need
+//! to be read by a human. Advent of Code has always used the same two fonts
+//! for this: a small one where each letter is (at most) `4` pixels wide and
+//! `6` pixels tall, and a large one where each letter is `6` pixels wide and
+//! `10` pixels tall.
+//!
+//! Instead of relying on a fixed stride between letters we split the image
+//! into glyphs on the columns that don't have any lit pixels. Each glyph is
+//! then trimmed and looked up in the table for the font that matches the
+//! height of the image. This way we don't need to care about how much
+//! padding there is between letters or around the whole image (and the few
+//! letters that don't quite fit in the standard width, like the small `Y`,
+//! work without any special handling).
+
+/// The small (`4x6`) Advent of Code font.
+const SMALL_FONT: [(char, [&str; 6]); 18] = [
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', ["###", ".#.", ".#.", ".#.", ".#.", "###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Y', ["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+/// The large (`6x10`) Advent of Code font.
+const LARGE_FONT: [(char, [&str; 10]); 15] = [
+    (
+        'A',
+        [
+            "..##..", ".#..#.", "#....#", "#....#", "#....#", "######",
+            "#....#", "#....#", "#....#", "#....#",
+        ],
+    ),
+    (
+        'B',
+        [
+            "#####.", "#....#", "#....#", "#....#", "#####.", "#....#",
+            "#....#", "#....#", "#....#", "#####.",
+        ],
+    ),
+    (
+        'C',
+        [
+            ".####.", "#....#", "#.....", "#.....", "#.....", "#.....",
+            "#.....", "#.....", "#....#", ".####.",
+        ],
+    ),
+    (
+        'E',
+        [
+            "######", "#.....", "#.....", "#.....", "#####.", "#.....",
+            "#.....", "#.....", "#.....", "######",
+        ],
+    ),
+    (
+        'F',
+        [
+            "######", "#.....", "#.....", "#.....", "#####.", "#.....",
+            "#.....", "#.....", "#.....", "#.....",
+        ],
+    ),
+    (
+        'G',
+        [
+            ".####.", "#....#", "#.....", "#.....", "#.....", "#..###",
+            "#....#", "#....#", "#...##", ".###.#",
+        ],
+    ),
+    (
+        'H',
+        [
+            "#....#", "#....#", "#....#", "#....#", "######", "#....#",
+            "#....#", "#....#", "#....#", "#....#",
+        ],
+    ),
+    (
+        'J',
+        [
+            "...###", "....#.", "....#.", "....#.", "....#.", "....#.",
+            "....#.", "#...#.", "#...#.", ".###..",
+        ],
+    ),
+    (
+        'K',
+        [
+            "#....#", "#...#.", "#..#..", "#.#...", "##....", "##....",
+            "#.#...", "#..#..", "#...#.", "#....#",
+        ],
+    ),
+    (
+        'L',
+        [
+            "#.....", "#.....", "#.....", "#.....", "#.....", "#.....",
+            "#.....", "#.....", "#.....", "######",
+        ],
+    ),
+    (
+        'N',
+        [
+            "#....#", "##...#", "##...#", "#.#..#", "#.#..#", "#..#.#",
+            "#..#.#", "#...##", "#...##", "#....#",
+        ],
+    ),
+    (
+        'P',
+        [
+            "#####.", "#....#", "#....#", "#....#", "#####.", "#.....",
+            "#.....", "#.....", "#.....", "#.....",
+        ],
+    ),
+    (
+        'R',
+        [
+            "#####.", "#....#", "#....#", "#....#", "#####.", "#..#..",
+            "#...#.", "#...#.", "#....#", "#....#",
+        ],
+    ),
+    (
+        'X',
+        [
+            "#....#", "#....#", ".#..#.", ".#..#.", "..##..", "..##..",
+            ".#..#.", ".#..#.", "#....#", "#....#",
+        ],
+    ),
+    (
+        'Z',
+        [
+            "######", ".....#", ".....#", "....#.", "...#..", "..#...",
+            ".#....", "#.....", "#.....", "######",
+        ],
+    ),
+];
+
+/// Decodes the letters drawn in the given image.
+///
+/// The image is expected to use `#` for lit pixels; any other character is
+/// considered to be unlit. Blank rows around the letters are ignored. Returns
+/// `None` if the image isn't drawn in one of the two known fonts or if any of
+/// the letters aren't recognized.
+///
+/// # Example
+/// ```rust
+/// # use aoc::util::ocr::ocr;
+/// let image = concat!(
+///     "#..#.###.\n",
+///     "#..#..#..\n",
+///     "####..#..\n",
+///     "#..#..#..\n",
+///     "#..#..#..\n",
+///     "#..#.###.",
+/// );
+/// assert_eq!(ocr(image), Some("HI".to_string()));
+/// ```
+pub fn ocr(image: &str) -> Option<String> {
+    let grid: Vec<Vec<bool>> = image
+        .lines()
+        .map(|line| line.chars().map(|c| c == '#').collect())
+        .collect();
+
+    ocr_grid(&grid)
+}
+
+/// Decodes the letters drawn in the given grid of pixels.
+///
+/// This is the same as [`ocr`] except that it works directly on the pixels
+/// (`true` for lit) for solutions that don't build a string first. The rows
+/// don't need to all be the same length, missing pixels are unlit.
+///
+/// # Example
+/// ```rust
+/// # use aoc::util::ocr::ocr_grid;
+/// let grid: Vec<Vec<bool>> = [
+///     "####", "#...", "###.", "#...", "#...", "#..."
+/// ]
+/// .iter()
+/// .map(|row| row.chars().map(|c| c == '#').collect())
+/// .collect();
+/// assert_eq!(ocr_grid(&grid), Some("F".to_string()));
+/// ```
+pub fn ocr_grid(grid: &[Vec<bool>]) -> Option<String> {
+    let first = grid.iter().position(|row| row.contains(&true))?;
+    let last = grid.iter().rposition(|row| row.contains(&true))?;
+    let rows = &grid[first..=last];
+    let width = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+    let lit = |y: usize, x: usize| rows[y].get(x).copied().unwrap_or(false);
+
+    let mut glyphs = Vec::new();
+    let mut start = None;
+
+    for x in 0..=width {
+        let blank = (0..rows.len()).all(|y| !lit(y, x));
+
+        match (start, blank) {
+            (None, false) => start = Some(x),
+            (Some(s), true) => {
+                glyphs.push((s, x));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+
+    let mut result = String::new();
+    for (from, to) in glyphs {
+        let glyph: Vec<String> = (0..rows.len())
+            .map(|y| {
+                (from..to)
+                    .map(|x| if lit(y, x) { '#' } else { '.' })
+                    .collect()
+            })
+            .collect();
+
+        result.push(lookup(&glyph)?);
+    }
+
+    Some(result)
+}
+
+/// Finds the letter that matches the given (trimmed) glyph in the font that
+/// corresponds to its height.
+fn lookup(glyph: &[String]) -> Option<char> {
+    let matches = |rows: &[&str]| {
+        // some letters (e.g., the small "J") have a blank first column, so
+        // trim the font the same way that we trimmed the glyph
+        let offset = (0..rows[0].len())
+            .find(|&x| rows.iter().any(|row| &row[x..=x] == "#"))
+            .unwrap_or(0);
+        rows.iter().zip(glyph).all(|(row, g)| {
+            row[offset..].trim_end_matches('.') == g.trim_end_matches('.')
+        })
+    };
+
+    match glyph.len() {
+        6 => SMALL_FONT
+            .iter()
+            .find(|(_, rows)| matches(rows))
+            .map(|g| g.0),
+        10 => LARGE_FONT
+            .iter()
+            .find(|(_, rows)| matches(rows))
+            .map(|g| g.0),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Draws the given letters in the given font with a one column gap
+    /// between them so that we can check that we're able to read them back.
+    fn draw<const N: usize>(font: &[(char, [&str; N])], word: &str) -> String {
+        (0..N)
+            .map(|y| {
+                word.chars()
+                    .map(|c| font.iter().find(|(l, _)| *l == c).unwrap().1[y])
+                    .collect::<Vec<_>>()
+                    .join(".")
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn it_works() {
+        let small: String = SMALL_FONT.iter().map(|(c, _)| c).collect();
+        assert_eq!(ocr(&draw(&SMALL_FONT, &small)), Some(small));
+
+        let large: String = LARGE_FONT.iter().map(|(c, _)| c).collect();
+        assert_eq!(ocr(&draw(&LARGE_FONT, &large)), Some(large));
+
+        assert_eq!(ocr(""), None);
+        assert_eq!(ocr("#.#\n.#.\n#.#"), None);
+        assert_eq!(ocr(&draw(&SMALL_FONT, "HI").replace('#', "█")), None);
+        assert_eq!(
+            ocr(&format!("\n{}\n\n", draw(&SMALL_FONT, "JO"))),
+            Some("JO".to_string())
+        );
+    }
+}
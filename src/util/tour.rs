@@ -0,0 +1,288 @@
+/* Copyright 2025 Mario Finelli
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Best routes through every node of a small graph.
+//!
+//! A few puzzles (e.g., [`crate::y15d09`] and [`crate::y15d13`]) ask for the
+//! best way to visit every node exactly once, either as an open path (where
+//! we can start and end anywhere) or as a closed cycle (where we come back to
+//! the start). Trying every permutation works for the handful of nodes in the
+//! prompts but it stops being feasible at around a dozen nodes. Instead we use
+//! the [Held-Karp algorithm](https://en.wikipedia.org/wiki/Held%E2%80%93Karp_algorithm):
+//! the best way to visit a set of nodes ending at a particular node only
+//! depends on that set and that node (and not on the order that we visited
+//! the rest of the set in), so we can build it up from smaller sets. That's
+//! `O(2^n * n^2)` time and `O(2^n * n)` memory, which is fine for 20 or so
+//! nodes. The graph doesn't need to be complete: a missing edge just can't
+//! be used, and if that means there's no way to visit every node then there's
+//! no best route either.
+
+/// Whether the route ends where it started.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Shape {
+    /// Visit every node once, starting and ending anywhere.
+    Path,
+    /// Visit every node once and come back to the first one.
+    Cycle,
+}
+
+/// Whether we're looking for the cheapest or the most expensive route.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Goal {
+    /// The route with the smallest total cost.
+    Minimize,
+    /// The route with the largest total cost.
+    Maximize,
+}
+
+/// The best route and its total cost.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Tour {
+    /// The total cost of the route.
+    pub cost: i64,
+    /// The nodes in the order that they're visited (for cycles the first node
+    /// isn't repeated at the end).
+    pub order: Vec<usize>,
+}
+
+/// Finds the best route through all of the nodes where `costs[a][b]` is the
+/// cost of going from node `a` to node `b` (it doesn't need to be
+/// symmetric) or `None` if there's no edge between them. Returns `None` if
+/// there aren't any nodes or no route visits all of them.
+///
+/// We keep the best cost of every set of nodes (as a bitmask) and the node
+/// that we ended at. For paths every node is a possible start, for cycles we
+/// always start at node `0` (every cycle goes through it anyway) which also
+/// means that we only need the sets that include it. Then we go through the
+/// sets in increasing order (a set always comes after all of its subsets) and
+/// try to extend the best route of each set and end node with every node
+/// that isn't in the set yet. Once we have the best cost of every end node
+/// for the full set we pick the best one (adding the cost of the way back to
+/// the start for cycles) and then walk backwards: the node before the end is
+/// whichever one gives us exactly the best cost when we add the last step.
+///
+/// # Panics
+/// If there are more nodes than fit in the bitmask (although we'd run out of
+/// memory long before that).
+///
+/// # Example
+/// ```rust
+/// # use aoc::util::tour::{best_tour, Goal, Shape, Tour};
+/// let costs = vec![
+///     vec![None, Some(10), Some(25)],
+///     vec![Some(10), None, Some(15)],
+///     vec![Some(25), Some(15), None],
+/// ];
+/// assert_eq!(
+///     best_tour(&costs, Shape::Path, Goal::Minimize),
+///     Some(Tour { cost: 25, order: vec![2, 1, 0] })
+/// );
+/// assert_eq!(
+///     best_tour(&costs, Shape::Path, Goal::Maximize),
+///     Some(Tour { cost: 40, order: vec![1, 2, 0] })
+/// );
+/// assert_eq!(best_tour(&costs, Shape::Cycle, Goal::Minimize).unwrap().cost, 50);
+///
+/// // without the edge from 0 to 2 (and back) there's only one path left
+/// let costs = vec![
+///     vec![None, Some(10), None],
+///     vec![Some(10), None, Some(15)],
+///     vec![None, Some(15), None],
+/// ];
+/// assert_eq!(best_tour(&costs, Shape::Path, Goal::Maximize).unwrap().cost, 25);
+/// assert_eq!(best_tour(&costs, Shape::Cycle, Goal::Maximize), None);
+/// ```
+pub fn best_tour(
+    costs: &[Vec<Option<i64>>],
+    shape: Shape,
+    goal: Goal,
+) -> Option<Tour> {
+    let n = costs.len();
+    if n == 0 {
+        return None;
+    }
+    assert!(n < usize::BITS as usize, "too many nodes");
+
+    let unreached = match goal {
+        Goal::Minimize => i64::MAX,
+        Goal::Maximize => i64::MIN,
+    };
+    let better = |a: i64, b: i64| match goal {
+        Goal::Minimize => a < b,
+        Goal::Maximize => a > b,
+    };
+
+    let full = (1 << n) - 1;
+    let mut best = vec![unreached; (1 << n) * n];
+
+    match shape {
+        Shape::Path => {
+            for node in 0..n {
+                best[(1 << node) * n + node] = 0;
+            }
+        }
+        Shape::Cycle => best[n] = 0,
+    }
+
+    for set in 1..=full {
+        if shape == Shape::Cycle && set & 1 == 0 {
+            continue;
+        }
+
+        for last in 0..n {
+            let cost = best[set * n + last];
+            if cost == unreached {
+                continue;
+            }
+
+            for (next, step) in costs[last].iter().enumerate() {
+                let Some(step) = step else {
+                    continue;
+                };
+                if set & (1 << next) != 0 {
+                    continue;
+                }
+
+                let index = (set | (1 << next)) * n + next;
+                let extended = cost + step;
+                if best[index] == unreached || better(extended, best[index]) {
+                    best[index] = extended;
+                }
+            }
+        }
+    }
+
+    let back = |last: usize| match shape {
+        Shape::Path => Some(0),
+        Shape::Cycle if n == 1 => Some(0),
+        Shape::Cycle => costs[last][0],
+    };
+    let (mut last, cost) = (0..n)
+        .filter(|last| best[full * n + last] != unreached)
+        .filter_map(|last| Some((last, best[full * n + last] + back(last)?)))
+        .reduce(|a, b| if better(b.1, a.1) { b } else { a })?;
+
+    let mut order = vec![last];
+    let mut set = full;
+    while set != 1 << last {
+        let previous = set & !(1 << last);
+        let target = best[set * n + last];
+        last = (0..n)
+            .find(|p| {
+                previous & (1 << p) != 0
+                    && best[previous * n + p] != unreached
+                    && costs[*p][last].is_some_and(|step| {
+                        best[previous * n + p] + step == target
+                    })
+            })
+            .unwrap();
+        order.push(last);
+        set = previous;
+    }
+
+    order.reverse();
+    Some(Tour { cost, order })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use itertools::Itertools;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    #[test]
+    fn it_works() {
+        let mut rng = StdRng::seed_from_u64(9);
+
+        for n in 1..=7 {
+            for missing in [0.0, 0.3, 0.6] {
+                let costs: Vec<Vec<Option<i64>>> = (0..n)
+                    .map(|_| {
+                        (0..n)
+                            .map(|_| {
+                                (!rng.gen_bool(missing))
+                                    .then(|| rng.gen_range(-50..100))
+                            })
+                            .collect()
+                    })
+                    .collect();
+                let cost = |order: &[usize], shape| {
+                    let mut steps: Vec<_> =
+                        order.windows(2).map(|w| costs[w[0]][w[1]]).collect();
+                    if shape == Shape::Cycle && n > 1 {
+                        steps.push(costs[order[n - 1]][order[0]]);
+                    }
+                    steps.into_iter().sum::<Option<i64>>()
+                };
+
+                for shape in [Shape::Path, Shape::Cycle] {
+                    let costs_of_all: Vec<_> = (0..n)
+                        .permutations(n)
+                        .filter_map(|p| cost(&p, shape))
+                        .collect();
+
+                    let tour = best_tour(&costs, shape, Goal::Minimize);
+                    assert_eq!(
+                        tour.as_ref().map(|t| t.cost),
+                        costs_of_all.iter().min().copied()
+                    );
+                    if let Some(tour) = tour {
+                        assert_eq!(Some(tour.cost), cost(&tour.order, shape));
+                        assert_eq!(
+                            tour.order.iter().sorted().collect::<Vec<_>>(),
+                            (0..n)
+                                .collect::<Vec<_>>()
+                                .iter()
+                                .collect::<Vec<_>>()
+                        );
+                    }
+
+                    let tour = best_tour(&costs, shape, Goal::Maximize);
+                    assert_eq!(
+                        tour.as_ref().map(|t| t.cost),
+                        costs_of_all.iter().max().copied()
+                    );
+                    if let Some(tour) = tour {
+                        assert_eq!(Some(tour.cost), cost(&tour.order, shape));
+                    }
+                }
+            }
+        }
+
+        assert_eq!(best_tour(&[], Shape::Path, Goal::Minimize), None);
+    }
+
+    #[test]
+    fn test_many_nodes() {
+        // nodes around a circle, the best cycle just goes around it
+        let n = 16;
+        let costs: Vec<Vec<Option<i64>>> = (0..n)
+            .map(|a: i64| {
+                (0..n)
+                    .map(|b: i64| {
+                        let d = (a - b).abs();
+                        Some(d.min(n - d) * d.min(n - d))
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let tour = best_tour(&costs, Shape::Cycle, Goal::Minimize).unwrap();
+        assert_eq!(tour.cost, n);
+        let tour = best_tour(&costs, Shape::Path, Goal::Minimize).unwrap();
+        assert_eq!(tour.cost, n - 1);
+    }
+}
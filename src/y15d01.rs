@@ -23,6 +23,11 @@
 //! in the second part to never reach the basement (negative counter). The
 //! second part also returns a strictly positive number whereas in part one
 //! it could be possible to return a negative number.
+//!
+//! For really big inputs there's also [`y15d01_stream`] which solves both
+//! parts at once while reading the input a chunk at a time.
+
+use std::io::{self, BufRead};
 
 /// The solution for part one of the day one challenge.
 ///
@@ -82,10 +87,87 @@ pub fn y15d01p2(input: &str) -> Option<u32> {
     None
 }
 
+/// The solution for both parts of the day one challenge, reading the input
+/// from a [`BufRead`] as it goes instead of needing it all in memory.
+///
+/// We go through each chunk of the input that the reader has buffered and
+/// keep track of the floor exactly like in part one, remembering the position
+/// of the first character that takes us into the basement like in part two.
+/// Whitespace at the start and end of the input (e.g., the trailing newline)
+/// is skipped just like the other parts trim it, but whitespace in between
+/// counts as going down a floor (just like any other character that isn't a
+/// `(`). Since we don't know whether whitespace is at the end until we see
+/// what comes after it we hold on to how much of it there's been and only
+/// count it once another character shows up.
+///
+/// # Errors
+/// If reading the input fails.
+///
+/// # Example
+/// ```rust
+/// # use aoc::y15d01::y15d01_stream;
+/// let input = "(()))((\n"; // probably read this from a file or stdin...
+/// assert_eq!(y15d01_stream(input.as_bytes()).unwrap(), (1, Some(5)));
+/// ```
+pub fn y15d01_stream(
+    mut reader: impl BufRead,
+) -> io::Result<(i64, Option<u64>)> {
+    let mut floor: i64 = 0;
+    let mut position: u64 = 0;
+    let mut basement = None;
+    let mut whitespace: u64 = 0;
+
+    loop {
+        let chunk = reader.fill_buf()?;
+        if chunk.is_empty() {
+            break;
+        }
+
+        for c in chunk {
+            if c.is_ascii_whitespace() {
+                // leading whitespace is skipped entirely
+                if position > 0 {
+                    whitespace += 1;
+                }
+                continue;
+            }
+
+            // the whitespace wasn't at the end after all
+            if whitespace > 0 && basement.is_none() && floor < whitespace as i64
+            {
+                basement = Some(position + floor as u64 + 1);
+            }
+            floor -= whitespace as i64;
+            position += whitespace;
+            whitespace = 0;
+
+            position += 1;
+            if *c == b'(' {
+                floor += 1;
+            } else {
+                floor -= 1;
+            }
+
+            if floor < 0 && basement.is_none() {
+                basement = Some(position);
+            }
+        }
+
+        let length = chunk.len();
+        reader.consume(length);
+    }
+
+    Ok((floor, basement))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::generate;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
     use std::fs;
+    use std::io::Read;
 
     #[test]
     fn it_works() {
@@ -123,11 +205,73 @@ mod tests {
         assert_eq!(y15d01p2(input), Some(5));
     }
 
+    #[test]
+    fn properties() {
+        for input in generate::cases("y15d01", 200, 50) {
+            let up = input.matches('(').count() as i32;
+            let down = input.matches(')').count() as i32;
+            assert_eq!(y15d01p1(&input), up - down);
+
+            // brute force: replay every prefix from the start
+            let basement = (1..=input.len()).find(|&i| {
+                let prefix = &input[..i];
+                prefix.matches('(').count() < prefix.matches(')').count()
+            });
+            assert_eq!(y15d01p2(&input), basement.map(|i| i as u32));
+
+            let (floor, position) = y15d01_stream(input.as_bytes()).unwrap();
+            assert_eq!(floor, i64::from(up - down));
+            assert_eq!(position, basement.map(|i| i as u64));
+        }
+    }
+
     #[test]
     fn the_solution() {
         let contents = fs::read_to_string("input/2015/day01.txt").unwrap();
 
         assert_eq!(y15d01p1(&contents), 232);
         assert_eq!(y15d01p2(&contents).unwrap(), 1783);
+        assert_eq!(
+            y15d01_stream(contents.as_bytes()).unwrap(),
+            (232, Some(1783))
+        );
+    }
+
+    #[test]
+    fn test_whitespace() {
+        let mut rng = StdRng::seed_from_u64(1);
+
+        // the same input as a string and as a stream always agrees, no
+        // matter where the whitespace is
+        for input in generate::cases("y15d01", 200, 20) {
+            let mut spaced = String::new();
+            for c in input.chars() {
+                while rng.gen_bool(0.2) {
+                    spaced.push([' ', '\n', '\t'][rng.gen_range(0..3)]);
+                }
+                spaced.push(c);
+            }
+            while rng.gen_bool(0.3) {
+                spaced.push('\n');
+            }
+
+            let (floor, basement) = y15d01_stream(spaced.as_bytes()).unwrap();
+            assert_eq!(floor, i64::from(y15d01p1(&spaced)));
+            assert_eq!(basement, y15d01p2(&spaced).map(u64::from));
+        }
+
+        assert_eq!(y15d01_stream(&b"\n(  ((\n\n"[..]).unwrap(), (1, Some(3)));
+    }
+
+    #[test]
+    fn test_stream() {
+        // a small buffer so that the input is read in lots of chunks
+        let input = io::repeat(b'(').take(3_000_000).chain(&b")\n"[..]);
+        let reader = io::BufReader::with_capacity(64, input);
+        assert_eq!(y15d01_stream(reader).unwrap(), (2_999_999, None));
+
+        let input = io::repeat(b')').take(5_000_000);
+        let reader = io::BufReader::with_capacity(100, input);
+        assert_eq!(y15d01_stream(reader).unwrap(), (-5_000_000, Some(1)));
     }
 }
@@ -20,10 +20,16 @@
 //! if/else statments and array sorting, but after solving the puzzle I
 //! decided to switch it to use a [min
 //! heap](https://en.wikipedia.org/wiki/Min-max_heap) which I think results
-//! in a much cleaner and easier to understand/reason about solution.
+//! in a much cleaner and easier to understand/reason about solution. The
+//! original version is still around as [`y15d02_naive`] so that the two can
+//! be compared.
+//!
+//! For really big inputs there's also [`y15d02_stream`] which solves both
+//! parts at once while reading the input a line at a time.
 
 use std::cmp::Reverse;
 use std::collections::BinaryHeap;
+use std::io::{self, BufRead};
 
 /// The solution for the day two challenge.
 ///
@@ -98,9 +104,103 @@ pub fn y15d02(input: &str, part: u32) -> u32 {
     total
 }
 
+/// The original solution for the day two challenge.
+///
+/// This works exactly like [`y15d02`] but instead of using a heap to find the
+/// smallest side (part `1`) we just compare the sides to each other with some
+/// if/else statements, and to find the two smallest dimensions (part `2`) we
+/// sort them.
+///
+/// # Example
+/// ```rust
+/// # use aoc::y15d02::y15d02_naive;
+/// let input = "1x2x3\n4x5x6\n"; // probably read this from the input file...
+/// assert_eq!(y15d02_naive(input, 1), 192);
+/// assert_eq!(y15d02_naive(input, 2), 150);
+/// ```
+pub fn y15d02_naive(input: &str, part: u32) -> u32 {
+    let lines: Vec<_> = input.lines().collect();
+    let mut total = 0;
+
+    for line in lines {
+        let mut dimensions: Vec<u32> =
+            line.split('x').map(|d| d.parse().unwrap()).collect();
+        let l = dimensions[0];
+        let w = dimensions[1];
+        let h = dimensions[2];
+
+        if part == 1 {
+            let lw = l * w;
+            let wh = w * h;
+            let hl = h * l;
+
+            let extra = if lw <= wh && lw <= hl {
+                lw
+            } else if wh <= hl {
+                wh
+            } else {
+                hl
+            };
+
+            total += 2 * lw + 2 * wh + 2 * hl + extra;
+        } else {
+            dimensions.sort();
+            total += 2 * dimensions[0] + 2 * dimensions[1] + l * w * h;
+        }
+    }
+
+    total
+}
+
+/// The solution for both parts of the day two challenge, reading the input
+/// from a [`BufRead`] one line at a time instead of needing it all in memory.
+///
+/// We reuse the same buffer for every line and calculate the wrapping paper
+/// (part one) and ribbon (part two) for each present the same way as
+/// [`y15d02_naive`], just with both totals at once (and as `u64`s since the
+/// inputs that need streaming are big).
+///
+/// # Errors
+/// If reading the input fails.
+///
+/// # Panics
+/// If a line isn't three dimensions separated by `x`.
+///
+/// # Example
+/// ```rust
+/// # use aoc::y15d02::y15d02_stream;
+/// let input = "1x2x3\n4x5x6\n"; // probably read this from a file or stdin...
+/// assert_eq!(y15d02_stream(input.as_bytes()).unwrap(), (192, 150));
+/// ```
+pub fn y15d02_stream(mut reader: impl BufRead) -> io::Result<(u64, u64)> {
+    let mut line = String::new();
+    let mut paper = 0;
+    let mut ribbon = 0;
+
+    while reader.read_line(&mut line)? != 0 {
+        if !line.trim().is_empty() {
+            let mut dimensions = [0u64; 3];
+            let mut parts = line.trim().split('x');
+            for dimension in &mut dimensions {
+                *dimension = parts.next().unwrap().parse().unwrap();
+            }
+            dimensions.sort_unstable();
+            let [a, b, c] = dimensions;
+
+            paper += 2 * (a * b + b * c + c * a) + a * b;
+            ribbon += 2 * (a + b) + a * b * c;
+        }
+
+        line.clear();
+    }
+
+    Ok((paper, ribbon))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::generate;
     use std::fs;
 
     #[test]
@@ -116,6 +216,26 @@ mod tests {
         input = "2x3x4\n1x1x10\n";
         assert_eq!(y15d02(input, 1), 101);
         assert_eq!(y15d02(input, 2), 48);
+        assert_eq!(y15d02_naive(input, 1), 101);
+        assert_eq!(y15d02_naive(input, 2), 48);
+    }
+
+    #[test]
+    fn properties() {
+        for input in generate::cases("y15d02", 100, 20) {
+            assert_eq!(y15d02(&input, 1), y15d02_naive(&input, 1));
+            assert_eq!(y15d02(&input, 2), y15d02_naive(&input, 2));
+
+            let (paper, ribbon) = y15d02_stream(input.as_bytes()).unwrap();
+            assert_eq!(paper, u64::from(y15d02(&input, 1)));
+            assert_eq!(ribbon, u64::from(y15d02(&input, 2)));
+
+            // we always need at least one square foot of paper and one foot
+            // of ribbon on each side of every present
+            let presents = input.lines().count() as u32;
+            assert!(y15d02(&input, 1) >= presents * 7);
+            assert!(y15d02(&input, 2) >= presents * 5);
+        }
     }
 
     #[test]
@@ -124,5 +244,11 @@ mod tests {
 
         assert_eq!(y15d02(&contents, 1), 1606483);
         assert_eq!(y15d02(&contents, 2), 3842356);
+        assert_eq!(y15d02_naive(&contents, 1), 1606483);
+        assert_eq!(y15d02_naive(&contents, 2), 3842356);
+        assert_eq!(
+            y15d02_stream(contents.as_bytes()).unwrap(),
+            (1606483, 3842356)
+        );
     }
 }
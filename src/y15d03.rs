@@ -30,9 +30,42 @@
 //! counting the number of entries in the collection. More advanced answers
 //! (e.g., the number of houses receiving more than one present) can be
 //! determined by analyzing the same result collection.
+//!
+//! That collection is now kept around in a [`Deliveries`] along with the path
+//! that each Santa took, so that we can look at which houses got the most
+//! presents, how far out the Santas went and draw a heatmap of it all.
 
+use crate::render::{Frame, Rgb};
 use std::collections::HashMap;
 
+/// The characters used for the ASCII heatmap, from fewest presents to most.
+const SHADES: &[u8] = b".:-=+*#%@";
+
+/// The color of a house that got no presents in the heatmap.
+const EMPTY: Rgb = [0, 0, 0];
+
+/// The color of the houses that got the fewest presents in the heatmap.
+const COLD: Rgb = [15, 15, 35];
+
+/// The color of the houses that got the most presents in the heatmap.
+const HOT: Rgb = [255, 221, 87];
+
+/// The houses that got presents and the paths that the Santas took.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Deliveries {
+    houses: HashMap<(i32, i32), u32>,
+    paths: Vec<Vec<(i32, i32)>>,
+}
+
+/// The smallest rectangle that contains all of the houses that got presents.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Bounds {
+    /// The smallest `x` and `y` coordinates.
+    pub min: (i32, i32),
+    /// The largest `x` and `y` coordinates.
+    pub max: (i32, i32),
+}
+
 /// The solution for the day three challenge.
 ///
 /// The function takes the input as a string and the number of Santa's that are
@@ -41,22 +74,8 @@ use std::collections::HashMap;
 /// the challenge for `2` Santa's). The difference between part one and two of
 /// the challenge is therefore the number of Santa's participating: `1` or `2`.
 ///
-/// The solution is to split the input into number-of-santas-sized chunks and
-/// then loop over each chunk. Then, for each chunk we loop through each Santa
-/// and check the instruction that they have using their index as an index in
-/// the given chunk. We adjust their position based on the character
-/// (instruction that we found) and then increment (or initialize to `1`
-/// present) the house corresponding to their position. Finally we return the
-/// length of the houses collection as the response to the challenge.
-///
-/// A more naïve approach could have been to make the Santa loop the outer
-/// loop and the chunk loop the inner loop i.e., use a single position tracker
-/// (similar to the original solution to part one of the challenge) and then
-/// reset it for each Santa and then use each Santa "i" as the index to pull
-/// out of each chunk as we loop through the chunks collection -- but this
-/// results in looping through the chunks loop santa-times instead of just
-/// once through, though it hardly makes a difference for the small inputs and
-/// number of Santa's that we actually have.
+/// We simulate the deliveries (see [`Deliveries::simulate`]) and return the
+/// number of houses that got at least one present.
 ///
 /// # Example
 /// ```rust
@@ -66,40 +85,160 @@ use std::collections::HashMap;
 /// assert_eq!(y15d03(input, 2), 6);
 /// ```
 pub fn y15d03(input: &str, santas: u32) -> u32 {
-    let chars: Vec<_> = input.trim().chars().collect();
-    let mut houses = HashMap::new();
-    houses.insert((0, 0), santas); // starting position gets a present
-
-    // initialize each santa in their starting position
-    let mut positions = Vec::new();
-    for _ in 0..santas {
-        positions.push((0, 0));
+    let deliveries = Deliveries::simulate(input, santas as usize);
+    deliveries.houses().len() as u32
+}
+
+impl Deliveries {
+    /// Simulates the Santas delivering presents. They all start by delivering
+    /// a present to the house at `(0, 0)` and then take turns following the
+    /// instructions (the first Santa takes the first instruction, the second
+    /// Santa the second one, and so on, wrapping around) moving one house
+    /// over and delivering a present each time. North (`^`) is `y + 1`.
+    ///
+    /// # Panics
+    /// If there are no Santas or if an instruction isn't one of `^`,
+    /// `v`, `<` or `>`.
+    pub fn simulate(input: &str, santas: usize) -> Deliveries {
+        assert!(santas > 0, "somebody needs to deliver the presents");
+
+        let mut houses = HashMap::from([((0, 0), santas as u32)]);
+        let mut paths = vec![vec![(0, 0)]; santas];
+
+        for (i, c) in input.trim().chars().enumerate() {
+            let path = &mut paths[i % santas];
+            let (x, y) = *path.last().unwrap();
+            let position = match c {
+                '<' => (x - 1, y),
+                '>' => (x + 1, y),
+                '^' => (x, y + 1),
+                'v' => (x, y - 1),
+                _ => panic!("unknown instruction {:?}", c),
+            };
+
+            path.push(position);
+            *houses.entry(position).or_insert(0) += 1;
+        }
+
+        Deliveries { houses, paths }
     }
 
-    for chunk in chars.chunks(santas as usize) {
-        for santa in 0..santas {
-            let (mut x, mut y) = positions[santa as usize];
-            let c = chunk[santa as usize];
-
-            if c == '<' {
-                x -= 1;
-            } else if c == '^' {
-                y += 1;
-            } else if c == '>' {
-                x += 1;
-            } else {
-                y -= 1;
-            }
+    /// Returns how many presents each house that got any presents got.
+    pub fn houses(&self) -> &HashMap<(i32, i32), u32> {
+        &self.houses
+    }
 
-            // write santa's new position back to the position tracker
-            positions[santa as usize] = (x, y);
+    /// Returns how many presents the house at the given coordinate got.
+    pub fn presents(&self, x: i32, y: i32) -> u32 {
+        self.houses.get(&(x, y)).copied().unwrap_or(0)
+    }
 
-            let count = houses.entry((x, y)).or_insert(0);
-            *count += 1;
+    /// Returns every house that the given Santa visited, in order (starting
+    /// with `(0, 0)`).
+    ///
+    /// # Panics
+    /// If there's no such Santa.
+    pub fn path(&self, santa: usize) -> &[(i32, i32)] {
+        &self.paths[santa]
+    }
+
+    /// Returns the given number of houses that got the most presents and how
+    /// many they got (ties are broken by coordinate so that the result is
+    /// always the same).
+    ///
+    /// # Example
+    /// ```rust
+    /// # use aoc::y15d03::Deliveries;
+    /// let deliveries = Deliveries::simulate("^v^v>", 1);
+    /// assert_eq!(deliveries.most_visited(2), vec![((0, 0), 3), ((0, 1), 2)]);
+    /// ```
+    pub fn most_visited(&self, count: usize) -> Vec<((i32, i32), u32)> {
+        let mut houses: Vec<_> =
+            self.houses.iter().map(|(house, n)| (*house, *n)).collect();
+        houses.sort_by_key(|(house, n)| (std::cmp::Reverse(*n), *house));
+        houses.truncate(count);
+        houses
+    }
+
+    /// Returns the smallest rectangle that contains every house that got a
+    /// present.
+    pub fn bounds(&self) -> Bounds {
+        let xs = self.houses.keys().map(|(x, _)| *x);
+        let ys = self.houses.keys().map(|(_, y)| *y);
+
+        Bounds {
+            min: (xs.clone().min().unwrap(), ys.clone().min().unwrap()),
+            max: (xs.max().unwrap(), ys.max().unwrap()),
         }
     }
 
-    houses.len() as u32
+    /// Draws the number of presents that each house got using characters
+    /// that get denser as the number goes up (relative to the house that got
+    /// the most). Houses without presents are spaces and north is up.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use aoc::y15d03::Deliveries;
+    /// let deliveries = Deliveries::simulate(">>>^<<v", 1);
+    /// assert_eq!(deliveries.ascii_heatmap(), " ...\n.@..\n");
+    /// ```
+    pub fn ascii_heatmap(&self) -> String {
+        let Bounds { min, max } = self.bounds();
+        let most = self.houses.values().max().copied().unwrap();
+        let mut map = String::new();
+
+        for y in (min.1..=max.1).rev() {
+            for x in min.0..=max.0 {
+                map.push(match self.presents(x, y) {
+                    0 => ' ',
+                    n => SHADES[shade(n, most, SHADES.len())] as char,
+                });
+            }
+            map.push('\n');
+        }
+
+        map
+    }
+
+    /// Draws the number of presents that each house got as a [`Frame`]
+    /// (going from dark blue for the fewest presents to yellow for the most)
+    /// which can be turned into a `PPM` image with [`Frame::to_ppm`].
+    pub fn heatmap(&self) -> Frame {
+        let Bounds { min, max } = self.bounds();
+        let most = self.houses.values().max().copied().unwrap();
+        let width = (max.0 - min.0 + 1) as usize;
+        let height = (max.1 - min.1 + 1) as usize;
+
+        Frame::from_fn(width, height, |x, y| {
+            let x = min.0 + x as i32;
+            let y = max.1 - y as i32;
+            match self.presents(x, y) {
+                0 => EMPTY,
+                n => {
+                    let level = shade(n, most, 256) as u32;
+                    let mut color = COLD;
+                    for (c, (cold, hot)) in
+                        color.iter_mut().zip(COLD.iter().zip(HOT))
+                    {
+                        *c = (*cold as u32
+                            + (hot as u32 - *cold as u32) * level / 255)
+                            as u8;
+                    }
+                    color
+                }
+            }
+        })
+    }
+}
+
+/// Buckets a number of presents (between one and the most that any house got)
+/// into one of the given number of levels.
+fn shade(presents: u32, most: u32, levels: usize) -> usize {
+    if most <= 1 {
+        return levels - 1;
+    }
+
+    (presents as usize - 1) * (levels - 1) / (most as usize - 1)
 }
 
 #[cfg(test)]
@@ -107,6 +246,35 @@ mod tests {
     use super::*;
     use std::fs;
 
+    #[test]
+    fn test_deliveries() {
+        let deliveries = Deliveries::simulate("^>v<", 2);
+
+        assert_eq!(deliveries.path(0), &[(0, 0), (0, 1), (0, 0)]);
+        assert_eq!(deliveries.path(1), &[(0, 0), (1, 0), (0, 0)]);
+        assert_eq!(deliveries.presents(0, 0), 4);
+        assert_eq!(deliveries.presents(1, 1), 0);
+        assert_eq!(
+            deliveries.bounds(),
+            Bounds {
+                min: (0, 0),
+                max: (1, 1)
+            }
+        );
+        assert_eq!(
+            deliveries.most_visited(10),
+            vec![((0, 0), 4), ((0, 1), 1), ((1, 0), 1)]
+        );
+        assert_eq!(deliveries.ascii_heatmap(), ". \n@.\n");
+
+        let frame = deliveries.heatmap();
+        assert_eq!((frame.width(), frame.height()), (2, 2));
+        assert_eq!(frame.get(0, 1), Some(HOT));
+        assert_eq!(frame.get(1, 1), Some(COLD));
+        assert_eq!(frame.get(1, 0), Some(EMPTY));
+        assert!(frame.to_ppm(1).starts_with(b"P6\n2 2\n255\n"));
+    }
+
     #[test]
     fn it_works() {
         let mut input = ">\n";
@@ -130,5 +298,9 @@ mod tests {
 
         assert_eq!(y15d03(&contents, 1), 2565);
         assert_eq!(y15d03(&contents, 2), 2639);
+
+        let deliveries = Deliveries::simulate(&contents, 2);
+        let total: u32 = deliveries.houses().values().sum();
+        assert_eq!(total as usize, contents.trim().len() + 2);
     }
 }
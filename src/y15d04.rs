@@ -22,42 +22,36 @@
 //!
 //! A second attempt to improve the runtime of this solution maintains the
 //! same general logic as above but splits the work into parallel threads.
-//! On my machine this saw time improvements from about 10-11 seconds down to
-//! 2.5 to 3.5 seconds to compute both prefixes independently. There are
-//! probably other improvements that could be made to speed this up further
-//! (such as running both prefixes together so that we don't have to recompute
-//! so many hashes that we know aren't long enough, but I prefer to have each
-//! part distinct). The way that I implemented it is to spawn threads and have
-//! them work on 100,000 hashes at a time. This could (maybe) be improved by
-//! instead having a solution found mutex and having each thread work
-//! independently until it finds a solution or until the solution mutex is
-//! set or something similar. Obviously, the way I have currently done it,
-//! there is a decent amount of wasted compute if the resulting number is very
-//! small (e.g., answers less than 100,000) or even if we could find an answer
-//! early in any batch of work we could return instantly but instead need to
-//! wait for all of the threads to finish.
+//! The first version of that spawned a fresh batch of threads for every
+//! 100,000 numbers and then had to wait for the whole batch to finish even if
+//! one of the threads found the answer right away. Now the threads are only
+//! spawned once and keep claiming small blocks of numbers from a shared
+//! counter (so a thread that's done with its block just takes the next one)
+//! until every block that could still contain a smaller answer has been
+//! checked. The best answer found so far is kept in an atomic so that all of
+//! the threads know when they can stop.
+//!
+//! Since a hash with six leading zeros also has five leading zeros we can
+//! look for the answers to both parts (or any number of prefixes) in the same
+//! pass, and instead of formatting every hash as hex we just count the zero
+//! nibbles at the start of the raw digest.
 //!
 //! I didn't try to implement the `MD5` algorithm myself and instead decided
 //! to use the [md-5](https://docs.rs/md-5/latest/md5/) crate.
 
 use md5::{Digest, Md5};
-use std::{sync::Arc, thread};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::thread;
+
+/// How many numbers a thread claims at a time.
+const BLOCK: u64 = 4096;
 
 /// The solution for the day four challenge.
 ///
 /// This function takes the input string and the number of leading zeros to
-/// find as arguments. The logic is otherwise fairly simple, as described
-/// above we batch the work into threads. Each thread works on its chunk of
-/// 100,000 numbers combining the input string and each number one after the
-/// other to compute the `MD5` hash until it either finds a hash that has the
-/// correct number of leading zeros or it exhausts its numbers. After all
-/// threads have finished we check to see if any results were found and if so
-/// we add them to a vector, sort it, and return the smallest value (this is
-/// important because in theory two threads could find a number that results
-/// in a has with the correct number of leading zeros and we want the smallest
-/// of those numbers). If no answer was found we spawn another batch of the
-/// threads and continue in this way until we either find a match or reach the
-/// maximum integer size (in which case we will return `None`).
+/// find as arguments and returns the smallest number that produces a hash
+/// with (at least) that many leading zeros. See [`y15d04_mine`] for how the
+/// work is actually done.
 ///
 /// # Example
 /// ```rust
@@ -66,70 +60,126 @@ use std::{sync::Arc, thread};
 /// assert_eq!(y15d04(input.to_string(), 1), Some(27));
 /// ```
 pub fn y15d04(input: String, leading_zeros: u32) -> Option<u64> {
-    let check = Arc::new("0".repeat(leading_zeros as usize));
-    let input = input.trim().to_string();
+    y15d04_mine(&input, &[leading_zeros])[0]
+}
+
+/// Finds the smallest number for each of the given numbers of leading zeros
+/// in a single pass.
+///
+/// We keep one atomic "best" answer per prefix length (starting at
+/// `u64::MAX`) and a shared counter of the next block of numbers to check.
+/// Every thread claims the next block, hashes each number in it, and updates
+/// the best answer of every prefix length that the hash satisfies. Blocks are
+/// claimed in increasing order, so once a thread claims a block that starts
+/// after the largest of the best answers nothing that's left can improve any
+/// of them and the thread stops (the same goes for the rest of a block once
+/// we're past the answers). The answers are returned in the same order as the
+/// prefix lengths, or `None` if we ran out of numbers.
+///
+/// # Example
+/// ```rust
+/// # use aoc::y15d04::y15d04_mine;
+/// let input = "a"; // probably read this from the input file...
+/// assert_eq!(y15d04_mine(input, &[1, 2]), vec![Some(27), Some(269)]);
+/// ```
+pub fn y15d04_mine(input: &str, leading_zeros: &[u32]) -> Vec<Option<u64>> {
+    let prefix = Md5::new().chain_update(input.trim().as_bytes());
+    let best: Vec<_> = leading_zeros
+        .iter()
+        .map(|_| AtomicU64::new(u64::MAX))
+        .collect();
+    let next = AtomicU64::new(1);
     let threads = thread::available_parallelism().unwrap().get();
-    let chunks = 100000 / threads as u64;
-    let actual_chunks = chunks * threads as u64;
-    let mut results = Vec::new();
-
-    let mut i = 1;
-    while i < u64::MAX {
-        let mut handles = Vec::new();
-        for j in 0..threads as u64 {
-            let input = input.clone();
-            let check = Arc::clone(&check);
-            let start = i + j * chunks;
-            handles.push(thread::spawn(move || {
-                compute_hashes(input, start, start + chunks + 1, check)
-            }));
-        }
 
-        for handle in handles {
-            let result = handle.join().unwrap();
-            if let Some(answer) = result {
-                results.push(answer);
-            }
+    thread::scope(|scope| {
+        for _ in 0..threads {
+            scope.spawn(|| {
+                mine(&prefix, leading_zeros, &best, &next);
+            });
         }
+    });
+
+    best.into_iter()
+        .map(|answer| match answer.into_inner() {
+            u64::MAX => None,
+            answer => Some(answer),
+        })
+        .collect()
+}
 
-        if !results.is_empty() {
-            results.sort();
-            return Some(results[0]);
+/// This is the function that actually runs in each thread: keep claiming
+/// blocks of numbers and hashing them (after the input) until every prefix
+/// length has an answer that's smaller than the next block.
+fn mine(
+    prefix: &Md5,
+    leading_zeros: &[u32],
+    best: &[AtomicU64],
+    next: &AtomicU64,
+) {
+    let bound = || {
+        best.iter()
+            .map(|answer| answer.load(Ordering::Relaxed))
+            .max()
+            .unwrap_or(0)
+    };
+
+    loop {
+        let start = next.fetch_add(BLOCK, Ordering::Relaxed);
+        if start >= bound() || start > u64::MAX - BLOCK {
+            return;
         }
 
-        i += actual_chunks;
+        for i in start..start + BLOCK {
+            let mut buffer = [0; 20];
+            let digest = prefix
+                .clone()
+                .chain_update(digits(i, &mut buffer))
+                .finalize();
+            let zeros = zero_nibbles(&digest);
+
+            for (length, answer) in leading_zeros.iter().zip(best) {
+                if zeros >= *length {
+                    answer.fetch_min(i, Ordering::Relaxed);
+                }
+            }
+
+            if zeros > 0 && i >= bound() {
+                break;
+            }
+        }
     }
+}
+
+/// Writes the decimal digits of the number into the end of the buffer and
+/// returns them (so that we don't need to allocate a string for every hash).
+fn digits(mut n: u64, buffer: &mut [u8; 20]) -> &[u8] {
+    let mut start = buffer.len();
 
-    None
+    loop {
+        start -= 1;
+        buffer[start] = b'0' + (n % 10) as u8;
+        n /= 10;
+
+        if n == 0 {
+            return &buffer[start..];
+        }
+    }
 }
 
-/// This is the function that actually runs in the thread. It takes the input,
-/// start and end ranges, and the string of leading zeros to check againtst
-/// and then computes the associated `MD5` has for every integer in its
-/// range. It will stop early if it finds a match and return it otherwise it
-/// will return `None`.
-fn compute_hashes(
-    input: String,
-    start: u64,
-    end: u64,
-    check: Arc<String>,
-) -> Option<u64> {
-    let leading_zeros = check.len();
-
-    for i in start..end {
-        let hash = format!(
-            "{:x}",
-            Md5::new()
-                .chain_update(input.as_bytes())
-                .chain_update(i.to_string().as_bytes())
-                .finalize()
-        );
-        if hash.get(0..leading_zeros).unwrap() == *check {
-            return Some(i);
+/// Counts the number of leading zeros that the digest would have if it were
+/// formatted as hex (i.e., the leading zero nibbles).
+fn zero_nibbles(digest: &[u8]) -> u32 {
+    let mut zeros = 0;
+
+    for byte in digest {
+        match byte {
+            0 => zeros += 2,
+            1..=0x0f => return zeros + 1,
+            _ => return zeros,
         }
     }
 
-    None
+    zeros
 }
 
 #[cfg(test)]
@@ -147,12 +197,34 @@ mod tests {
         assert_eq!(y15d04(input.to_string(), 5), Some(1048970));
     }
 
+    #[test]
+    fn test_helpers() {
+        let mut buffer = [0; 20];
+        assert_eq!(digits(0, &mut buffer), b"0");
+        assert_eq!(digits(609043, &mut buffer), b"609043");
+        assert_eq!(digits(u64::MAX, &mut buffer), b"18446744073709551615");
+
+        assert_eq!(zero_nibbles(&[0x00, 0x00, 0x0f, 0xff]), 5);
+        assert_eq!(zero_nibbles(&[0x00, 0x10]), 2);
+        assert_eq!(zero_nibbles(&[0x00, 0x00]), 4);
+
+        assert_eq!(y15d04_mine("abc", &[]), vec![]);
+        assert_eq!(
+            y15d04_mine("a", &[2, 1, 3]),
+            vec![Some(269), Some(27), Some(1233)]
+        );
+    }
+
     #[test]
     #[ignore]
     fn the_solution() {
         let contents = fs::read_to_string("input/2015/day04.txt").unwrap();
 
         assert_eq!(y15d04(contents.clone(), 5).unwrap(), 254575);
-        assert_eq!(y15d04(contents, 6).unwrap(), 1038736);
+        assert_eq!(y15d04(contents.clone(), 6).unwrap(), 1038736);
+        assert_eq!(
+            y15d04_mine(&contents, &[5, 6]),
+            vec![Some(254575), Some(1038736)]
+        );
     }
 }
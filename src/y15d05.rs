@@ -17,22 +17,59 @@
 //!
 //! This challenge is relatively straightforward, it just needs to compare
 //! various substrings to check for matches and other conditions.
+//!
+//! Each of the conditions is a [`Rule`] of its own that reports not only
+//! whether it passed but also where in the string it looked, so that a
+//! [`Verdict`] can explain exactly why a string is naughty.
+
+use std::collections::HashMap;
+use std::fmt;
+
+/// The substrings that make a string naughty in part one.
+const FORBIDDEN: [&str; 4] = ["ab", "cd", "pq", "xy"];
+
+/// One of the conditions that decide whether a string is nice.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Rule {
+    /// At least this many vowels (`aeiou` only).
+    Vowels(usize),
+    /// A letter that appears twice in a row.
+    DoubleLetter,
+    /// None of these substrings.
+    Forbidden(Vec<String>),
+    /// A pair of letters that appears at least twice without overlapping.
+    RepeatedPair,
+    /// A letter that repeats with exactly one letter between them.
+    Sandwich,
+}
+
+/// The result of checking a string against a single [`Rule`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Check {
+    /// The rule that was checked.
+    pub rule: Rule,
+    /// Whether the string satisfies the rule.
+    pub passed: bool,
+    /// The (character) positions that the rule found: the vowels, the start
+    /// of the doubles, forbidden substrings and sandwiches, or the start of
+    /// both copies of the repeated pair.
+    pub positions: Vec<usize>,
+}
+
+/// The result of checking a string against a set of rules.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Verdict<'a> {
+    /// The string that was checked.
+    pub string: &'a str,
+    /// The result of each of the rules (in order).
+    pub checks: Vec<Check>,
+}
 
 /// The solution for the day five challenge.
 ///
 /// We expect the input as a string and either part `1` or part `2` to decide
-/// which ruleset to use to determine "nice" strings.
-///
-/// In part one nice strings contain at least three vowels (`aeiou` only),
-/// contains at least one letter that appears twice in a row, and does _not_
-/// contain any of these substrings: `ab`, `cd`, `pq`, or `xy`. The final rule
-/// takes precedence over the first two, that is if a string meets the first
-/// two criteria but has one of the forbidden substrings then it is still
-/// naughty.
-///
-/// In part two the rules are slightly different. A nice string contains at
-/// least one pair of non-overlapping (in position in the string) characters
-/// and at least one character that repeats after one other character.
+/// which ruleset to use to determine "nice" strings (see [`rules`]) and then
+/// count the lines that pass all of the rules.
 ///
 /// # Example
 /// ```rust
@@ -43,93 +80,176 @@
 /// assert_eq!(y15d05(input, 2), 1);
 /// ```
 pub fn y15d05(input: &str, part: u32) -> u32 {
-    let lines: Vec<_> = input.lines().collect();
-    let mut nice_strings = 0;
+    let rules = rules(part);
+    input
+        .lines()
+        .filter(|line| judge(line, &rules).is_nice())
+        .count() as u32
+}
 
-    for line in lines {
-        if (part == 1 && is_nice_p1(line)) || (part == 2 && is_nice_p2(line)) {
-            nice_strings += 1;
-        }
-    }
+/// Returns the verdict for every line of the input (one after the other) for
+/// the given part.
+pub fn y15d05_explain(input: &str, part: u32) -> String {
+    let rules = rules(part);
+    input
+        .lines()
+        .map(|line| judge(line, &rules).to_string())
+        .collect()
+}
 
-    nice_strings
+/// Returns the rules for the given part.
+///
+/// In part one nice strings contain at least three vowels (`aeiou` only),
+/// contains at least one letter that appears twice in a row, and does _not_
+/// contain any of these substrings: `ab`, `cd`, `pq`, or `xy`.
+///
+/// In part two the rules are slightly different. A nice string contains at
+/// least one pair of non-overlapping (in position in the string) characters
+/// and at least one character that repeats after one other character.
+pub fn rules(part: u32) -> Vec<Rule> {
+    if part == 1 {
+        vec![
+            Rule::Vowels(3),
+            Rule::DoubleLetter,
+            Rule::Forbidden(FORBIDDEN.iter().map(|s| s.to_string()).collect()),
+        ]
+    } else {
+        vec![Rule::RepeatedPair, Rule::Sandwich]
+    }
 }
 
-/// Evaluates the part one rules for the given string and returns true or
-/// false if the string is nice or not.
-fn is_nice_p1(s: &str) -> bool {
-    // these bad strings are immediately disqualifying so check them first
-    let bad_strings = ["ab", "cd", "pq", "xy"];
-    for bad_string in bad_strings {
-        if s.contains(bad_string) {
-            return false;
-        }
+/// Checks a string against each of the rules.
+///
+/// # Example
+/// ```rust
+/// # use aoc::y15d05::{judge, rules, Rule};
+/// let verdict = judge("haegwjzuvuyypxyu", &rules(1));
+/// assert!(!verdict.is_nice());
+///
+/// let failed: Vec<_> = verdict.failed().collect();
+/// assert_eq!(failed.len(), 1);
+/// assert!(matches!(failed[0].rule, Rule::Forbidden(_)));
+/// assert_eq!(failed[0].positions, vec![13]);
+/// ```
+pub fn judge<'a>(string: &'a str, rules: &[Rule]) -> Verdict<'a> {
+    Verdict {
+        string,
+        checks: rules.iter().map(|rule| rule.check(string)).collect(),
     }
+}
 
-    let chars: Vec<_> = s.chars().collect();
-    let mut vowels = 0;
-    let mut double = false;
-    for (i, c) in chars.iter().enumerate() {
-        if c == &'a' || c == &'e' || c == &'i' || c == &'o' || c == &'u' {
-            vowels += 1;
-        }
+impl Rule {
+    /// Checks the string against the rule.
+    pub fn check(&self, string: &str) -> Check {
+        let chars: Vec<_> = string.chars().collect();
 
-        // if we're not on the last character we can check if the next
-        // character is the same
-        if i < chars.len() - 1 && c == &chars[i + 1] {
-            double = true;
-        }
+        let (passed, positions) = match self {
+            Rule::Vowels(count) => {
+                let vowels: Vec<_> = (0..chars.len())
+                    .filter(|i| "aeiou".contains(chars[*i]))
+                    .collect();
+                (vowels.len() >= *count, vowels)
+            }
+            Rule::DoubleLetter => {
+                let doubles = starts(&chars, |w| w[0] == w[1], 2);
+                (!doubles.is_empty(), doubles)
+            }
+            Rule::Forbidden(substrings) => {
+                let found: Vec<_> = (0..chars.len())
+                    .filter(|i| {
+                        substrings.iter().any(|substring| {
+                            let substring: Vec<_> = substring.chars().collect();
+                            chars[*i..].starts_with(&substring)
+                        })
+                    })
+                    .collect();
+                (found.is_empty(), found)
+            }
+            Rule::RepeatedPair => {
+                // remember where we first saw each pair, any later copy of it
+                // that doesn't overlap will do
+                let mut first = HashMap::new();
+                let pair = chars.windows(2).enumerate().find_map(|(i, w)| {
+                    let j = *first.entry((w[0], w[1])).or_insert(i);
+                    (i >= j + 2).then_some(vec![j, i])
+                });
+                (pair.is_some(), pair.unwrap_or_default())
+            }
+            Rule::Sandwich => {
+                let sandwiches = starts(&chars, |w| w[0] == w[2], 3);
+                (!sandwiches.is_empty(), sandwiches)
+            }
+        };
 
-        // we can stop checking as soon as we meet all of the conditions
-        if double && vowels >= 3 {
-            return true;
+        Check {
+            rule: self.clone(),
+            passed,
+            positions,
         }
     }
-
-    false
 }
 
-/// Evaluates the part two rules for the given string and returns true or
-/// false if the string is nice or not.
-fn is_nice_p2(s: &str) -> bool {
-    let chars: Vec<_> = s.chars().collect();
-
-    let mut non_overlapping_pair = false;
-    let mut repeat_with_middle = false;
-    for i in 0..chars.len() {
-        // if we're not on the last character (windows are two characters long
-        // and we check the current character plus the next one) then we can
-        // evaluate the other character windows
-        if i != chars.len() - 1 {
-            for (j, window) in chars.windows(2).enumerate() {
-                // if we're in a window that overlaps with the current window
-                // then skip it (note the last condition checks to make sure
-                // we're on at least the second character before trying to
-                // discard previous windows that can't exist)
-                if i == j || i + 1 == j || (i >= 1 && i - 1 == j) {
-                    continue;
-                }
-
-                if chars[i] == window[0] && chars[i + 1] == window[1] {
-                    non_overlapping_pair = true;
-                }
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Rule::Vowels(count) => write!(f, "at least {} vowels", count),
+            Rule::DoubleLetter => write!(f, "a double letter"),
+            Rule::Forbidden(substrings) => {
+                write!(f, "none of {}", substrings.join(", "))
             }
+            Rule::RepeatedPair => write!(f, "a repeated pair"),
+            Rule::Sandwich => write!(f, "a letter repeated around another"),
         }
+    }
+}
 
-        // if we're not on the penultimate character we can look ahead two
-        // characters to see if it matches the current character
-        if i < chars.len() - 2 && chars[i] == chars[i + 2] {
-            repeat_with_middle = true;
-        }
+impl Verdict<'_> {
+    /// Returns whether the string passed all of the rules.
+    pub fn is_nice(&self) -> bool {
+        self.checks.iter().all(|check| check.passed)
+    }
 
-        // like in part one we can stop evaluating as soon as we meet all of
-        // the conditions
-        if non_overlapping_pair && repeat_with_middle {
-            return true;
+    /// Returns the checks for the rules that the string didn't pass.
+    pub fn failed(&self) -> impl Iterator<Item = &Check> {
+        self.checks.iter().filter(|check| !check.passed)
+    }
+}
+
+/// Shows the string, whether it's nice, and then a line for each rule with
+/// the positions that the rule found.
+///
+/// ```text
+/// ugknbfddgicrmopn: nice
+///   pass at least 3 vowels [0, 9, 13]
+///   pass a double letter [6]
+///   pass none of ab, cd, pq, xy []
+/// ```
+impl fmt::Display for Verdict<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let nice = if self.is_nice() { "nice" } else { "naughty" };
+        writeln!(f, "{}: {}", self.string, nice)?;
+
+        for check in &self.checks {
+            let result = if check.passed { "pass" } else { "fail" };
+            writeln!(f, "  {} {} {:?}", result, check.rule, check.positions)?;
         }
+
+        Ok(())
     }
+}
 
-    false
+/// Returns the start of every window of the given size that matches.
+fn starts(
+    chars: &[char],
+    matches: fn(&[char]) -> bool,
+    size: usize,
+) -> Vec<usize> {
+    chars
+        .windows(size)
+        .enumerate()
+        .filter(|(_, window)| matches(window))
+        .map(|(i, _)| i)
+        .collect()
 }
 
 #[cfg(test)]
@@ -137,6 +257,14 @@ mod tests {
     use super::*;
     use std::fs;
 
+    fn is_nice_p1(s: &str) -> bool {
+        judge(s, &rules(1)).is_nice()
+    }
+
+    fn is_nice_p2(s: &str) -> bool {
+        judge(s, &rules(2)).is_nice()
+    }
+
     #[test]
     fn test_is_nice_p1() {
         assert!(!is_nice_p1("aei"));
@@ -158,6 +286,41 @@ mod tests {
         assert!(is_nice_p2("xxyxx"));
         assert!(!is_nice_p2("uurcxstgmygtbstg"));
         assert!(!is_nice_p2("ieodomkazucvgmuy"));
+        assert!(!is_nice_p2("aaa"));
+        assert!(!is_nice_p2(""));
+    }
+
+    #[test]
+    fn test_verdict() {
+        let verdict = judge("jchzalrnumimnmhp", &rules(1));
+        assert_eq!(
+            verdict.failed().collect::<Vec<_>>(),
+            vec![&Check {
+                rule: Rule::DoubleLetter,
+                passed: false,
+                positions: vec![],
+            }]
+        );
+
+        let verdict = judge("uurcxstgmygtbstg", &rules(2));
+        assert_eq!(verdict.checks[0].positions, vec![5, 13]);
+        assert!(verdict.checks[0].passed);
+        assert!(!verdict.checks[1].passed);
+
+        assert_eq!(
+            judge("ugknbfddgicrmopn", &rules(1)).to_string(),
+            concat!(
+                "ugknbfddgicrmopn: nice\n",
+                "  pass at least 3 vowels [0, 9, 13]\n",
+                "  pass a double letter [6]\n",
+                "  pass none of ab, cd, pq, xy []\n",
+            )
+        );
+
+        let custom = [Rule::Vowels(1), Rule::Forbidden(vec!["x".to_string()])];
+        assert!(judge("ab", &custom).is_nice());
+        assert_eq!(judge("xax", &custom).checks[1].positions, vec![0, 2]);
+        assert_eq!(y15d05_explain("ab\nxax\n", 2).lines().count(), 6);
     }
 
     #[test]
@@ -18,8 +18,12 @@
 //! This challenge is pretty straightforward and amounts to dealing with a big
 //! grid of booleans in part one or a big grid of individual brightnesses
 //! (integers) in part two.
-
-use std::collections::HashMap;
+//!
+//! Neither of those grids needs to be that big though: the instructions only
+//! ever cover rectangles, so every light between two consecutive rectangle
+//! edges (in both directions) always ends up in the same state. We split the
+//! display into those regions (see [`Lights`]) and track one state per
+//! region, which doesn't care how big the display is.
 
 /// Instruction is a representation of the kind of operation to take: toggle
 /// the state of the light, turn if on (even if it's already on), or turn it
@@ -27,21 +31,50 @@ use std::collections::HashMap;
 /// a light that is already on or off this essentially results in a no-op. In
 /// part two in which we track total brightness turn off if the value is
 /// already zero results in a no-op.
-#[derive(Debug, PartialEq)]
-enum Instruction {
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Instruction {
+    /// Flip the light (part one) or increase the brightness by two (part
+    /// two).
     Toggle,
+    /// Turn the light on (part one) or increase the brightness by one (part
+    /// two).
     TurnOn,
+    /// Turn the light off (part one) or decrease the brightness by one (part
+    /// two).
     TurnOff,
 }
 
+/// An instruction and the (inclusive) corners of the rectangle of lights
+/// that it applies to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Step {
+    /// What to do to the lights.
+    pub instruction: Instruction,
+    /// The top-left corner of the rectangle.
+    pub from: (u64, u64),
+    /// The bottom-right corner of the rectangle.
+    pub to: (u64, u64),
+}
+
+/// The state of the lights after following the steps, kept per region.
+///
+/// `xs` and `ys` are the (sorted) coordinates where a rectangle starts or
+/// just ended so that the region `(i, j)` covers the lights from `xs[i]` up
+/// to (but not including) `xs[i + 1]` and from `ys[j]` up to `ys[j + 1]`.
+/// The edges are wider than the coordinates so that a rectangle can go all
+/// the way to `u64::MAX`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Lights {
+    xs: Vec<u128>,
+    ys: Vec<u128>,
+    on: Vec<bool>,
+    brightness: Vec<u64>,
+}
+
 /// The solution for part one of the day six challenge.
 ///
-/// Given the input as a string we start by building a `1000x1000` grid of
-/// booleans that represent the state of each light in each position. All of
-/// the lights start "off" (`false`). We then parse each instruction and loop
-/// through the lights in the instruction, turning them on or off as necessary.
-/// Finally, we make one final pass through every light and increment our final
-/// counter if the light is on and then return that final sum.
+/// Given the input as a string we parse the steps, follow them (see
+/// [`Lights::new`]) and then return how many lights are on.
 ///
 /// # Example
 /// ```rust
@@ -56,55 +89,15 @@ enum Instruction {
 /// assert_eq!(y15d06p1(input), 704);
 /// ```
 pub fn y15d06p1(input: &str) -> u32 {
-    let lines: Vec<_> = input.lines().collect();
-    let mut on = 0;
-
-    let mut lights = [[false; 1000]; 1000];
-
-    for line in lines {
-        let (instruction, x1, y1, x2, y2) = parse_instruction(line);
-
-        for light_row in lights.iter_mut().take(y2 + 1).skip(y1) {
-            for light in light_row.iter_mut().take(x2 + 1).skip(x1) {
-                if instruction == Instruction::Toggle {
-                    *light = !(*light);
-                } else {
-                    *light = instruction != Instruction::TurnOff;
-                }
-            }
-        }
-    }
-
-    for light_row in &lights {
-        for light in light_row.iter() {
-            if *light {
-                on += 1;
-            }
-        }
-    }
-
-    on
+    Lights::new(&parse(input)).lit().try_into().unwrap()
 }
 
 /// The solution part two of the day six challenge.
 ///
-/// Part two is both similar and dissimilar to part one. At first I wanted to
-/// manage the brightness count in a two-dimensional array of `u32`s but I
-/// kept overflowing the stack so instead we maintain a
-/// [`std::collections::HashMap`] of the brightness of each lights coordinates.
-/// If the lights current brightness is zero (or would result in zero after the
-/// operation) it does not have an entry in the map (or has its entry removed).
-///
-/// So, given our input as a string like in part one, we start by parsing each
-/// instruction and then loop through the light positions defined by the
-/// instruction. As mentioned in the prompt "turn on" means increase the
-/// brightness by one, "turn off" means decrease it by one (but not below
-/// zero), and "toggle" means increase the brightness by two. As we loop
-/// through each coordinate we create missing entries (lights that currently
-/// have a brightness of zero) and remove entries (lights whose brightness
-/// would become zero) as necessary. Finally, to compute the total brightness
-/// we just need to add the value of all of the lights that we're currently
-/// tracking (i.e., have a brightness of at least one).
+/// Part two is the same as part one except that we return the total
+/// brightness of the lights instead. As mentioned in the prompt "turn on"
+/// means increase the brightness by one, "turn off" means decrease it by one
+/// (but not below zero), and "toggle" means increase the brightness by two.
 ///
 /// # Example
 /// ```rust
@@ -119,86 +112,293 @@ pub fn y15d06p1(input: &str) -> u32 {
 /// assert_eq!(y15d06p2(input), 7457);
 /// ```
 pub fn y15d06p2(input: &str) -> u64 {
-    let lines: Vec<_> = input.lines().collect();
-    let mut lights = HashMap::new();
-    let mut total = 0;
-
-    for line in lines {
-        let (instruction, x1, y1, x2, y2) = parse_instruction(line);
-
-        for y in y1..y2 + 1 {
-            for x in x1..x2 + 1 {
-                if instruction == Instruction::Toggle {
-                    lights.entry((x, y)).and_modify(|v| *v += 2).or_insert(2);
-                } else if instruction == Instruction::TurnOff {
-                    let v = lights.entry((x, y)).or_insert(0);
-
-                    if *v == 0 {
-                        lights.remove(&(x, y));
-                    } else {
-                        *v -= 1;
+    Lights::new(&parse(input)).brightness()
+}
+
+/// Parses each line of the input into a [`Step`].
+///
+/// # Panics
+/// If a line is malformed.
+pub fn parse(input: &str) -> Vec<Step> {
+    input.lines().map(parse_instruction).collect()
+}
+
+impl Lights {
+    /// Splits the display into regions along the edges of every rectangle
+    /// and then follows the steps, updating every region inside of each
+    /// rectangle. Lights outside of all of the rectangles are never touched
+    /// so the display is as big as it needs to be.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use aoc::y15d06::{parse, Lights};
+    /// let input = concat!(
+    ///     "turn on 0,0 through 999999999,999999999\n",
+    ///     "toggle 1,1 through 999999998,999999998\n",
+    /// );
+    /// let lights = Lights::new(&parse(input));
+    /// assert_eq!(lights.lit(), 3999999996);
+    /// assert_eq!(lights.regions(), 9);
+    /// assert!(lights.is_on(0, 500) && !lights.is_on(1, 500));
+    /// assert_eq!(lights.brightness_at(1, 500), 3);
+    /// ```
+    pub fn new(steps: &[Step]) -> Lights {
+        let steps: Vec<_> = steps
+            .iter()
+            .filter(|s| s.from.0 <= s.to.0 && s.from.1 <= s.to.1)
+            .collect();
+
+        let edges = |axis: fn(&(u64, u64)) -> u64| {
+            let mut edges: Vec<u128> = steps
+                .iter()
+                .flat_map(|s| {
+                    [axis(&s.from).into(), u128::from(axis(&s.to)) + 1]
+                })
+                .collect();
+            edges.sort_unstable();
+            edges.dedup();
+            edges
+        };
+        let xs = edges(|p| p.0);
+        let ys = edges(|p| p.1);
+
+        let width = xs.len().saturating_sub(1);
+        let height = ys.len().saturating_sub(1);
+        let mut lights = Lights {
+            on: vec![false; width * height],
+            brightness: vec![0; width * height],
+            xs,
+            ys,
+        };
+
+        for step in steps {
+            let edge = |edges: &[u128], value: u64, offset: u128| {
+                edges.binary_search(&(u128::from(value) + offset)).unwrap()
+            };
+            let x1 = edge(&lights.xs, step.from.0, 0);
+            let x2 = edge(&lights.xs, step.to.0, 1);
+            let y1 = edge(&lights.ys, step.from.1, 0);
+            let y2 = edge(&lights.ys, step.to.1, 1);
+
+            for y in y1..y2 {
+                let row = y * width;
+                let on = &mut lights.on[row + x1..row + x2];
+                let brightness = &mut lights.brightness[row + x1..row + x2];
+
+                for (on, brightness) in on.iter_mut().zip(brightness) {
+                    match step.instruction {
+                        Instruction::Toggle => {
+                            *on = !*on;
+                            *brightness += 2;
+                        }
+                        Instruction::TurnOn => {
+                            *on = true;
+                            *brightness += 1;
+                        }
+                        Instruction::TurnOff => {
+                            *on = false;
+                            *brightness = brightness.saturating_sub(1);
+                        }
                     }
-                } else {
-                    lights.entry((x, y)).and_modify(|v| *v += 1).or_insert(1);
                 }
             }
         }
+
+        lights
+    }
+
+    /// Returns how many regions the display was split into.
+    pub fn regions(&self) -> usize {
+        self.on.len()
+    }
+
+    /// Returns how many lights are on.
+    ///
+    /// # Panics
+    /// If there are more lights on than fit in a `u64` (only possible with
+    /// rectangles that are billions of lights wide and tall).
+    pub fn lit(&self) -> u64 {
+        self.total(|i| u64::from(self.on[i]))
+    }
+
+    /// Returns the total brightness of all of the lights.
+    ///
+    /// # Panics
+    /// If the total doesn't fit in a `u64`.
+    pub fn brightness(&self) -> u64 {
+        self.total(|i| self.brightness[i])
     }
 
-    for value in lights.values() {
-        total += value;
+    /// Returns whether the light at the given coordinate is on.
+    pub fn is_on(&self, x: u64, y: u64) -> bool {
+        self.region(x, y).is_some_and(|i| self.on[i])
     }
 
-    total
+    /// Returns the brightness of the light at the given coordinate.
+    pub fn brightness_at(&self, x: u64, y: u64) -> u64 {
+        self.region(x, y).map_or(0, |i| self.brightness[i])
+    }
+
+    /// Adds up the value of each region multiplied by how many lights are
+    /// in it.
+    fn total(&self, value: impl Fn(usize) -> u64) -> u64 {
+        let width = self.xs.len().saturating_sub(1);
+        let mut total: u128 = 0;
+
+        for (j, ys) in self.ys.windows(2).enumerate() {
+            for (i, xs) in self.xs.windows(2).enumerate() {
+                total = u128::from(value(j * width + i))
+                    .checked_mul(xs[1] - xs[0])
+                    .and_then(|area| area.checked_mul(ys[1] - ys[0]))
+                    .and_then(|lights| total.checked_add(lights))
+                    .expect("too many lights to count");
+            }
+        }
+
+        total.try_into().expect("too many lights to count")
+    }
+
+    /// Returns the index of the region that contains the given coordinate.
+    fn region(&self, x: u64, y: u64) -> Option<usize> {
+        let find = |edges: &[u128], value: u64| {
+            let i = edges.partition_point(|edge| *edge <= value.into());
+            (0 < i && i < edges.len()).then(|| i - 1)
+        };
+
+        let width = self.xs.len().saturating_sub(1);
+        Some(find(&self.ys, y)? * width + find(&self.xs, x)?)
+    }
 }
 
 /// This function simply parses an input line and returns the necessary
 /// instruction with the matching coordinates because the positions of the
 /// coordinates change if the string starts with "toggle" or "turn on/off" by
 /// one.
-fn parse_instruction(line: &str) -> (Instruction, usize, usize, usize, usize) {
+fn parse_instruction(line: &str) -> Step {
     let parts: Vec<_> = line.split_whitespace().collect();
+    let (instruction, from, to) = if line.starts_with("toggle") {
+        (Instruction::Toggle, parts[1], parts[3])
+    } else if line.starts_with("turn off") {
+        (Instruction::TurnOff, parts[2], parts[4])
+    } else {
+        (Instruction::TurnOn, parts[2], parts[4])
+    };
 
-    if line.starts_with("toggle") {
-        let from: Vec<_> = parts[1].split(',').collect();
-        let to: Vec<_> = parts[3].split(',').collect();
-        let x1: usize = from[0].parse().unwrap();
-        let x2: usize = to[0].parse().unwrap();
-        let y1: usize = from[1].parse().unwrap();
-        let y2: usize = to[1].parse().unwrap();
+    let corner = |text: &str| {
+        let (x, y) = text.split_once(',').unwrap();
+        (x.parse().unwrap(), y.parse().unwrap())
+    };
 
-        (Instruction::Toggle, x1, y1, x2, y2)
-    } else {
-        let from: Vec<_> = parts[2].split(',').collect();
-        let to: Vec<_> = parts[4].split(',').collect();
-        let x1: usize = from[0].parse().unwrap();
-        let x2: usize = to[0].parse().unwrap();
-        let y1: usize = from[1].parse().unwrap();
-        let y2: usize = to[1].parse().unwrap();
-
-        if line.starts_with("turn off") {
-            (Instruction::TurnOff, x1, y1, x2, y2)
-        } else {
-            (Instruction::TurnOn, x1, y1, x2, y2)
-        }
+    Step {
+        instruction,
+        from: corner(from),
+        to: corner(to),
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
     use std::fs;
 
     #[test]
     fn test_parse_instruction() {
+        let step = |instruction, from, to| Step {
+            instruction,
+            from,
+            to,
+        };
+
         let mut l = "toggle 1,2 through 10,20";
-        assert_eq!(parse_instruction(l), (Instruction::Toggle, 1, 2, 10, 20));
+        assert_eq!(
+            parse_instruction(l),
+            step(Instruction::Toggle, (1, 2), (10, 20))
+        );
 
         l = "turn on 3,4 through 30,40";
-        assert_eq!(parse_instruction(l), (Instruction::TurnOn, 3, 4, 30, 40));
+        assert_eq!(
+            parse_instruction(l),
+            step(Instruction::TurnOn, (3, 4), (30, 40))
+        );
 
         l = "turn off 5,6 through 50,60";
-        assert_eq!(parse_instruction(l), (Instruction::TurnOff, 5, 6, 50, 60));
+        assert_eq!(
+            parse_instruction(l),
+            step(Instruction::TurnOff, (5, 6), (50, 60))
+        );
+    }
+
+    #[test]
+    fn test_lights() {
+        let mut rng = StdRng::seed_from_u64(6);
+        let instructions = [
+            Instruction::Toggle,
+            Instruction::TurnOn,
+            Instruction::TurnOff,
+        ];
+
+        for _ in 0..50 {
+            let steps: Vec<_> = (0..rng.gen_range(0..15))
+                .map(|_| {
+                    let (x1, x2) = (rng.gen_range(0..20), rng.gen_range(0..20));
+                    let (y1, y2) = (rng.gen_range(0..20), rng.gen_range(0..20));
+                    Step {
+                        instruction: instructions[rng.gen_range(0..3)],
+                        from: (x1.min(x2), y1.min(y2)),
+                        to: (x1.max(x2), y1.max(y2)),
+                    }
+                })
+                .collect();
+            let lights = Lights::new(&steps);
+
+            // brute force every light
+            let mut on = [[false; 20]; 20];
+            let mut brightness = [[0u64; 20]; 20];
+            for step in &steps {
+                for y in step.from.1..=step.to.1 {
+                    for x in step.from.0..=step.to.0 {
+                        let (x, y) = (x as usize, y as usize);
+                        match step.instruction {
+                            Instruction::Toggle => {
+                                on[y][x] = !on[y][x];
+                                brightness[y][x] += 2;
+                            }
+                            Instruction::TurnOn => {
+                                on[y][x] = true;
+                                brightness[y][x] += 1;
+                            }
+                            Instruction::TurnOff => {
+                                on[y][x] = false;
+                                brightness[y][x] =
+                                    brightness[y][x].saturating_sub(1);
+                            }
+                        }
+                    }
+                }
+            }
+
+            for y in 0..21 {
+                for x in 0..21 {
+                    let expected = on.get(y).and_then(|row| row.get(x));
+                    assert_eq!(
+                        lights.is_on(x as u64, y as u64),
+                        expected == Some(&true)
+                    );
+                    let expected = brightness.get(y).and_then(|row| row.get(x));
+                    assert_eq!(
+                        lights.brightness_at(x as u64, y as u64),
+                        expected.copied().unwrap_or(0)
+                    );
+                }
+            }
+
+            let lit = on.iter().flatten().filter(|on| **on).count();
+            assert_eq!(lights.lit(), lit as u64);
+            let total: u64 = brightness.iter().flatten().sum();
+            assert_eq!(lights.brightness(), total);
+        }
     }
 
     #[test]
@@ -218,10 +418,38 @@ mod tests {
 
         input = "turn on 0,0 through 0,0\ntoggle 0,0 through 999,999";
         assert_eq!(y15d06p2(input), 2000001);
+
+        assert_eq!(y15d06p1(""), 0);
+        assert_eq!(y15d06p2(""), 0);
+
+        let lights = Lights::new(&[]);
+        assert!(!lights.is_on(0, 0));
+        assert_eq!(lights.brightness_at(u64::MAX, 0), 0);
+
+        let max = u64::MAX;
+        let step = |instruction, from, to| Step {
+            instruction,
+            from,
+            to,
+        };
+        let lights = Lights::new(&[
+            step(Instruction::TurnOn, (max - 1, 0), (max, 2)),
+            step(Instruction::Toggle, (max, 1), (max, max - 10)),
+        ]);
+        assert!(lights.is_on(max - 1, 2) && !lights.is_on(max, 2));
+        assert!(lights.is_on(max, max - 10) && !lights.is_on(max, max));
+        assert_eq!(lights.lit(), max - 8);
+        assert_eq!(lights.brightness_at(max, 2), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "too many lights to count")]
+    fn test_too_many_lights() {
+        Lights::new(&parse("turn on 0,0 through 18446744073709551615,1\n"))
+            .lit();
     }
 
     #[test]
-    #[ignore]
     fn the_solution() {
         let contents = fs::read_to_string("input/2015/day06.txt").unwrap();
 
@@ -19,9 +19,65 @@
 //! beginning because it appears that the circuit can provide instructions
 //! out-of-order. Once accounting for that possibility it was pretty
 //! straightforward.
+//!
+//! My original solution kept re-scanning the instructions until every wire
+//! had a signal, which never finishes if a wire depends on a wire that
+//! doesn't exist (or on itself). Now the instructions are parsed once into a
+//! [`Circuit`] (a graph of gates keyed by their output wire) which is then
+//! evaluated in topological order, so undefined wires and cycles can be
+//! reported as a [`CircuitError`] instead.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+/// An input to a gate: either a literal signal or the signal of another wire.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Signal<'a> {
+    /// A literal signal.
+    Value(u16),
+    /// The signal of another wire.
+    Wire(&'a str),
+}
+
+/// A gate that produces the signal for a single wire.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Gate<'a> {
+    /// Passes the input signal through unchanged.
+    Assign(Signal<'a>),
+    /// Bitwise complement of the input.
+    Not(Signal<'a>),
+    /// Bitwise AND of the inputs.
+    And(Signal<'a>, Signal<'a>),
+    /// Bitwise OR of the inputs.
+    Or(Signal<'a>, Signal<'a>),
+    /// The input shifted left by the given number of bits.
+    LShift(Signal<'a>, u16),
+    /// The input shifted right by the given number of bits.
+    RShift(Signal<'a>, u16),
+}
 
-use regex::Regex;
-use std::collections::HashMap;
+/// The reasons that a circuit can't be evaluated.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CircuitError {
+    /// A wire was used (or queried) but no gate provides its signal.
+    Undefined(String),
+    /// The wires form a loop (the first wire is repeated at the end).
+    Cycle(Vec<String>),
+}
+
+/// The wires of a circuit and the gates that provide their signals.
+#[derive(Debug)]
+pub struct Circuit<'a> {
+    gates: BTreeMap<&'a str, Gate<'a>>,
+}
+
+/// The state of a wire during the depth-first search for the topological
+/// order.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Mark {
+    Visiting,
+    Done,
+}
 
 /// The solution for the day seven challenge.
 ///
@@ -30,29 +86,9 @@ use std::collections::HashMap;
 /// part `1` (normal) or for part `2` (override the signal of wire `b` with
 /// the signal of wire `a` calculated in part one).
 ///
-/// We start by parsing our input and creating our
-/// [`std::collections::HashMap`] that will store wires as keys and their
-/// signals as the values. If we're in part two then we calculate the signal
-/// for wire `a` in part one and then assign it to wire `b` immediately. This
-/// works because we only set new values if they don't exist (implemented
-/// during part one as it appeared that instructions could come out-of-order).
-///
-/// Now we start a loop that we'll use to run over the input until we've
-/// accounted for all of the wires. Each iteration of the loop assumes that
-/// we're done (so that it will exit when it's done) and then if we make _any_
-/// changes (i.e., assign a signal to a wire) then we mark it as not done so
-/// that we run the loop again. This lets us process the input even if there
-/// are some wires that we can't calculate yet because we don't have all of
-/// their input values.
-///
-/// From here it's pretty easy: figure out the wire to which we want to assign
-/// a signal (always the final component of the instruction) and if we've
-/// already assigned it a value then move on, otherwise figure out which
-/// operation we want to perform and perform it. If we haven't calculated the
-/// signal for any of the inputs then we skip it and try to calculate it again
-/// when we come back around on the next loop iteration.
-///
-/// Finally, return the signal of the wire that we asked for as an argument.
+/// All of the work happens in the [`Circuit`]: we parse it once and then ask
+/// for the signal of the wire, in part two we ask twice, the second time
+/// overriding wire `b`.
 ///
 /// **N.B.** the use of `u16` is important as it's specified in the prompt that
 /// the integers are 16-bit which changes the values when performing bitwise
@@ -67,96 +103,319 @@ use std::collections::HashMap;
 /// assert_eq!(y15d07(input, "a", 2), 12);
 /// ```
 pub fn y15d07(input: &str, wire: &str, part: u32) -> u16 {
-    let lines: Vec<_> = input.lines().collect();
-    let mut wires: HashMap<&str, u16> = HashMap::new();
+    let circuit = Circuit::parse(input);
+    let mut overrides = Vec::new();
 
     if part == 2 {
-        wires.insert("b", y15d07(input, "a", 1));
+        overrides.push(("b", circuit.signal("a", &[]).unwrap()));
     }
 
-    let r = Regex::new(r"^\d+ ").unwrap();
-    let mut done = false;
+    circuit.signal(wire, &overrides).unwrap()
+}
+
+impl<'a> Circuit<'a> {
+    /// Parses the circuit instructions (one gate per line).
+    ///
+    /// # Panics
+    /// If an instruction isn't one of the gates from the prompt.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use aoc::y15d07::{Circuit, Gate, Signal};
+    /// let circuit = Circuit::parse("x LSHIFT 2 -> f\n");
+    /// assert_eq!(
+    ///     circuit.gate("f"),
+    ///     Some(Gate::LShift(Signal::Wire("x"), 2))
+    /// );
+    /// ```
+    pub fn parse(input: &'a str) -> Self {
+        let mut gates = BTreeMap::new();
 
-    while !done {
-        // presume that we finished...
-        done = true;
+        for line in input.lines().filter(|line| !line.is_empty()) {
+            let text: Vec<_> = line.split_whitespace().collect();
+            let (wire, expression) = match text.split_last() {
+                Some((wire, [expression @ .., "->"])) => (*wire, expression),
+                _ => panic!("invalid instruction: {}", line),
+            };
 
-        for line in &lines {
-            let text: Vec<&str> = line.split_whitespace().collect();
-            let wire = text.last().unwrap();
+            let gate = match expression {
+                [input] => Gate::Assign(signal(input)),
+                ["NOT", input] => Gate::Not(signal(input)),
+                [left, "AND", right] => Gate::And(signal(left), signal(right)),
+                [left, "OR", right] => Gate::Or(signal(left), signal(right)),
+                [input, "LSHIFT", by] => {
+                    Gate::LShift(signal(input), by.parse().unwrap())
+                }
+                [input, "RSHIFT", by] => {
+                    Gate::RShift(signal(input), by.parse().unwrap())
+                }
+                _ => panic!("Unsupported operation!"),
+            };
+
+            gates.insert(wire, gate);
+        }
+
+        Self { gates }
+    }
 
-            match wires.get(wire) {
-                Some(_) => continue,
+    /// Returns the gate that provides the signal for the given wire.
+    pub fn gate(&self, wire: &str) -> Option<Gate<'a>> {
+        self.gates.get(wire).copied()
+    }
+
+    /// Returns the signal of a single wire. Overridden wires get the given
+    /// signal regardless of their gate (and the gate's inputs are never
+    /// evaluated, so they don't even need to exist).
+    ///
+    /// # Example
+    /// ```rust
+    /// # use aoc::y15d07::{Circuit, CircuitError};
+    /// let circuit = Circuit::parse("x AND y -> z\n3 -> x\n");
+    /// assert_eq!(
+    ///     circuit.signal("z", &[]),
+    ///     Err(CircuitError::Undefined("y".to_string()))
+    /// );
+    /// assert_eq!(circuit.signal("z", &[("y", 6)]), Ok(2));
+    /// ```
+    pub fn signal(
+        &self,
+        wire: &str,
+        overrides: &[(&str, u16)],
+    ) -> Result<u16, CircuitError> {
+        let signals = self.evaluate(&[wire], overrides)?;
+        Ok(signals[wire])
+    }
+
+    /// Returns the signals of every wire in the circuit (including any
+    /// overridden wires).
+    pub fn signals<'b>(
+        &self,
+        overrides: &[(&'b str, u16)],
+    ) -> Result<HashMap<&'b str, u16>, CircuitError>
+    where
+        'a: 'b,
+    {
+        let wires: Vec<_> = self.gates.keys().copied().collect();
+        let mut signals = self.evaluate(&wires, overrides)?;
+
+        for (wire, signal) in overrides {
+            signals.entry(*wire).or_insert(*signal);
+        }
+
+        Ok(signals)
+    }
+
+    /// Exports the circuit as a Graphviz DOT graph: every wire is a node
+    /// labeled with its gate and the edges go from the inputs of the gate to
+    /// its output wire.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use aoc::y15d07::Circuit;
+    /// let circuit = Circuit::parse("123 -> x\nNOT x -> h\n");
+    /// assert_eq!(
+    ///     circuit.to_dot(),
+    ///     concat!(
+    ///         "digraph circuit {\n",
+    ///         "  \"h\" [label=\"h\\nNOT\"];\n",
+    ///         "  \"x\" -> \"h\";\n",
+    ///         "  \"x\" [label=\"x\\n123\"];\n",
+    ///         "}\n",
+    ///     )
+    /// );
+    /// ```
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph circuit {\n");
+
+        for (wire, gate) in &self.gates {
+            let (label, inputs) = match gate {
+                Gate::Assign(Signal::Value(value)) => {
+                    (value.to_string(), vec![])
+                }
+                Gate::Assign(input) => (String::new(), vec![*input]),
+                Gate::Not(input) => ("NOT".to_string(), vec![*input]),
+                Gate::And(left, right) => {
+                    ("AND".to_string(), vec![*left, *right])
+                }
+                Gate::Or(left, right) => {
+                    ("OR".to_string(), vec![*left, *right])
+                }
+                Gate::LShift(input, by) => {
+                    (format!("LSHIFT {}", by), vec![*input])
+                }
+                Gate::RShift(input, by) => {
+                    (format!("RSHIFT {}", by), vec![*input])
+                }
+            };
+
+            if label.is_empty() {
+                dot += &format!("  \"{}\";\n", wire);
+            } else {
+                dot += &format!(
+                    "  \"{}\" [label=\"{}\\n{}\"];\n",
+                    wire, wire, label
+                );
+            }
+
+            for input in inputs {
+                match input {
+                    Signal::Wire(from) => {
+                        dot += &format!("  \"{}\" -> \"{}\";\n", from, wire);
+                    }
+                    Signal::Value(value) => {
+                        dot += &format!(
+                            "  \"{}:{}\" [label=\"{}\", shape=plaintext];\n",
+                            wire, value, value
+                        );
+                        dot += &format!(
+                            "  \"{}:{}\" -> \"{}\";\n",
+                            wire, value, wire
+                        );
+                    }
+                }
+            }
+        }
+
+        dot + "}\n"
+    }
+
+    /// Computes the signals of the given wires (and of every wire that they
+    /// depend on) by first putting the wires in topological order and then
+    /// evaluating their gates in that order.
+    fn evaluate<'b>(
+        &self,
+        wires: &[&'b str],
+        overrides: &[(&str, u16)],
+    ) -> Result<HashMap<&'b str, u16>, CircuitError>
+    where
+        'a: 'b,
+    {
+        let overrides: HashMap<_, _> = overrides.iter().copied().collect();
+        let mut marks = HashMap::new();
+        let mut order = Vec::new();
+
+        for wire in wires {
+            self.visit(
+                wire,
+                &overrides,
+                &mut marks,
+                &mut Vec::new(),
+                &mut order,
+            )?;
+        }
+
+        let mut signals: HashMap<&str, u16> = HashMap::new();
+        for wire in order {
+            let signal = match overrides.get(wire) {
+                Some(signal) => *signal,
                 None => {
-                    // we're making a change, so run through again
-                    done = false;
-
-                    if text.len() == 3 {
-                        // simple assignment
-                        if r.is_match(line) {
-                            let signal = text[0].parse().unwrap();
-                            wires.insert(wire, signal);
-                        } else {
-                            match wires.get(text[0]) {
-                                Some(signal) => {
-                                    wires.insert(wire, *signal);
-                                }
-                                None => continue,
-                            }
-                        }
-                    } else if text[0] == "NOT" {
-                        match wires.get(text[1]) {
-                            Some(signal) => {
-                                wires.insert(wire, !signal);
-                            }
-                            None => continue,
-                        }
-                    } else if text[1] == "AND" || text[1] == "OR" {
-                        let lparse = &text[0].parse();
-                        let leftarg = match lparse {
-                            Ok(arg) => arg,
-                            Err(_) => match wires.get(text[0]) {
-                                Some(signal) => signal,
-                                None => continue,
-                            },
-                        };
-
-                        let rparse = &text[2].parse();
-                        let rightarg = match rparse {
-                            Ok(arg) => arg,
-                            Err(_) => match wires.get(text[2]) {
-                                Some(signal) => signal,
-                                None => continue,
-                            },
-                        };
-
-                        if text[1] == "AND" {
-                            wires.insert(wire, leftarg & rightarg);
-                        } else {
-                            wires.insert(wire, leftarg | rightarg);
+                    let value = |input: Signal| match input {
+                        Signal::Value(value) => value,
+                        Signal::Wire(wire) => signals[wire],
+                    };
+
+                    match self.gates[wire] {
+                        Gate::Assign(input) => value(input),
+                        Gate::Not(input) => !value(input),
+                        Gate::And(left, right) => value(left) & value(right),
+                        Gate::Or(left, right) => value(left) | value(right),
+                        // shifting a 16-bit signal by 16 or more clears it
+                        Gate::LShift(input, by) => {
+                            value(input).checked_shl(by.into()).unwrap_or(0)
                         }
-                    } else if text[1] == "LSHIFT" || text[1] == "RSHIFT" {
-                        let by: u16 = text[2].parse().unwrap();
-
-                        match wires.get(text[0]) {
-                            Some(signal) => {
-                                if text[1] == "LSHIFT" {
-                                    wires.insert(wire, signal << by);
-                                } else {
-                                    wires.insert(wire, signal >> by);
-                                }
-                            }
-                            None => continue,
+                        Gate::RShift(input, by) => {
+                            value(input).checked_shr(by.into()).unwrap_or(0)
                         }
-                    } else {
-                        panic!("Unsupported operation!");
                     }
                 }
+            };
+
+            signals.insert(wire, signal);
+        }
+
+        Ok(signals)
+    }
+
+    /// Depth-first search that adds a wire to the order only after all of
+    /// the wires that its gate depends on. The path is the chain of wires
+    /// that we're currently visiting so that we can report the cycle if we
+    /// come across one of them again.
+    fn visit<'b>(
+        &self,
+        wire: &'b str,
+        overrides: &HashMap<&str, u16>,
+        marks: &mut HashMap<&'b str, Mark>,
+        path: &mut Vec<&'b str>,
+        order: &mut Vec<&'b str>,
+    ) -> Result<(), CircuitError>
+    where
+        'a: 'b,
+    {
+        match marks.get(wire) {
+            Some(Mark::Done) => return Ok(()),
+            Some(Mark::Visiting) => {
+                let start = path.iter().position(|w| *w == wire).unwrap();
+                let mut cycle: Vec<_> =
+                    path[start..].iter().map(|w| w.to_string()).collect();
+                cycle.push(wire.to_string());
+                return Err(CircuitError::Cycle(cycle));
+            }
+            None => (),
+        }
+
+        if !overrides.contains_key(wire) {
+            let gate = match self.gates.get(wire) {
+                Some(gate) => gate,
+                None => return Err(CircuitError::Undefined(wire.to_string())),
+            };
+
+            marks.insert(wire, Mark::Visiting);
+            path.push(wire);
+
+            let inputs = match *gate {
+                Gate::Assign(input)
+                | Gate::Not(input)
+                | Gate::LShift(input, _)
+                | Gate::RShift(input, _) => [Some(input), None],
+                Gate::And(left, right) | Gate::Or(left, right) => {
+                    [Some(left), Some(right)]
+                }
+            };
+
+            for input in inputs.into_iter().flatten() {
+                if let Signal::Wire(input) = input {
+                    self.visit(input, overrides, marks, path, order)?;
+                }
             }
+
+            path.pop();
         }
+
+        marks.insert(wire, Mark::Done);
+        order.push(wire);
+        Ok(())
     }
+}
 
-    *wires.get(wire).unwrap()
+impl fmt::Display for CircuitError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CircuitError::Undefined(wire) => {
+                write!(f, "wire {} has no signal", wire)
+            }
+            CircuitError::Cycle(wires) => {
+                write!(f, "wires form a cycle: {}", wires.join(" -> "))
+            }
+        }
+    }
+}
+
+/// Parses a gate input: numbers are literal signals, anything else is a wire.
+fn signal(input: &str) -> Signal<'_> {
+    match input.parse() {
+        Ok(value) => Signal::Value(value),
+        Err(_) => Signal::Wire(input),
+    }
 }
 
 #[cfg(test)]
@@ -185,6 +444,42 @@ mod tests {
         assert_eq!(y15d07(input, "i", 1), 65079);
         assert_eq!(y15d07(input, "x", 1), 123);
         assert_eq!(y15d07(input, "y", 1), 456);
+
+        let circuit = Circuit::parse(input);
+        let signals = circuit.signals(&[("x", 1)]).unwrap();
+        assert_eq!(signals.len(), 8);
+        assert_eq!(signals["x"], 1);
+        assert_eq!(signals["d"], 0);
+        assert_eq!(signals["f"], 4);
+        assert_eq!(signals["h"], 65534);
+    }
+
+    #[test]
+    fn test_errors() {
+        let circuit = Circuit::parse("a OR b -> c\nc -> b\n1 -> a\n");
+        assert_eq!(
+            circuit.signal("c", &[]),
+            Err(CircuitError::Cycle(vec![
+                "c".to_string(),
+                "b".to_string(),
+                "c".to_string()
+            ]))
+        );
+        assert_eq!(circuit.signal("c", &[("b", 2)]), Ok(3));
+        assert_eq!(
+            circuit.signal("d", &[]),
+            Err(CircuitError::Undefined("d".to_string()))
+        );
+        assert_eq!(circuit.signal("d", &[("d", 7)]), Ok(7));
+
+        assert_eq!(y15d07("1 LSHIFT 16 -> a\n", "a", 1), 0);
+        assert_eq!(y15d07("65535 RSHIFT 99 -> a\n", "a", 1), 0);
+        assert_eq!(y15d07("3 LSHIFT 15 -> a\n", "a", 1), 32768);
+
+        let dot = circuit.to_dot();
+        assert!(dot.contains("  \"c\" [label=\"c\\nOR\"];\n"));
+        assert!(dot.contains("  \"b\" -> \"c\";\n"));
+        assert!(dot.contains("  \"c\" -> \"b\";\n"));
     }
 
     #[test]
@@ -18,127 +18,296 @@
 //! Today's challenge was very simple. It essentially amounted to looping over
 //! some strings with some counters and in a couple of cases changing how the
 //! counters were incremented.
+//!
+//! Counting characters never actually tells us what's in the strings though
+//! (or whether they're even valid), so the counts now come from a proper
+//! codec: [`decode`] turns a literal into the bytes it represents (with the
+//! position of anything malformed) and [`encode`] turns bytes back into a
+//! literal. [`encode_with`] can also escape only what the prompt escapes.
+
+use std::fmt;
+
+/// The ways that a string literal can be malformed. All of the positions are
+/// byte offsets into the literal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The literal doesn't start (or end) with a quote.
+    Unquoted(usize),
+    /// There's a quote inside of the literal that isn't escaped.
+    UnescapedQuote(usize),
+    /// A backslash isn't followed by `\`, `"` or `x` (or by anything).
+    InvalidEscape(usize),
+    /// A `\x` escape isn't followed by two hexadecimal digits.
+    InvalidHex(usize),
+}
+
+/// What gets escaped when encoding a literal (quotes and backslashes always
+/// are).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Escape {
+    /// Only quotes and backslashes, like the prompt for part two. Everything
+    /// else is left as it is.
+    Prompt,
+    /// Anything that isn't printable ASCII too (as a `\xNN` escape).
+    Printable,
+}
 
 /// The solution for part one of the day eight challenge.
 ///
-/// Given the input string we initialize the total sums of each type and then
-/// loop over each string. The "code" representation we can find by simply
-/// calling the `.len()` function (and converting it to a `u32`). To calculate
-/// the "memory" representation we loop over all of the characters. If we have
-/// previously encountered the escape character then we check if we have an `x`
-/// which means a hexadecimal escape (and we need to skip the next two
-/// characters) or not in which case we just move on without incrementing the
-/// counter. If we have a skip value set then decrement it and move on without
-/// incrementing the counter. If we encounter the `"` we don't increment the
-/// counter because it's the start/end. Otherwise we increment the counter.
-/// If we've encountered the escape character then trigger the behavior
-/// described above for the next loop iteration. Finally, add the "memory" sum
-/// to the running total and then return the "code" sum minus the "memory" sum
-/// as requested by the prompt.
+/// Given the input string we decode each of the string literals (see
+/// [`decode`]) and return the total length of the literals minus the total
+/// number of bytes that they decode to as requested by the prompt.
+///
+/// # Panics
+/// If any of the string literals are malformed.
 ///
 /// # Example
 /// ```rust
 /// # use aoc::y15d08::y15d08p1;
 /// // probably read this from the input file...
-/// let input = "\"a\"b\"\n\"\"\n\"\x23\"";
-/// assert_eq!(y15d08p1(input), 7);
+/// let input = "\"a\\\"b\"\n\"\"\n\"\\x23\"";
+/// assert_eq!(y15d08p1(input), 10);
 /// ```
 pub fn y15d08p1(input: &str) -> u32 {
-    let lines: Vec<_> = input.lines().collect();
-    let mut codesum = 0;
-    let mut memsum = 0;
-
-    for line in lines {
-        let mut memlen: u32 = 0;
-        let codelen: u32 = line.len().try_into().unwrap();
-        codesum += codelen;
-
-        let chars: Vec<_> = line.trim().chars().collect();
-        let mut skip = 0;
-        let mut escape = false;
-        for c in chars {
-            if escape {
-                if c == 'x' {
-                    // we got a hex escape, skip two more
-                    skip = 2;
-                    escape = false;
-                    continue;
-                } else {
-                    escape = false;
-                    continue;
-                }
-            }
-
-            if skip > 0 {
-                skip -= 1;
-                continue;
-            }
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            let bytes = decode(line).unwrap_or_else(|error| {
+                panic!("line {}: {}", i + 1, error);
+            });
+            line.len() - bytes.len()
+        })
+        .sum::<usize>()
+        .try_into()
+        .unwrap()
+}
 
-            if c == '"' {
-                continue;
-            }
+/// The solution for part two of the day eight challenge.
+///
+/// Even easier than part one, we encode each of the input lines as a new
+/// string literal and return the total length of the new literals minus the
+/// total length of the lines as requested by the prompt. The prompt's
+/// encoding only escapes quotes and backslashes (unlike [`encode`] which also
+/// escapes anything that isn't printable) so we use [`Escape::Prompt`].
+///
+/// # Example
+/// ```rust
+/// # use aoc::y15d08::y15d08p2;
+/// // probably read this from the input file...
+/// let input = "\"a\\\"b\"\n\"\"\n\"\\x23\"";
+/// assert_eq!(y15d08p2(input), 15);
+/// ```
+pub fn y15d08p2(input: &str) -> u32 {
+    input
+        .lines()
+        .map(|line| {
+            encode_with(line.as_bytes(), Escape::Prompt).len() - line.len()
+        })
+        .sum::<usize>()
+        .try_into()
+        .unwrap()
+}
 
-            memlen += 1;
+/// Decodes a string literal (surrounded by quotes, with `\\`, `\"` and `\xNN`
+/// escapes) into the bytes that it represents.
+///
+/// # Example
+/// ```rust
+/// # use aoc::y15d08::{decode, DecodeError};
+/// assert_eq!(decode(r#""a\"b\\\x41""#), Ok(b"a\"b\\A".to_vec()));
+/// assert_eq!(decode(r#""ab\q""#), Err(DecodeError::InvalidEscape(3)));
+/// assert_eq!(decode(r#""\x4""#), Err(DecodeError::InvalidHex(1)));
+/// ```
+pub fn decode(literal: &str) -> Result<Vec<u8>, DecodeError> {
+    let bytes = literal.as_bytes();
+    if bytes.first() != Some(&b'"') {
+        return Err(DecodeError::Unquoted(0));
+    }
 
-            if c == '\\' {
-                escape = true;
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 1;
+    loop {
+        match bytes.get(i) {
+            None => return Err(DecodeError::Unquoted(i)),
+            Some(b'"') if i == bytes.len() - 1 => return Ok(decoded),
+            Some(b'"') => return Err(DecodeError::UnescapedQuote(i)),
+            Some(b'\\') => {
+                let (byte, length) = match bytes.get(i + 1) {
+                    Some(b'\\') => (b'\\', 2),
+                    Some(b'"') => (b'"', 2),
+                    Some(b'x') => (hex(bytes, i)?, 4),
+                    _ => return Err(DecodeError::InvalidEscape(i)),
+                };
+                decoded.push(byte);
+                i += length;
+            }
+            Some(byte) => {
+                decoded.push(*byte);
+                i += 1;
             }
         }
-
-        memsum += memlen;
     }
-
-    codesum - memsum
 }
 
-/// The solution for part two of the day eight challenge.
+/// Encodes bytes as a string literal: surrounded by quotes with backslashes
+/// and quotes escaped and anything that isn't printable ASCII as a `\xNN`
+/// escape, so that [`decode`] gives back the same bytes.
 ///
-/// Even easier than part one, we loop through all of the input lines
-/// calculating the "code" sum the same as in part one (with the `.len()`
-/// function). To calculate the "encoded" sum we start with `2` to account for
-/// the new `"` that are added to the start and end of the string. Then we
-/// loop through the characters and if we encounter the `"` or `\` characters
-/// we increment by two to account for the escape otherwise we increment by
-/// one as its standard. Add the tallies for the line to the totals and then
-/// return the "encoded" sum minus the "code" sum as requested by the prompt.
+/// # Example
+/// ```rust
+/// # use aoc::y15d08::encode;
+/// assert_eq!(encode(br#""aaa\"aaa""#), r#""\"aaa\\\"aaa\"""#);
+/// assert_eq!(encode(&[0, b'a', 255]), r#""\x00a\xff""#);
+/// ```
+pub fn encode(bytes: &[u8]) -> String {
+    // everything that's left is printable ASCII
+    String::from_utf8(encode_with(bytes, Escape::Printable)).unwrap()
+}
+
+/// Encodes bytes as a string literal, escaping only what the given [`Escape`]
+/// asks for. With [`Escape::Prompt`] any other bytes are copied as they are
+/// (so the literal is only valid UTF-8 if the bytes were).
 ///
 /// # Example
 /// ```rust
-/// # use aoc::y15d08::y15d08p2;
-/// // probably read this from the input file...
-/// let input = "\"a\"b\"\n\"\"\n\"\x23\"";
-/// assert_eq!(y15d08p2(input), 13);
+/// # use aoc::y15d08::{encode_with, Escape};
+/// let bytes = "a\"é".as_bytes();
+/// assert_eq!(encode_with(bytes, Escape::Prompt), "\"a\\\"é\"".as_bytes());
+/// assert_eq!(encode_with(bytes, Escape::Printable), br#""a\"\xc3\xa9""#);
 /// ```
-pub fn y15d08p2(input: &str) -> u32 {
-    let lines: Vec<_> = input.lines().collect();
-    let mut codesum = 0;
-    let mut encsum = 0;
-
-    for line in lines {
-        let mut enclen: u32 = 2;
-        let codelen: u32 = line.len().try_into().unwrap();
-        codesum += codelen;
-
-        let chars: Vec<_> = line.trim().chars().collect();
-        for c in chars {
-            if c == '"' || c == '\\' {
-                enclen += 2;
-            } else {
-                enclen += 1;
+pub fn encode_with(bytes: &[u8], escape: Escape) -> Vec<u8> {
+    let mut encoded = Vec::with_capacity(bytes.len() + 2);
+    encoded.push(b'"');
+
+    for byte in bytes {
+        match (byte, escape) {
+            (b'"' | b'\\', _) => encoded.extend([b'\\', *byte]),
+            (b' '..=b'~', _) | (_, Escape::Prompt) => encoded.push(*byte),
+            (_, Escape::Printable) => {
+                encoded.extend(format!("\\x{:02x}", byte).bytes())
             }
         }
-
-        encsum += enclen;
     }
 
-    encsum - codesum
+    encoded.push(b'"');
+    encoded
+}
+
+/// Reads the two hexadecimal digits of the `\x` escape at the given
+/// position.
+fn hex(bytes: &[u8], position: usize) -> Result<u8, DecodeError> {
+    bytes
+        .get(position + 2..position + 4)
+        .filter(|digits| digits.iter().all(u8::is_ascii_hexdigit))
+        .map(|digits| {
+            let digits = std::str::from_utf8(digits).unwrap();
+            u8::from_str_radix(digits, 16).unwrap()
+        })
+        .ok_or(DecodeError::InvalidHex(position))
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DecodeError::Unquoted(position) => {
+                write!(f, "expected a quote at {}", position)
+            }
+            DecodeError::UnescapedQuote(position) => {
+                write!(f, "unescaped quote at {}", position)
+            }
+            DecodeError::InvalidEscape(position) => {
+                write!(f, "invalid escape at {}", position)
+            }
+            DecodeError::InvalidHex(position) => {
+                write!(f, "invalid hexadecimal escape at {}", position)
+            }
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
     use std::fs;
 
+    #[test]
+    fn test_decode() {
+        assert_eq!(decode(r#""""#), Ok(vec![]));
+        assert_eq!(decode(r#""abc""#), Ok(b"abc".to_vec()));
+        assert_eq!(decode(r#""aaa\"aaa""#), Ok(b"aaa\"aaa".to_vec()));
+        assert_eq!(decode(r#""\x27""#), Ok(vec![0x27]));
+        assert_eq!(decode(r#""\xfF\x00""#), Ok(vec![0xff, 0]));
+
+        assert_eq!(decode(""), Err(DecodeError::Unquoted(0)));
+        assert_eq!(decode("abc\""), Err(DecodeError::Unquoted(0)));
+        assert_eq!(decode("\""), Err(DecodeError::Unquoted(1)));
+        assert_eq!(decode(r#""abc"#), Err(DecodeError::Unquoted(4)));
+        assert_eq!(decode(r#""abc\""#), Err(DecodeError::Unquoted(6)));
+        assert_eq!(decode(r#""a"b""#), Err(DecodeError::UnescapedQuote(2)));
+        assert_eq!(decode(r#""a\n""#), Err(DecodeError::InvalidEscape(2)));
+        assert_eq!(decode(r#""a\"#), Err(DecodeError::InvalidEscape(2)));
+        assert_eq!(decode(r#""\xg1""#), Err(DecodeError::InvalidHex(1)));
+        assert_eq!(decode(r#""\x1""#), Err(DecodeError::InvalidHex(1)));
+
+        assert_eq!(
+            DecodeError::InvalidHex(1).to_string(),
+            "invalid hexadecimal escape at 1"
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut rng = StdRng::seed_from_u64(8);
+
+        for _ in 0..500 {
+            let length = rng.gen_range(0..20);
+            let bytes: Vec<u8> = (0..length).map(|_| rng.gen()).collect();
+            let encoded = encode(&bytes);
+
+            assert_eq!(decode(&encoded), Ok(bytes));
+            assert_eq!(encode(&decode(&encoded).unwrap()), encoded);
+        }
+
+        let contents = fs::read_to_string("input/2015/day08.txt").unwrap();
+        for line in contents.lines() {
+            let encoded = encode(line.as_bytes());
+            assert_eq!(decode(&encoded), Ok(line.as_bytes().to_vec()));
+
+            let encoded = encode_with(line.as_bytes(), Escape::Prompt);
+            let encoded = String::from_utf8(encoded).unwrap();
+            assert_eq!(decode(&encoded), Ok(line.as_bytes().to_vec()));
+        }
+    }
+
+    #[test]
+    fn test_round_trip_prompt() {
+        let mut rng = StdRng::seed_from_u64(8);
+        let letters = ['a', '"', '\\', 'x', '4', '\t', 'é', '\0'];
+
+        for _ in 0..500 {
+            let length = rng.gen_range(0..20);
+            let text: String = (0..length)
+                .map(|_| letters[rng.gen_range(0..letters.len())])
+                .collect();
+            let encoded = encode_with(text.as_bytes(), Escape::Prompt);
+            let encoded = String::from_utf8(encoded).unwrap();
+
+            assert_eq!(decode(&encoded), Ok(text.as_bytes().to_vec()));
+            // only quotes and backslashes grow
+            let escaped = text.matches(['"', '\\']).count();
+            assert_eq!(encoded.len(), text.len() + escaped + 2);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "line 2: unescaped quote at 2")]
+    fn test_malformed() {
+        y15d08p1("\"ok\"\n\"a\"b\"\n");
+    }
+
     #[test]
     fn it_works() {
         let input =
@@ -146,6 +315,11 @@ mod tests {
 
         assert_eq!(y15d08p1(input), 12);
         assert_eq!(y15d08p2(input), 19);
+
+        // only quotes and backslashes get escaped in part two, even if the
+        // line has bytes that encode() would escape
+        assert_eq!(y15d08p2("\"a\tb\"\n\"é\"\n"), 8);
+        assert_eq!(encode("\"é\"".as_bytes()).len(), 14);
     }
 
     #[test]
@@ -19,13 +19,97 @@
 //! so we don't need to worry about building any graphs and then trying to
 //! find the shortest path or anything. We can just compute all of the possible
 //! trips and their distances and then get the shortest/longest.
+//!
+//! Computing all of the trips stops being feasible with more than about a
+//! dozen cities though, so the solution now uses the shared
+//! [`crate::util::tour`] solver (the original version is still around as
+//! [`y15d09_permutations`]).
 
+use crate::util::tour::{best_tour, Goal, Shape};
 use itertools::Itertools;
 use std::cmp::Reverse;
 use std::collections::{BinaryHeap, HashMap};
 
 /// The solution for the day nine challenge.
 ///
+/// We take the input as a string and the problem part as an integer as usual
+/// and return the distance of the shortest (part `1`) or longest (part `2`)
+/// route (see [`y15d09_route`]).
+///
+/// # Panics
+/// If there isn't a route that visits every city.
+///
+/// # Example
+/// ```rust
+/// # use aoc::y15d09::y15d09;
+/// // probably read this from the input file...
+/// let input = "A to B = 10\nB to C = 15\nA to C = 25\n";
+/// assert_eq!(y15d09(input, 1), 25);
+/// assert_eq!(y15d09(input, 2), 40);
+/// ```
+pub fn y15d09(input: &str, part: u32) -> u32 {
+    y15d09_route(input, part)
+        .expect("no route visits every city")
+        .1
+}
+
+/// Returns the cities in the order of the shortest (part `1`) or longest
+/// (part `2`) route that visits each of them once along with its distance.
+///
+/// We parse the input into a list of the cities and a matrix of the distances
+/// between them and then find the best open path through all of them.
+/// Cities that aren't listed as having a leg between them aren't connected,
+/// so if that means that there's no way to visit every city then there's no
+/// route (and we return `None`).
+///
+/// # Example
+/// ```rust
+/// # use aoc::y15d09::y15d09_route;
+/// // probably read this from the input file...
+/// let input = "A to B = 10\nB to C = 15\nA to C = 25\n";
+/// assert_eq!(y15d09_route(input, 1), Some((vec!["C", "B", "A"], 25)));
+/// assert_eq!(y15d09_route(input, 2), Some((vec!["B", "C", "A"], 40)));
+/// assert_eq!(y15d09_route("A to B = 5\nC to D = 1\n", 1), None);
+/// ```
+pub fn y15d09_route(input: &str, part: u32) -> Option<(Vec<&str>, u32)> {
+    let mut cities = Vec::new();
+    let mut legs = Vec::new();
+
+    for line in input.lines() {
+        let text: Vec<&str> = line.split_whitespace().collect();
+        let distance: i64 = text[4].parse().unwrap();
+        let mut index = |city| match cities.iter().position(|c| *c == city) {
+            Some(index) => index,
+            None => {
+                cities.push(city);
+                cities.len() - 1
+            }
+        };
+
+        legs.push((index(text[0]), index(text[2]), distance));
+    }
+
+    let mut distances = vec![vec![None; cities.len()]; cities.len()];
+    for (a, b, distance) in legs {
+        distances[a][b] = Some(distance);
+        distances[b][a] = Some(distance);
+    }
+
+    let goal = if part == 1 {
+        Goal::Minimize
+    } else {
+        Goal::Maximize
+    };
+    let tour = best_tour(&distances, Shape::Path, goal)?;
+
+    Some((
+        tour.order.into_iter().map(|city| cities[city]).collect(),
+        tour.cost.try_into().unwrap(),
+    ))
+}
+
+/// The original solution for the day nine challenge.
+///
 /// We take the input as a string and the problem part as an integer as usual.
 /// We start by iterating over the lines and parsing the city names and the
 /// distances. We maintain a [`std::collections::HashMap`] of the city names
@@ -40,13 +124,13 @@ use std::collections::{BinaryHeap, HashMap};
 ///
 /// # Example
 /// ```rust
-/// # use aoc::y15d09::y15d09;
+/// # use aoc::y15d09::y15d09_permutations;
 /// // probably read this from the input file...
 /// let input = "A to B = 10\nB to C = 15\nA to C = 25\n";
-/// assert_eq!(y15d09(input, 1), 25);
-/// assert_eq!(y15d09(input, 2), 40);
+/// assert_eq!(y15d09_permutations(input, 1), 25);
+/// assert_eq!(y15d09_permutations(input, 2), 40);
 /// ```
-pub fn y15d09(input: &str, part: u32) -> u32 {
+pub fn y15d09_permutations(input: &str, part: u32) -> u32 {
     let lines: Vec<_> = input.lines().collect();
     let mut distances: HashMap<&str, HashMap<&str, u32>> = HashMap::new();
     let mut shortest_trips = BinaryHeap::new();
@@ -100,6 +184,23 @@ mod tests {
 
         assert_eq!(y15d09(input, 1), 605);
         assert_eq!(y15d09(input, 2), 982);
+        assert_eq!(y15d09_permutations(input, 1), 605);
+        assert_eq!(y15d09_permutations(input, 2), 982);
+        assert_eq!(
+            y15d09_route(input, 1),
+            Some((vec!["Belfast", "Dublin", "London"], 605))
+        );
+
+        // a missing leg can't be used, even if there's another way around
+        let partial = "A to B = 5\nB to C = 7\nC to D = 1\n";
+        assert_eq!(
+            y15d09_route(partial, 1),
+            Some((vec!["D", "C", "B", "A"], 13))
+        );
+        assert_eq!(
+            y15d09_route("A to B = 5\nA to C = 1\nA to D = 2\n", 2),
+            None
+        );
     }
 
     #[test]
@@ -108,5 +209,7 @@ mod tests {
 
         assert_eq!(y15d09(&contents, 1), 207);
         assert_eq!(y15d09(&contents, 2), 804);
+        assert_eq!(y15d09_permutations(&contents, 1), 207);
+        assert_eq!(y15d09_permutations(&contents, 2), 804);
     }
 }
@@ -17,14 +17,573 @@
 //!
 //! It took me a little while to wrap my head around what look-and-say is but
 //! once I figured it out this was relatively easy to implement.
+//!
+//! Actually building the sequence gets expensive quickly though (it grows by
+//! about 30% every round) so now we use John Conway's observation that after
+//! a few rounds every sequence is made up of 92 "elements" that never
+//! interact with each other again: each element just turns into the same
+//! handful of elements every round. So we only need to keep track of how many
+//! of each element we have (see [`ELEMENTS`]), which lets us compute the
+//! length after hundreds of rounds without ever building the sequence.
+//! Sequences with digits bigger than `3` never split into just those 92, the
+//! big digit stays at the end forever, so for those we also need Conway's
+//! transuranic elements (see [`TRANSURANIC`]).
+
+use crate::util::big::Natural;
+use std::collections::{HashMap, HashSet};
+
+/// One of Conway's audioactive elements.
+#[derive(Debug)]
+pub struct Element {
+    /// The name of the element (Conway named them after the chemical
+    /// elements from hydrogen to uranium).
+    pub name: &'static str,
+    /// The look-and-say sequence of the element.
+    pub sequence: &'static str,
+    /// The elements that the element turns into after one round.
+    pub decay: &'static [&'static str],
+}
+
+/// Conway's 92 common elements.
+pub const ELEMENTS: [Element; 92] = [
+    Element {
+        name: "H",
+        sequence: "22",
+        decay: &["H"],
+    },
+    Element {
+        name: "He",
+        sequence: "13112221133211322112211213322112",
+        decay: &["Hf", "Pa", "H", "Ca", "Li"],
+    },
+    Element {
+        name: "Li",
+        sequence: "312211322212221121123222112",
+        decay: &["He"],
+    },
+    Element {
+        name: "Be",
+        sequence: "111312211312113221133211322112211213322112",
+        decay: &["Ge", "Ca", "Li"],
+    },
+    Element {
+        name: "B",
+        sequence: "1321132122211322212221121123222112",
+        decay: &["Be"],
+    },
+    Element {
+        name: "C",
+        sequence: "3113112211322112211213322112",
+        decay: &["B"],
+    },
+    Element {
+        name: "N",
+        sequence: "111312212221121123222112",
+        decay: &["C"],
+    },
+    Element {
+        name: "O",
+        sequence: "132112211213322112",
+        decay: &["N"],
+    },
+    Element {
+        name: "F",
+        sequence: "31121123222112",
+        decay: &["O"],
+    },
+    Element {
+        name: "Ne",
+        sequence: "111213322112",
+        decay: &["F"],
+    },
+    Element {
+        name: "Na",
+        sequence: "123222112",
+        decay: &["Ne"],
+    },
+    Element {
+        name: "Mg",
+        sequence: "3113322112",
+        decay: &["Pm", "Na"],
+    },
+    Element {
+        name: "Al",
+        sequence: "1113222112",
+        decay: &["Mg"],
+    },
+    Element {
+        name: "Si",
+        sequence: "1322112",
+        decay: &["Al"],
+    },
+    Element {
+        name: "P",
+        sequence: "311311222112",
+        decay: &["Ho", "Si"],
+    },
+    Element {
+        name: "S",
+        sequence: "1113122112",
+        decay: &["P"],
+    },
+    Element {
+        name: "Cl",
+        sequence: "132112",
+        decay: &["S"],
+    },
+    Element {
+        name: "Ar",
+        sequence: "3112",
+        decay: &["Cl"],
+    },
+    Element {
+        name: "K",
+        sequence: "1112",
+        decay: &["Ar"],
+    },
+    Element {
+        name: "Ca",
+        sequence: "12",
+        decay: &["K"],
+    },
+    Element {
+        name: "Sc",
+        sequence: "3113112221133112",
+        decay: &["Ho", "Pa", "H", "Ca", "Co"],
+    },
+    Element {
+        name: "Ti",
+        sequence: "11131221131112",
+        decay: &["Sc"],
+    },
+    Element {
+        name: "V",
+        sequence: "13211312",
+        decay: &["Ti"],
+    },
+    Element {
+        name: "Cr",
+        sequence: "31132",
+        decay: &["V"],
+    },
+    Element {
+        name: "Mn",
+        sequence: "111311222112",
+        decay: &["Cr", "Si"],
+    },
+    Element {
+        name: "Fe",
+        sequence: "13122112",
+        decay: &["Mn"],
+    },
+    Element {
+        name: "Co",
+        sequence: "32112",
+        decay: &["Fe"],
+    },
+    Element {
+        name: "Ni",
+        sequence: "11133112",
+        decay: &["Zn", "Co"],
+    },
+    Element {
+        name: "Cu",
+        sequence: "131112",
+        decay: &["Ni"],
+    },
+    Element {
+        name: "Zn",
+        sequence: "312",
+        decay: &["Cu"],
+    },
+    Element {
+        name: "Ga",
+        sequence: "13221133122211332",
+        decay: &["Eu", "Ca", "Ac", "H", "Ca", "Zn"],
+    },
+    Element {
+        name: "Ge",
+        sequence: "31131122211311122113222",
+        decay: &["Ho", "Ga"],
+    },
+    Element {
+        name: "As",
+        sequence: "11131221131211322113322112",
+        decay: &["Ge", "Na"],
+    },
+    Element {
+        name: "Se",
+        sequence: "13211321222113222112",
+        decay: &["As"],
+    },
+    Element {
+        name: "Br",
+        sequence: "3113112211322112",
+        decay: &["Se"],
+    },
+    Element {
+        name: "Kr",
+        sequence: "11131221222112",
+        decay: &["Br"],
+    },
+    Element {
+        name: "Rb",
+        sequence: "1321122112",
+        decay: &["Kr"],
+    },
+    Element {
+        name: "Sr",
+        sequence: "3112112",
+        decay: &["Rb"],
+    },
+    Element {
+        name: "Y",
+        sequence: "1112133",
+        decay: &["Sr", "U"],
+    },
+    Element {
+        name: "Zr",
+        sequence: "12322211331222113112211",
+        decay: &["Y", "H", "Ca", "Tc"],
+    },
+    Element {
+        name: "Nb",
+        sequence: "1113122113322113111221131221",
+        decay: &["Er", "Zr"],
+    },
+    Element {
+        name: "Mo",
+        sequence: "13211322211312113211",
+        decay: &["Nb"],
+    },
+    Element {
+        name: "Tc",
+        sequence: "311322113212221",
+        decay: &["Mo"],
+    },
+    Element {
+        name: "Ru",
+        sequence: "132211331222113112211",
+        decay: &["Eu", "Ca", "Tc"],
+    },
+    Element {
+        name: "Rh",
+        sequence: "311311222113111221131221",
+        decay: &["Ho", "Ru"],
+    },
+    Element {
+        name: "Pd",
+        sequence: "111312211312113211",
+        decay: &["Rh"],
+    },
+    Element {
+        name: "Ag",
+        sequence: "132113212221",
+        decay: &["Pd"],
+    },
+    Element {
+        name: "Cd",
+        sequence: "3113112211",
+        decay: &["Ag"],
+    },
+    Element {
+        name: "In",
+        sequence: "11131221",
+        decay: &["Cd"],
+    },
+    Element {
+        name: "Sn",
+        sequence: "13211",
+        decay: &["In"],
+    },
+    Element {
+        name: "Sb",
+        sequence: "3112221",
+        decay: &["Pm", "Sn"],
+    },
+    Element {
+        name: "Te",
+        sequence: "1322113312211",
+        decay: &["Eu", "Ca", "Sb"],
+    },
+    Element {
+        name: "I",
+        sequence: "311311222113111221",
+        decay: &["Ho", "Te"],
+    },
+    Element {
+        name: "Xe",
+        sequence: "11131221131211",
+        decay: &["I"],
+    },
+    Element {
+        name: "Cs",
+        sequence: "13211321",
+        decay: &["Xe"],
+    },
+    Element {
+        name: "Ba",
+        sequence: "311311",
+        decay: &["Cs"],
+    },
+    Element {
+        name: "La",
+        sequence: "11131",
+        decay: &["Ba"],
+    },
+    Element {
+        name: "Ce",
+        sequence: "1321133112",
+        decay: &["La", "H", "Ca", "Co"],
+    },
+    Element {
+        name: "Pr",
+        sequence: "31131112",
+        decay: &["Ce"],
+    },
+    Element {
+        name: "Nd",
+        sequence: "111312",
+        decay: &["Pr"],
+    },
+    Element {
+        name: "Pm",
+        sequence: "132",
+        decay: &["Nd"],
+    },
+    Element {
+        name: "Sm",
+        sequence: "311332",
+        decay: &["Pm", "Ca", "Zn"],
+    },
+    Element {
+        name: "Eu",
+        sequence: "1113222",
+        decay: &["Sm"],
+    },
+    Element {
+        name: "Gd",
+        sequence: "13221133112",
+        decay: &["Eu", "Ca", "Co"],
+    },
+    Element {
+        name: "Tb",
+        sequence: "3113112221131112",
+        decay: &["Ho", "Gd"],
+    },
+    Element {
+        name: "Dy",
+        sequence: "111312211312",
+        decay: &["Tb"],
+    },
+    Element {
+        name: "Ho",
+        sequence: "1321132",
+        decay: &["Dy"],
+    },
+    Element {
+        name: "Er",
+        sequence: "311311222",
+        decay: &["Ho", "Pm"],
+    },
+    Element {
+        name: "Tm",
+        sequence: "11131221133112",
+        decay: &["Er", "Ca", "Co"],
+    },
+    Element {
+        name: "Yb",
+        sequence: "1321131112",
+        decay: &["Tm"],
+    },
+    Element {
+        name: "Lu",
+        sequence: "311312",
+        decay: &["Yb"],
+    },
+    Element {
+        name: "Hf",
+        sequence: "11132",
+        decay: &["Lu"],
+    },
+    Element {
+        name: "Ta",
+        sequence: "13112221133211322112211213322113",
+        decay: &["Hf", "Pa", "H", "Ca", "W"],
+    },
+    Element {
+        name: "W",
+        sequence: "312211322212221121123222113",
+        decay: &["Ta"],
+    },
+    Element {
+        name: "Re",
+        sequence: "111312211312113221133211322112211213322113",
+        decay: &["Ge", "Ca", "W"],
+    },
+    Element {
+        name: "Os",
+        sequence: "1321132122211322212221121123222113",
+        decay: &["Re"],
+    },
+    Element {
+        name: "Ir",
+        sequence: "3113112211322112211213322113",
+        decay: &["Os"],
+    },
+    Element {
+        name: "Pt",
+        sequence: "111312212221121123222113",
+        decay: &["Ir"],
+    },
+    Element {
+        name: "Au",
+        sequence: "132112211213322113",
+        decay: &["Pt"],
+    },
+    Element {
+        name: "Hg",
+        sequence: "31121123222113",
+        decay: &["Au"],
+    },
+    Element {
+        name: "Tl",
+        sequence: "111213322113",
+        decay: &["Hg"],
+    },
+    Element {
+        name: "Pb",
+        sequence: "123222113",
+        decay: &["Tl"],
+    },
+    Element {
+        name: "Bi",
+        sequence: "3113322113",
+        decay: &["Pm", "Pb"],
+    },
+    Element {
+        name: "Po",
+        sequence: "1113222113",
+        decay: &["Bi"],
+    },
+    Element {
+        name: "At",
+        sequence: "1322113",
+        decay: &["Po"],
+    },
+    Element {
+        name: "Rn",
+        sequence: "311311222113",
+        decay: &["Ho", "At"],
+    },
+    Element {
+        name: "Fr",
+        sequence: "1113122113",
+        decay: &["Rn"],
+    },
+    Element {
+        name: "Ra",
+        sequence: "132113",
+        decay: &["Fr"],
+    },
+    Element {
+        name: "Ac",
+        sequence: "3113",
+        decay: &["Ra"],
+    },
+    Element {
+        name: "Th",
+        sequence: "1113",
+        decay: &["Ac"],
+    },
+    Element {
+        name: "Pa",
+        sequence: "13",
+        decay: &["Th"],
+    },
+    Element {
+        name: "U",
+        sequence: "3",
+        decay: &["Pa"],
+    },
+];
+
+/// Conway's transuranic elements: neptunium and plutonium ending in each
+/// digit from `4` to `9` (they're helium and lithium with the last `2`
+/// replaced by the big digit).
+pub const TRANSURANIC: [Element; 12] = [
+    Element {
+        name: "Np4",
+        sequence: "13112221133211322112211213322114",
+        decay: &["Hf", "Pa", "H", "Ca", "Pu4"],
+    },
+    Element {
+        name: "Np5",
+        sequence: "13112221133211322112211213322115",
+        decay: &["Hf", "Pa", "H", "Ca", "Pu5"],
+    },
+    Element {
+        name: "Np6",
+        sequence: "13112221133211322112211213322116",
+        decay: &["Hf", "Pa", "H", "Ca", "Pu6"],
+    },
+    Element {
+        name: "Np7",
+        sequence: "13112221133211322112211213322117",
+        decay: &["Hf", "Pa", "H", "Ca", "Pu7"],
+    },
+    Element {
+        name: "Np8",
+        sequence: "13112221133211322112211213322118",
+        decay: &["Hf", "Pa", "H", "Ca", "Pu8"],
+    },
+    Element {
+        name: "Np9",
+        sequence: "13112221133211322112211213322119",
+        decay: &["Hf", "Pa", "H", "Ca", "Pu9"],
+    },
+    Element {
+        name: "Pu4",
+        sequence: "312211322212221121123222114",
+        decay: &["Np4"],
+    },
+    Element {
+        name: "Pu5",
+        sequence: "312211322212221121123222115",
+        decay: &["Np5"],
+    },
+    Element {
+        name: "Pu6",
+        sequence: "312211322212221121123222116",
+        decay: &["Np6"],
+    },
+    Element {
+        name: "Pu7",
+        sequence: "312211322212221121123222117",
+        decay: &["Np7"],
+    },
+    Element {
+        name: "Pu8",
+        sequence: "312211322212221121123222118",
+        decay: &["Np8"],
+    },
+    Element {
+        name: "Pu9",
+        sequence: "312211322212221121123222119",
+        decay: &["Np9"],
+    },
+];
+
+/// According to Conway's cosmological theorem every sequence splits into
+/// common and transuranic elements after at most this many rounds.
+const DECAY_ROUNDS: u32 = 24;
 
 /// The solution for the day ten challenge.
 ///
 /// We take the input as a string and the number of times to perform the
 /// look-and-say as arguments and then return the length of the resulting
-/// string as requested by the prompt. We start by converting the input string
-/// into a vector of integers and then run the look-and-say function on the
-/// result over and over until we've run it the requested number of times.
+/// string as requested by the prompt. See [`y15d10_length`] for how we
+/// compute it.
 ///
 /// # Example
 /// ```rust
@@ -34,11 +593,28 @@
 /// assert_eq!(y15d10(input, 10), 26);
 /// ```
 pub fn y15d10(input: &str, times: u32) -> u32 {
-    let mut nums: Vec<u32> = input
-        .trim()
-        .chars()
-        .map(|n| n.to_digit(10).unwrap())
-        .collect();
+    y15d10_length(input, times)
+        .to_u128()
+        .unwrap()
+        .try_into()
+        .unwrap()
+}
+
+/// The original solution for the day ten challenge.
+///
+/// We start by converting the input string into a vector of integers and then
+/// run the look-and-say function on the result over and over until we've run
+/// it the requested number of times.
+///
+/// # Example
+/// ```rust
+/// # use aoc::y15d10::y15d10_direct;
+/// // probably read this from the input file...
+/// let input = "1";
+/// assert_eq!(y15d10_direct(input, 10), 26);
+/// ```
+pub fn y15d10_direct(input: &str, times: u32) -> u32 {
+    let mut nums = digits(input);
 
     for _ in 0..times {
         nums = lookandsay(nums);
@@ -47,6 +623,203 @@ pub fn y15d10(input: &str, times: u32) -> u32 {
     nums.len().try_into().unwrap()
 }
 
+/// Returns the length of the sequence after the given number of rounds.
+///
+/// Before every round we check if the sequence is a compound of the common
+/// and transuranic elements (see [`y15d10_elements`]). As soon as it is we switch to just
+/// counting how many of each element we have: every round each element adds
+/// its count to each of the elements that it decays into. At the end the
+/// length is the sum of the counts times the length of each element. Until
+/// then (which by Conway's cosmological theorem is at most a couple dozen
+/// rounds) we just perform the look-and-say directly.
+///
+/// The length passes a `u128` after about 330 rounds so the counts are
+/// [`Natural`]s, which means that there's no limit other than time: the
+/// length gets about one more decimal digit every nine rounds and each round
+/// adds up a hundred or so counts of that size (a few thousand rounds still
+/// take well under a second).
+///
+/// # Example
+/// ```rust
+/// # use aoc::y15d10::y15d10_length;
+/// let input = "1113122113"; // probably read this from the input file...
+/// let length = y15d10_length(input, 200);
+/// assert_eq!(length.to_string(), "951327517212589375047678");
+/// assert_eq!(y15d10_length("1", 1).to_u128(), Some(2));
+/// ```
+pub fn y15d10_length(input: &str, times: u32) -> Natural {
+    let decays = decays();
+    let mut nums = digits(input);
+
+    for round in 0..times {
+        if round <= DECAY_ROUNDS {
+            if let Some(compound) = compound(&nums, &decays) {
+                let mut counts = vec![Natural::default(); decays.len()];
+                for element in compound {
+                    counts[element] += &Natural::from(1);
+                }
+
+                for _ in round..times {
+                    let mut next = vec![Natural::default(); decays.len()];
+                    for (element, count) in counts.iter().enumerate() {
+                        for decay in &decays[element] {
+                            next[*decay] += count;
+                        }
+                    }
+                    counts = next;
+                }
+
+                let mut length = Natural::default();
+                for (mut count, element) in
+                    counts.into_iter().zip(ELEMENTS.iter().chain(&TRANSURANIC))
+                {
+                    count *= element.sequence.len() as u64;
+                    length += &count;
+                }
+                return length;
+            }
+        }
+
+        nums = lookandsay(nums);
+    }
+
+    Natural::from(nums.len() as u64)
+}
+
+/// Returns the names of the elements that the sequence is made of, or
+/// `None` if it isn't a compound of the common and transuranic elements.
+///
+/// # Example
+/// ```rust
+/// # use aoc::y15d10::y15d10_elements;
+/// assert_eq!(y15d10_elements("1113122113"), Some(vec!["Fr"]));
+/// assert_eq!(y15d10_elements("3113322113"), Some(vec!["Bi"]));
+/// assert_eq!(y15d10_elements("1322112312"), Some(vec!["Si", "Zn"]));
+/// assert_eq!(y15d10_elements("312211322212221121123222114"), Some(vec!["Pu4"]));
+/// assert_eq!(y15d10_elements("1"), None);
+/// ```
+pub fn y15d10_elements(input: &str) -> Option<Vec<&'static str>> {
+    let compound = compound(&digits(input), &decays())?;
+    Some(compound.into_iter().map(|e| element(e).name).collect())
+}
+
+/// Returns the element with the given index, counting the transuranic
+/// elements after the common ones.
+fn element(index: usize) -> &'static Element {
+    ELEMENTS
+        .get(index)
+        .unwrap_or_else(|| &TRANSURANIC[index - ELEMENTS.len()])
+}
+
+/// Returns the indexes of the elements that each element decays into.
+fn decays() -> Vec<Vec<usize>> {
+    let elements: Vec<_> = ELEMENTS.iter().chain(&TRANSURANIC).collect();
+    let indexes: HashMap<_, _> = elements
+        .iter()
+        .enumerate()
+        .map(|(i, element)| (element.name, i))
+        .collect();
+
+    elements
+        .iter()
+        .map(|element| element.decay.iter().map(|e| indexes[e]).collect())
+        .collect()
+}
+
+/// Converts the input into a vector of digits.
+fn digits(input: &str) -> Vec<u32> {
+    input
+        .trim()
+        .chars()
+        .map(|n| n.to_digit(10).unwrap())
+        .collect()
+}
+
+/// Splits the sequence into elements, if it can be.
+///
+/// It's not enough for the sequence to just be the elements one after the
+/// other, each pair of neighboring elements also can't ever interact (see
+/// [`splits`]). We walk through the sequence keeping track of every element
+/// that can end at each position (and where we came from so that we can
+/// rebuild the compound at the end) and only add an element after another
+/// one if the sequence splits between them.
+fn compound(nums: &[u32], decays: &[Vec<usize>]) -> Option<Vec<usize>> {
+    // the elements that end at each position and the index of the element
+    // before them (in the list of the elements that end where they start)
+    let mut ends: Vec<Vec<(usize, usize)>> = vec![Vec::new(); nums.len() + 1];
+    let mut splits_cache = HashMap::new();
+
+    for i in 0..nums.len() {
+        if i > 0 && ends[i].is_empty() {
+            continue;
+        }
+
+        for (e, element) in ELEMENTS.iter().chain(&TRANSURANIC).enumerate() {
+            let length = element.sequence.len();
+            if nums.len() - i < length
+                || !element
+                    .sequence
+                    .bytes()
+                    .zip(&nums[i..])
+                    .all(|(a, b)| (a - b'0') as u32 == *b)
+                || ends[i + length].iter().any(|(end, _)| *end == e)
+            {
+                continue;
+            }
+
+            let previous = if i == 0 {
+                Some(0)
+            } else {
+                ends[i].iter().position(|(left, _)| {
+                    *splits_cache
+                        .entry((*left, e))
+                        .or_insert_with(|| splits(*left, e, decays))
+                })
+            };
+
+            if let Some(previous) = previous {
+                ends[i + length].push((e, previous));
+            }
+        }
+    }
+
+    let (mut element, mut previous) = *ends[nums.len()].first()?;
+    let mut end = nums.len();
+    let mut compound = vec![element];
+
+    while end > self::element(element).sequence.len() {
+        end -= self::element(element).sequence.len();
+        (element, previous) = ends[end][previous];
+        compound.push(element);
+    }
+
+    compound.reverse();
+    Some(compound)
+}
+
+/// Checks if the sequence splits between two elements, i.e., that nothing
+/// that the left element turns into ever interacts with what the right
+/// element turns into. The only place that they could interact is where they
+/// meet, so we follow the last element of the left side and the first
+/// element of the right side round by round until we either find two of the
+/// same digit next to each other (which would be counted together) or we've
+/// seen the same pair before (so they never will be).
+fn splits(mut left: usize, mut right: usize, decays: &[Vec<usize>]) -> bool {
+    let mut seen = HashSet::new();
+
+    while seen.insert((left, right)) {
+        let last = element(left).sequence.bytes().last();
+        if last == element(right).sequence.bytes().next() {
+            return false;
+        }
+
+        left = *decays[left].last().unwrap();
+        right = decays[right][0];
+    }
+
+    true
+}
+
 /// This function implements the look-and-say algorithm. It's relatively
 /// straightforward: we mark the first digit as the "previous" and then start
 /// counting from the second digit (if there is one). At each digit we check
@@ -91,6 +864,92 @@ mod tests {
         assert_eq!(lookandsay(vec!(1, 1, 1, 2, 2, 1)), vec!(3, 1, 2, 2, 1, 1));
     }
 
+    #[test]
+    fn test_elements() {
+        let elements: Vec<_> = ELEMENTS.iter().chain(&TRANSURANIC).collect();
+        for element in &elements {
+            let decay: String = element
+                .decay
+                .iter()
+                .map(|name| {
+                    elements.iter().find(|e| e.name == *name).unwrap().sequence
+                })
+                .collect();
+            let sequence: Vec<_> = digits(element.sequence);
+            let expected: String = lookandsay(sequence)
+                .into_iter()
+                .map(|d| char::from_digit(d, 10).unwrap())
+                .collect();
+
+            assert_eq!(decay, expected);
+            assert_eq!(
+                y15d10_elements(element.sequence),
+                Some(vec![element.name])
+            );
+            assert_eq!(
+                y15d10_elements(&expected),
+                Some(element.decay.to_vec())
+            );
+        }
+
+        // can't split because the 2s would be counted together
+        assert_eq!(y15d10_elements("2222"), None);
+        // the 4 can't be the end of anything but a transuranic element
+        assert_eq!(y15d10_elements("31124"), None);
+    }
+
+    #[test]
+    fn test_transuranic() {
+        // without the transuranic elements these would build the whole
+        // sequence every round (and run out of memory long before the end)
+        assert_eq!(
+            y15d10_length("4", 55),
+            Natural::from(u64::from(y15d10_direct("4", 55)))
+        );
+        assert_eq!(y15d10_length("4", 100).to_u128(), Some(774405095112));
+        assert!(y15d10_length("1113122119", 300) > y15d10_length("4", 100));
+    }
+
+    #[test]
+    fn test_huge() {
+        // way past a u128 the length still grows by Conway's constant
+        // (1.303577269...) every round
+        let length = y15d10_length("1", 1000);
+        let mut next = y15d10_length("1", 1001);
+        next *= 1_000_000;
+        let mut low = length.clone();
+        low *= 1_303_577;
+        let mut high = length.clone();
+        high *= 1_303_578;
+
+        assert_eq!(length.to_u128(), None);
+        assert_eq!(length.to_string().len(), 116);
+        assert!(low < next && next < high);
+    }
+
+    #[test]
+    fn test_fallback() {
+        for seed in [
+            "1",
+            "3",
+            "11",
+            "123",
+            "4",
+            "1113122114",
+            "22",
+            "55",
+            "19",
+            "7777",
+        ] {
+            for times in 0..40 {
+                assert_eq!(
+                    y15d10_length(seed, times),
+                    Natural::from(u64::from(y15d10_direct(seed, times)))
+                );
+            }
+        }
+    }
+
     #[test]
     fn it_works() {
         let input = "1\n";
@@ -100,6 +959,7 @@ mod tests {
         assert_eq!(y15d10(input, 3), 4); // 1211
         assert_eq!(y15d10(input, 4), 6); // 111221
         assert_eq!(y15d10(input, 5), 6); // 312211
+        assert_eq!(y15d10_direct(input, 5), 6);
     }
 
     #[test]
@@ -108,5 +968,7 @@ mod tests {
 
         assert_eq!(y15d10(&contents, 40), 360154);
         assert_eq!(y15d10(&contents, 50), 5103798);
+        assert_eq!(y15d10_direct(&contents, 50), 5103798);
+        assert_eq!(y15d10_length(&contents, 50).to_u128(), Some(5103798));
     }
 }
@@ -15,22 +15,52 @@
 
 //! Advent of Code 2015 Day 11: <https://adventofcode.com/2015/day/11>
 //!
-//! This was fairly simple to implement and wasn't too challenging but the
-//! solution ended up being rather long and verbose. The solution doesn't use
-//! strings or regular expressions but operations instead on vectors of code
-//! points and so the magic numbers found within correspond to the several
-//! characters that we need to know about: `a`: `97`, `z`: `122`, `i`: `105`,
-//! `l`: `108`, and `o`: `111`.
+//! This was fairly simple to implement and wasn't too challenging. My first
+//! solution hard-coded the rules and the code points of the letters that
+//! they needed, now the rules are part of a [`Policy`] (an alphabet and a
+//! list of [`Rule`]s) which can generate the passwords that come after any
+//! other password. Passwords are treated like numbers written with the
+//! letters of the alphabet as digits, so getting the next password is just
+//! adding one.
+
+/// A requirement that a password has to meet.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Rule {
+    /// A run of (at least) this many letters that come one after the other
+    /// in the alphabet (like `abc`).
+    Straight(usize),
+    /// None of these letters can appear anywhere in the password.
+    Forbidden(Vec<char>),
+    /// At least this many pairs of the same letter (like `aa`) that don't
+    /// overlap, each with a different letter.
+    Pairs(usize),
+}
+
+/// An alphabet and the rules that the passwords have to follow.
+#[derive(Clone, Debug)]
+pub struct Policy {
+    alphabet: Vec<char>,
+    rules: Vec<Rule>,
+    forbidden: Vec<bool>,
+    satisfiable: bool,
+}
+
+/// An iterator over the valid passwords that come after a password according
+/// to a [`Policy`]. It's endless unless no password can meet the rules, in
+/// which case it's empty.
+#[derive(Clone, Debug)]
+pub struct Passwords<'a> {
+    policy: &'a Policy,
+    digits: Vec<usize>,
+}
 
 /// The solution for the day eleven challenge.
 ///
 /// We take the input as a string and a second parameter for how many valid
 /// new passwords we want to generate (it just so happens that in part one it's
-/// `1` and in part two it's `2`). We first convert the password into a vector
-/// of integers corresponding to the character code points. Then for the number
-/// of new passwords that we want we generate the next password and then until
-/// we have a valid password we keep generating the next password. Then we
-/// convert our vector back into a string and we're done.
+/// `1` and in part two it's `2`). Then we just ask Santa's [`Policy`] for
+/// the passwords that come after the input and take the last one that we
+/// want.
 ///
 /// # Example
 /// ```rust
@@ -40,100 +70,233 @@
 /// assert_eq!(y15d11(input, 1), "aaaaaabcc");
 /// ```
 pub fn y15d11(input: &str, howmany: u32) -> String {
-    let mut password: Vec<u8> = input.trim().chars().map(|c| c as u8).collect();
+    Policy::santa()
+        .passwords_after(input.trim())
+        .nth(howmany as usize - 1)
+        .unwrap()
+}
+
+impl Policy {
+    /// Creates a new policy for passwords made with the letters of the
+    /// alphabet (in order).
+    ///
+    /// We also check up front whether any password can meet the rules, since
+    /// otherwise looking for the next one would go on forever. There has to
+    /// be a letter that isn't forbidden, a straight needs that many letters
+    /// in a row in the alphabet that aren't forbidden, and pairs need that
+    /// many different letters that aren't forbidden. That's also enough: a
+    /// straight followed by the pairs meets all of the rules at once.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use aoc::y15d11::{Policy, Rule};
+    /// let policy = Policy::new("01", vec![Rule::Pairs(2)]);
+    /// let next: Vec<_> = policy.passwords_after("0").take(3).collect();
+    /// assert_eq!(next, vec!["0011", "1100", "00011"]);
+    ///
+    /// let policy = Policy::new("0", vec![Rule::Pairs(2)]);
+    /// assert_eq!(policy.passwords_after("0").next(), None);
+    /// ```
+    pub fn new(alphabet: &str, rules: Vec<Rule>) -> Self {
+        let alphabet: Vec<_> = alphabet.chars().collect();
+        let forbidden = alphabet
+            .iter()
+            .map(|letter| {
+                rules.iter().any(|rule| match rule {
+                    Rule::Forbidden(letters) => letters.contains(letter),
+                    _ => false,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let allowed = forbidden.iter().filter(|f| !**f).count();
+        let longest = forbidden
+            .split(|f| *f)
+            .map(|run| run.len())
+            .max()
+            .unwrap_or(0);
+        let satisfiable = allowed > 0
+            && rules.iter().all(|rule| match rule {
+                Rule::Straight(length) => *length <= longest,
+                Rule::Forbidden(_) => true,
+                Rule::Pairs(count) => *count <= allowed,
+            });
 
-    for _ in 0..howmany {
-        password = next_password(password);
-        while !valid_password(password.clone()) {
-            password = next_password(password);
+        Self {
+            alphabet,
+            rules,
+            forbidden,
+            satisfiable,
         }
     }
 
-    String::from_utf8(password).unwrap()
-}
+    /// The policy from the prompt: lowercase letters, a straight of three
+    /// letters, no `i`, `o`, or `l`, and two different pairs.
+    pub fn santa() -> Self {
+        Self::new(
+            "abcdefghijklmnopqrstuvwxyz",
+            vec![
+                Rule::Straight(3),
+                Rule::Forbidden(vec!['i', 'o', 'l']),
+                Rule::Pairs(2),
+            ],
+        )
+    }
 
-/// This function calculates the next password (it doesn't care if it generates
-/// a valid password or not). To make things easier it reverses the order of
-/// the password first and then calls the next character function (which is
-/// responsible for actually incrementing the password correctly) and then
-/// reverses it again to return it.
-fn next_password(current: Vec<u8>) -> Vec<u8> {
-    let mut password = current.into_iter().rev().collect();
-    password = increment_char(password, 0);
-    password.iter().rev().copied().collect()
-}
+    /// Checks if the password meets all of the rules.
+    ///
+    /// # Panics
+    /// If the password has letters that aren't in the alphabet.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use aoc::y15d11::Policy;
+    /// assert!(Policy::santa().is_valid("abcdffaa"));
+    /// assert!(!Policy::santa().is_valid("abcdffga"));
+    /// ```
+    pub fn is_valid(&self, password: &str) -> bool {
+        self.allows(&self.digits(password))
+    }
 
-/// This function is responsible for actually incrementing the password
-/// characters correctly. If we have an index greater than the length of our
-/// current password then we've overflowed and add a new character. Otherwise,
-/// we increment the character of the provided index and if that increases
-/// past "z" (code point 122) then we reset it to "a" (code point 97) and then
-/// run the increment on the next index recursively until we have incremented
-/// everything all the way.
-fn increment_char(mut p: Vec<u8>, index: u8) -> Vec<u8> {
-    if index >= p.len().try_into().unwrap() {
-        p.push(97);
-        p
-    } else {
-        p[index as usize] += 1;
-
-        if p[index as usize] > 122 {
-            p[index as usize] = 97;
-            p = increment_char(p, index + 1);
+    /// Returns the valid passwords that come after the given password, in
+    /// order.
+    ///
+    /// # Panics
+    /// If the password has letters that aren't in the alphabet.
+    pub fn passwords_after(&self, password: &str) -> Passwords<'_> {
+        Passwords {
+            policy: self,
+            digits: self.digits(password),
         }
+    }
 
-        p
+    /// Returns the next `count` valid passwords after the given password.
+    ///
+    /// # Panics
+    /// If the password has letters that aren't in the alphabet.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use aoc::y15d11::Policy;
+    /// let next = Policy::santa().next_passwords("abcdefgh", 2);
+    /// assert_eq!(next, vec!["abcdffaa", "abcdffbb"]);
+    /// ```
+    pub fn next_passwords(&self, password: &str, count: usize) -> Vec<String> {
+        self.passwords_after(password).take(count).collect()
     }
-}
 
-/// This function determines if the provided password meets all of the
-/// requirements laid out in the prompt: no "i", "l", or "o" characters (code
-/// points 105, 108, and 111 respectively), at least one run of three
-/// characters, and two distinct sets of double characters.
-fn valid_password(password: Vec<u8>) -> bool {
-    if password.contains(&105)
-        || password.contains(&108)
-        || password.contains(&111)
-    {
-        return false;
-    }
-
-    let mut found_run = false;
-    for w in password.windows(3) {
-        if w[1] - 1 == w[0] && w[2] - 2 == w[0] {
-            found_run = true;
-            break;
-        }
+    /// Converts a password into the positions of its letters in the alphabet.
+    fn digits(&self, password: &str) -> Vec<usize> {
+        password
+            .chars()
+            .map(|c| {
+                self.alphabet
+                    .iter()
+                    .position(|letter| *letter == c)
+                    .expect("letter isn't in the alphabet")
+            })
+            .collect()
     }
 
-    if !found_run {
-        return false;
+    /// Checks if a password (as positions in the alphabet) meets the rules.
+    fn allows(&self, digits: &[usize]) -> bool {
+        self.rules.iter().all(|rule| match rule {
+            Rule::Straight(length) => {
+                *length == 0
+                    || digits
+                        .windows(*length)
+                        .any(|w| w.windows(2).all(|p| p[1] == p[0] + 1))
+            }
+            Rule::Forbidden(_) => !digits.iter().any(|d| self.forbidden[*d]),
+            Rule::Pairs(count) => {
+                let mut pairs = Vec::new();
+                let mut i = 1;
+                while i < digits.len() {
+                    if digits[i] == digits[i - 1] {
+                        if !pairs.contains(&digits[i]) {
+                            pairs.push(digits[i]);
+                        }
+                        i += 2;
+                    } else {
+                        i += 1;
+                    }
+                }
+
+                pairs.len() >= *count
+            }
+        })
     }
 
-    // if we find a double character record it for later
-    let mut found_double: Option<u8> = None;
-    for w in password.windows(2) {
-        if w[0] == w[1] {
-            found_double = Some(w[0]);
-            break;
-        }
+    /// Returns the next letter after the given one that isn't forbidden, if
+    /// there is one.
+    fn next_allowed(&self, from: usize) -> Option<usize> {
+        (from..self.alphabet.len()).find(|d| !self.forbidden[*d])
     }
+}
 
-    // if we didn't find a double then we're done, otherwise repeat the above
-    // but also check to make sure that we find a double distinct from the one
-    // that we already found
-    match found_double {
-        None => return false,
-        Some(double) => {
-            for w in password.windows(2) {
-                if w[0] == w[1] && w[0] != double {
-                    return true;
+impl Passwords<'_> {
+    /// Moves on to the next password that doesn't have any forbidden
+    /// letters.
+    ///
+    /// We add one to the password, carrying into the next letter to the left
+    /// whenever a letter goes past the end of the alphabet (and adding a new
+    /// letter to the front if we carry past the first one), but we skip any
+    /// forbidden letters along the way. If the password had a forbidden
+    /// letter to begin with then every password until that letter changes
+    /// has it too, so we skip that whole block at once by moving the leftmost
+    /// forbidden letter to the next allowed letter and resetting everything
+    /// after it to the first allowed letter.
+    fn advance(&mut self) {
+        let policy = self.policy;
+        let first = policy.next_allowed(0).expect("every letter is forbidden");
+
+        let mut position =
+            match self.digits.iter().position(|d| policy.forbidden[*d]) {
+                Some(position) => {
+                    for digit in &mut self.digits[position + 1..] {
+                        *digit = first;
+                    }
+                    position + 1
+                }
+                None => self.digits.len(),
+            };
+
+        while position > 0 {
+            position -= 1;
+
+            match policy.next_allowed(self.digits[position] + 1) {
+                Some(digit) => {
+                    self.digits[position] = digit;
+                    return;
                 }
+                None => self.digits[position] = first,
             }
         }
+
+        self.digits.insert(0, first);
     }
+}
+
+impl Iterator for Passwords<'_> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        if !self.policy.satisfiable {
+            return None;
+        }
+
+        self.advance();
+        while !self.policy.allows(&self.digits) {
+            self.advance();
+        }
 
-    false
+        Some(
+            self.digits
+                .iter()
+                .map(|d| self.policy.alphabet[*d])
+                .collect(),
+        )
+    }
 }
 
 #[cfg(test)]
@@ -142,34 +305,97 @@ mod tests {
     use std::fs;
 
     #[test]
-    fn test_next_password() {
-        let mut expected: Vec<u8> = "xy".into();
-        assert_eq!(next_password("xx".into()), expected);
-
-        expected = "xz".into();
-        assert_eq!(next_password("xy".into()), expected);
+    fn test_advance() {
+        let policy = Policy::new("abcdefghijklmnopqrstuvwxyz", vec![]);
+        let advance = |password: &str| {
+            let mut passwords = policy.passwords_after(password);
+            passwords.advance();
+            passwords.next().unwrap()
+        };
 
-        expected = "ya".into();
-        assert_eq!(next_password("xz".into()), expected);
+        assert_eq!(policy.passwords_after("xx").next().unwrap(), "xy");
+        assert_eq!(policy.passwords_after("xy").next().unwrap(), "xz");
+        assert_eq!(policy.passwords_after("xz").next().unwrap(), "ya");
+        assert_eq!(advance("xz"), "yb");
+        assert_eq!(policy.passwords_after("a").next().unwrap(), "b");
+        assert_eq!(policy.passwords_after("z").next().unwrap(), "aa");
+        assert_eq!(policy.passwords_after("az").next().unwrap(), "ba");
 
-        expected = "yb".into();
-        assert_eq!(next_password("ya".into()), expected);
+        // skips the forbidden letters, including whole blocks of them
+        let policy = Policy::santa();
+        let mut passwords = policy.passwords_after("ah");
+        passwords.advance();
+        assert_eq!(passwords.digits, policy.digits("aj"));
+        let mut passwords = policy.passwords_after("ghijklmn");
+        passwords.advance();
+        assert_eq!(passwords.digits, policy.digits("ghjaaaaa"));
+        let mut passwords = policy.passwords_after("hz");
+        passwords.advance();
+        assert_eq!(passwords.digits, policy.digits("ja"));
     }
 
     #[test]
     fn test_valid_password() {
-        assert!(!valid_password("hijklmmn".into()));
-        assert!(!valid_password("abbceffg".into()));
-        assert!(!valid_password("abbcegjk".into()));
-        assert!(valid_password("abcdffaa".into()));
-        assert!(valid_password("ghjaabcc".into()));
+        let policy = Policy::santa();
+        assert!(!policy.is_valid("hijklmmn"));
+        assert!(!policy.is_valid("abbceffg"));
+        assert!(!policy.is_valid("abbcegjk"));
+        assert!(policy.is_valid("abcdffaa"));
+        assert!(policy.is_valid("ghjaabcc"));
+        assert!(!policy.is_valid("abcaaa"));
+        assert!(!policy.is_valid("abcaaxaa"));
+
+        let policy = Policy::new("abc", vec![Rule::Straight(1)]);
+        assert!(policy.is_valid("a"));
+        assert!(!policy.is_valid(""));
+    }
+
+    #[test]
+    fn test_next_passwords() {
+        let policy = Policy::santa();
+        let mut password = String::from("abcdefgh");
+        for expected in policy.next_passwords("abcdefgh", 20) {
+            // check against stepping one password at a time
+            let none = Policy::new("abcdefghijklmnopqrstuvwxyz", vec![]);
+            let step = none
+                .passwords_after(&password)
+                .find(|p| policy.is_valid(p))
+                .unwrap();
+            assert_eq!(step, expected);
+            password = expected;
+        }
     }
 
     #[test]
-    fn test_increment_char() {
-        assert_eq!(increment_char(vec!(97), 0), vec!(98)); // a -> b
-        assert_eq!(increment_char(vec!(122), 0), vec!(97, 97)); // z -> aa
-        assert_eq!(increment_char(vec!(122, 97), 0), vec!(97, 98)); // za -> ab
+    fn test_unsatisfiable() {
+        for (alphabet, rule) in [
+            ("a", Rule::Pairs(2)),
+            ("abc", Rule::Straight(4)),
+            ("abc", Rule::Forbidden(vec!['a', 'b', 'c'])),
+            ("", Rule::Straight(0)),
+        ] {
+            let policy = Policy::new(alphabet, vec![rule]);
+            assert_eq!(policy.next_passwords("", 3), Vec::<String>::new());
+        }
+
+        // the forbidden letters break up the straights and take away pairs
+        let forbidden = Rule::Forbidden(vec!['c']);
+        let policy =
+            Policy::new("abcde", vec![forbidden.clone(), Rule::Straight(3)]);
+        assert_eq!(policy.passwords_after("a").next(), None);
+        let policy =
+            Policy::new("abcde", vec![forbidden.clone(), Rule::Straight(2)]);
+        assert_eq!(policy.passwords_after("a").next().unwrap(), "ab");
+        let policy = Policy::new("ac", vec![forbidden.clone(), Rule::Pairs(2)]);
+        assert_eq!(policy.passwords_after("a").next(), None);
+
+        // only just satisfiable
+        let policy = Policy::new(
+            "abcd",
+            vec![forbidden, Rule::Straight(2), Rule::Pairs(3)],
+        );
+        let next = policy.passwords_after("").next().unwrap();
+        assert!(policy.is_valid(&next));
     }
 
     #[test]
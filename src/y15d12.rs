@@ -19,15 +19,47 @@
 //! easily recurse based on the possible JSON types. We make use of
 //! [serde](https://github.com/serde-rs/json) to handle the actual parsing of
 //! the input string.
+//!
+//! Instead of only knowing how to skip objects that contain `red` the sum is
+//! now computed by a [`Query`] which skips any objects or arrays that match
+//! one of its [`Predicate`]s and can also tell us where each of the numbers
+//! that it added came from.
+
+use serde_json::{Number, Value};
+
+/// Something that an object or an array can match to be excluded (along with
+/// everything inside of it) from a [`Query`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum Predicate {
+    /// Objects that have this value for any of their keys.
+    ObjectContains(Value),
+    /// Arrays that have this value as any of their elements.
+    ArrayContains(Value),
+    /// Objects that have this key.
+    HasKey(String),
+    /// Objects and arrays that are nested deeper than this (the document
+    /// itself is at depth `0`).
+    DeeperThan(usize),
+}
 
-use serde_json::Value;
+/// Sums (or lists) the numbers in a JSON document, skipping the objects and
+/// arrays that match any of the predicates.
+#[derive(Clone, Debug, Default)]
+pub struct Query {
+    exclude: Vec<Predicate>,
+}
+
+/// A step on the path from the document to a value.
+enum Segment<'v> {
+    Key(&'v str),
+    Index(usize),
+}
 
 /// The solution for the day twelve challenge.
 ///
 /// Given the input string and a boolean whether or not we should ignore
-/// objects that have a value `red` we pass everything off to the calculate
-/// value function which can call itself recursively to calculate the total
-/// sum.
+/// objects that have a value `red` we build the [`Query`] and let it
+/// calculate the total sum.
 ///
 /// # Example
 /// ```rust
@@ -39,60 +71,162 @@ use serde_json::Value;
 /// ```
 pub fn y15d12(input: &str, ignore_red: bool) -> i64 {
     let json: Value = serde_json::from_str(input).unwrap();
-    calculate_value(json, ignore_red)
+    let mut query = Query::new();
+
+    if ignore_red {
+        query = query.exclude(Predicate::ObjectContains(Value::from("red")));
+    }
+
+    query.sum(&json)
 }
 
-/// This function is what is responsible for actually calculating the value of
-/// all of the numbers of a JSON. We first check to see if we're ignoring red
-/// and if the object has red in it. If it does then we're done, otherwise we
-/// continue processing. We're only interested in three JSON types: arrays in
-/// which re recurse to each value of the array adding it to the sum, objects
-/// in which we recurse to each value of the object adding it to the sum, and
-/// numbers which we add directly to the sum. Everything else can be ignored.
-fn calculate_value(value: Value, ignore_red: bool) -> i64 {
-    let mut sum = 0;
-
-    if ignore_red && has_red(value.clone()) {
-        return 0;
+impl Query {
+    /// Creates a new query that includes every number.
+    pub fn new() -> Self {
+        Self::default()
     }
 
-    match value {
-        Value::Array(a) => {
-            for i in a {
-                sum += calculate_value(i, ignore_red);
-            }
+    /// Also excludes the objects and arrays that match the predicate.
+    pub fn exclude(mut self, predicate: Predicate) -> Self {
+        self.exclude.push(predicate);
+        self
+    }
+
+    /// Returns the sum of all of the numbers that aren't excluded.
+    ///
+    /// # Panics
+    /// If any of the numbers aren't integers (or don't fit in an `i64`).
+    ///
+    /// # Example
+    /// ```rust
+    /// # use aoc::y15d12::{Predicate, Query};
+    /// let json = serde_json::json!({"a": [1, {"b": 2}], "c": {"d": 4}});
+    /// assert_eq!(Query::new().sum(&json), 7);
+    /// assert_eq!(Query::new().exclude(Predicate::DeeperThan(1)).sum(&json), 5);
+    /// assert_eq!(
+    ///     Query::new().exclude(Predicate::HasKey("d".to_string())).sum(&json),
+    ///     3
+    /// );
+    /// ```
+    pub fn sum(&self, value: &Value) -> i64 {
+        let mut sum = 0;
+        self.walk(value, &mut Vec::new(), &mut |_, n| {
+            sum += n.as_i64().unwrap();
+        });
+        sum
+    }
+
+    /// Returns the path (like `$.a[1].b`) of each of the numbers that aren't
+    /// excluded along with the number itself (arrays are in order but the
+    /// keys of objects are sorted).
+    ///
+    /// # Example
+    /// ```rust
+    /// # use aoc::y15d12::{Predicate, Query};
+    /// let json = serde_json::json!([1, {"a b": 2, "c": [3, "red"]}]);
+    /// let query = Query::new().exclude(Predicate::ArrayContains("red".into()));
+    /// let paths: Vec<_> = query
+    ///     .numbers(&json)
+    ///     .into_iter()
+    ///     .map(|(path, n)| (path, n.as_i64().unwrap()))
+    ///     .collect();
+    /// assert_eq!(
+    ///     paths,
+    ///     vec![("$[0]".to_string(), 1), ("$[1][\"a b\"]".to_string(), 2)]
+    /// );
+    /// ```
+    pub fn numbers<'v>(&self, value: &'v Value) -> Vec<(String, &'v Number)> {
+        let mut numbers = Vec::new();
+        self.walk(value, &mut Vec::new(), &mut |path, n| {
+            numbers.push((format_path(path), n));
+        });
+        numbers
+    }
+
+    /// Checks if the value (an object or an array at the given depth) is
+    /// excluded by any of the predicates.
+    fn excludes(&self, value: &Value, depth: usize) -> bool {
+        self.exclude
+            .iter()
+            .any(|predicate| match (predicate, value) {
+                (Predicate::ObjectContains(v), Value::Object(o)) => {
+                    o.values().any(|i| i == v)
+                }
+                (Predicate::ArrayContains(v), Value::Array(a)) => a.contains(v),
+                (Predicate::HasKey(key), Value::Object(o)) => {
+                    o.contains_key(key)
+                }
+                (
+                    Predicate::DeeperThan(max),
+                    Value::Object(_) | Value::Array(_),
+                ) => depth > *max,
+                _ => false,
+            })
+    }
+
+    /// This function is what is responsible for actually finding all of the
+    /// numbers in the JSON. We first check to see if the value is excluded.
+    /// If it is then we're done, otherwise we continue processing. We're only
+    /// interested in three JSON types: arrays and objects in which we recurse
+    /// to each of their values (keeping track of the path that got us there)
+    /// and numbers which we pass to the visitor. Everything else can be
+    /// ignored. We only ever borrow the values so nothing gets copied.
+    fn walk<'v>(
+        &self,
+        value: &'v Value,
+        path: &mut Vec<Segment<'v>>,
+        visit: &mut impl FnMut(&[Segment<'v>], &'v Number),
+    ) {
+        if self.excludes(value, path.len()) {
+            return;
         }
-        Value::Object(o) => {
-            for (_, i) in o.iter() {
-                sum += calculate_value(i.clone(), ignore_red);
+
+        match value {
+            Value::Array(a) => {
+                for (i, item) in a.iter().enumerate() {
+                    path.push(Segment::Index(i));
+                    self.walk(item, path, visit);
+                    path.pop();
+                }
             }
+            Value::Object(o) => {
+                for (key, item) in o {
+                    path.push(Segment::Key(key));
+                    self.walk(item, path, visit);
+                    path.pop();
+                }
+            }
+            Value::Number(n) => visit(path, n),
+            _ => {}
         }
-        Value::Number(n) => {
-            sum += n.as_i64().unwrap();
-        }
-        _ => {}
     }
-
-    sum
 }
 
-/// This function determines if the provided JSON value is an object that has
-/// `red` for any of its values.
-fn has_red(value: Value) -> bool {
-    match value {
-        Value::Object(o) => {
-            for (_, i) in o.iter() {
-                if let Value::String(s) = i {
-                    if s == "red" {
-                        return true;
-                    }
+/// Formats a path like a JSONPath: keys that are identifiers use the dot
+/// notation and everything else uses brackets.
+fn format_path(path: &[Segment]) -> String {
+    let mut formatted = String::from("$");
+
+    for segment in path {
+        match segment {
+            Segment::Index(i) => formatted += &format!("[{}]", i),
+            Segment::Key(key) => {
+                let identifier = key.chars().enumerate().all(|(i, c)| {
+                    c == '_'
+                        || c.is_ascii_alphabetic()
+                        || i > 0 && c.is_ascii_digit()
+                });
+
+                if identifier && !key.is_empty() {
+                    formatted += &format!(".{}", key);
+                } else {
+                    formatted += &format!("[{}]", Value::from(*key));
                 }
             }
-
-            false
         }
-        _ => false,
     }
+
+    formatted
 }
 
 #[cfg(test)]
@@ -101,29 +235,67 @@ mod tests {
     use std::fs;
 
     #[test]
-    fn test_calculate_value() {
+    fn test_sum() {
         let mut input = "{\"a\":[]}";
         let mut json: Value = serde_json::from_str(input).unwrap();
-        assert_eq!(calculate_value(json, false), 0);
+        assert_eq!(Query::new().sum(&json), 0);
 
         input = "[0,1,2]";
         json = serde_json::from_str(input).unwrap();
-        assert_eq!(calculate_value(json, false), 3);
+        assert_eq!(Query::new().sum(&json), 3);
+
+        input = "[1,[2,[3,[4]]],{\"a\":{\"b\":5}}]";
+        json = serde_json::from_str(input).unwrap();
+        for (depth, sum) in [(0, 1), (1, 3), (2, 11), (3, 15)] {
+            let query = Query::new().exclude(Predicate::DeeperThan(depth));
+            assert_eq!(query.sum(&json), sum);
+        }
+
+        let query = Query::new()
+            .exclude(Predicate::ArrayContains(Value::from(4)))
+            .exclude(Predicate::HasKey("b".to_string()));
+        assert_eq!(query.sum(&json), 6);
     }
 
     #[test]
-    fn test_has_red() {
+    fn test_excludes() {
+        let red = Query::new().exclude(Predicate::ObjectContains("red".into()));
+
         let mut input = "{\"a\":\"red\"}";
         let mut json: Value = serde_json::from_str(input).unwrap();
-        assert!(has_red(json));
+        assert!(red.excludes(&json, 0));
 
         input = "{\"a\":\"green\"}";
         json = serde_json::from_str(input).unwrap();
-        assert!(!has_red(json));
+        assert!(!red.excludes(&json, 0));
 
         input = "{\"red\":\"\"}";
         json = serde_json::from_str(input).unwrap();
-        assert!(!has_red(json));
+        assert!(!red.excludes(&json, 0));
+        let key = Query::new().exclude(Predicate::HasKey("red".to_string()));
+        assert!(key.excludes(&json, 0));
+    }
+
+    #[test]
+    fn test_numbers() {
+        let input = "{\"a\":[1,{\"b\":2,\"\":3,\"1\":4}],\"_c9\":5}";
+        let json: Value = serde_json::from_str(input).unwrap();
+        let paths: Vec<_> = Query::new()
+            .numbers(&json)
+            .into_iter()
+            .map(|(path, _)| path)
+            .collect();
+
+        assert_eq!(
+            paths,
+            vec![
+                "$._c9",
+                "$.a[0]",
+                "$.a[1][\"\"]",
+                "$.a[1][\"1\"]",
+                "$.a[1].b"
+            ]
+        );
     }
 
     #[test]
@@ -179,5 +351,11 @@ mod tests {
 
         assert_eq!(y15d12(&contents, false), 119433);
         assert_eq!(y15d12(&contents, true), 68466);
+
+        let json: Value = serde_json::from_str(&contents).unwrap();
+        let red = Query::new().exclude(Predicate::ObjectContains("red".into()));
+        let numbers = red.numbers(&json);
+        let sum: i64 = numbers.iter().map(|(_, n)| n.as_i64().unwrap()).sum();
+        assert_eq!(sum, 68466);
     }
 }
@@ -16,14 +16,110 @@
 //! Advent of Code 2015 Day 13: <https://adventofcode.com/2015/day/13>
 //!
 //! This problem is extremely similar to the problem from day nine and so an
-//! extremely similar approach was taken.
+//! extremely similar approach was taken. Like day nine it now uses the shared
+//! [`crate::util::tour`] solver (the seating is a closed cycle because the
+//! table is round).
 
+use crate::util::tour::{best_tour, Goal, Shape};
 use itertools::Itertools;
 use std::collections::{BinaryHeap, HashMap};
 
 /// The solution for the day thirteen challenge.
 ///
 /// We take the input as a string and a boolean specifying if we're also
+/// attending dinner or not and return the total change in happiness of the
+/// best seating arrangement (see [`y15d13_seating`]).
+///
+/// # Example
+/// ```rust
+/// # use aoc::y15d13::y15d13;
+/// // probably read this from the input file...
+/// let input = concat!(
+///     "Bob would gain 10 happiness units by sitting next to Alice.\n",
+///     "Alice would lose 20 happiness units by sitting next to Bob.\n",
+/// );
+/// assert_eq!(y15d13(input, false), -20);
+/// assert_eq!(y15d13(input, true), -10);
+/// ```
+pub fn y15d13(input: &str, me: bool) -> i32 {
+    y15d13_seating(input, me).1
+}
+
+/// Returns the guests in the order that they should sit around the table to
+/// get the biggest change in happiness along with that change.
+///
+/// We parse the input into a list of the guests (adding `Me` if we're also
+/// attending) and a matrix of how much happiness changes when two guests sit
+/// next to each other (both of their changes added together, which means
+/// that `Me` is just a guest that doesn't change anything). Then we find the
+/// best closed cycle through all of the guests.
+///
+/// # Example
+/// ```rust
+/// # use aoc::y15d13::y15d13_seating;
+/// // probably read this from the input file...
+/// let input = concat!(
+///     "A would gain 1 happiness units by sitting next to B.\n",
+///     "A would gain 5 happiness units by sitting next to C.\n",
+///     "B would gain 1 happiness units by sitting next to A.\n",
+///     "B would gain 3 happiness units by sitting next to C.\n",
+///     "C would gain 5 happiness units by sitting next to A.\n",
+///     "C would gain 3 happiness units by sitting next to B.\n",
+/// );
+/// assert_eq!(y15d13_seating(input, false), (vec!["A", "C", "B"], 18));
+/// assert_eq!(y15d13_seating(input, true), (vec!["A", "Me", "B", "C"], 16));
+/// ```
+pub fn y15d13_seating(input: &str, me: bool) -> (Vec<&str>, i32) {
+    let mut guests = Vec::new();
+    let mut changes = Vec::new();
+
+    for line in input.lines() {
+        let text: Vec<&str> = line.split_whitespace().collect();
+        let amount: i64 = if text[2] == "gain" {
+            text[3].parse().unwrap()
+        } else {
+            -(text[3].parse::<i64>().unwrap())
+        };
+        let mut index = |guest| match guests.iter().position(|g| *g == guest) {
+            Some(index) => index,
+            None => {
+                guests.push(guest);
+                guests.len() - 1
+            }
+        };
+
+        changes.push((
+            index(text[0]),
+            index(text[10].strip_suffix('.').unwrap()),
+            amount,
+        ));
+    }
+
+    if me {
+        guests.push("Me");
+    }
+
+    let mut happiness = vec![vec![0; guests.len()]; guests.len()];
+    for (a, b, amount) in changes {
+        happiness[a][b] += amount;
+        happiness[b][a] += amount;
+    }
+
+    // anyone can sit next to anyone else (even if they don't care about it)
+    let happiness: Vec<Vec<_>> = happiness
+        .into_iter()
+        .map(|row| row.into_iter().map(Some).collect())
+        .collect();
+    let tour = best_tour(&happiness, Shape::Cycle, Goal::Maximize).unwrap();
+    (
+        tour.order.into_iter().map(|guest| guests[guest]).collect(),
+        tour.cost.try_into().unwrap(),
+    )
+}
+
+/// The original solution for the day thirteen challenge.
+///
+/// We take the input as a string and a boolean specifying if we're also
 /// attending dinner or not. Then much like on day nine we start by parsing the
 /// input to compute the happiness change of each guest sitting next to every
 /// other guest. If we're attending dinner then we also add a `Me` entry with
@@ -37,7 +133,7 @@ use std::collections::{BinaryHeap, HashMap};
 ///
 /// # Example
 /// ```rust
-/// # use aoc::y15d13::y15d13;
+/// # use aoc::y15d13::y15d13_permutations;
 /// // probably read this from the input file...
 /// let input = concat!(
 ///     "Bob would gain 10 happiness units by sitting next to Alice.\n",
@@ -53,10 +149,10 @@ use std::collections::{BinaryHeap, HashMap};
 ///     "Andy would lose 20 happiness units by sitting next to Jim.\n",
 ///     "Andy would lose 30 happiness units by sitting next to Bob.",
 /// );
-/// assert_eq!(y15d13(input, false), 10);
-/// assert_eq!(y15d13(input, true), 50);
+/// assert_eq!(y15d13_permutations(input, false), 10);
+/// assert_eq!(y15d13_permutations(input, true), 50);
 /// ```
-pub fn y15d13(input: &str, me: bool) -> i32 {
+pub fn y15d13_permutations(input: &str, me: bool) -> i32 {
     let lines: Vec<_> = input.lines().collect();
     let mut happinesses: HashMap<&str, HashMap<&str, i32>> = HashMap::new();
     let mut totals = BinaryHeap::new();
@@ -128,6 +224,12 @@ mod tests {
 
         assert_eq!(y15d13(input, false), 330);
         assert_eq!(y15d13(input, true), 286);
+        assert_eq!(y15d13_permutations(input, false), 330);
+        assert_eq!(y15d13_permutations(input, true), 286);
+        assert_eq!(
+            y15d13_seating(input, false),
+            (vec!["Alice", "David", "Carol", "Bob"], 330)
+        );
     }
 
     #[test]
@@ -136,5 +238,7 @@ mod tests {
 
         assert_eq!(y15d13(&contents, false), 709);
         assert_eq!(y15d13(&contents, true), 668);
+        assert_eq!(y15d13_permutations(&contents, false), 709);
+        assert_eq!(y15d13_permutations(&contents, true), 668);
     }
 }
@@ -21,93 +21,61 @@
 //! faster. After seeing part two I decided to maintain my approach but refine
 //! it to keep track of the state of all of the reindeer at each second so
 //! that I could correctly award the points to the current winner.
+//!
+//! Both of those turned out to be unnecessary though: a reindeer's flying
+//! pattern repeats, so its distance at any time can be computed directly
+//! from how many full fly/rest cycles fit and how much of the last one it
+//! spent flying. Points only need the moments where something can change
+//! (see [`Race::leaderboard`]), which means that we can find out who's
+//! winning after billions of seconds just as easily.
 
-use std::collections::BinaryHeap;
-
-/// Reindeer tracks the state of each reindeer during the race.
-#[derive(Debug, PartialEq)]
-struct Reindeer {
-    name: String,
-    speed: u32,
-    race_seconds: u32,
-    rest_seconds: u32,
-    distance: u32,
-    points: u32,
-
-    is_flying: bool,
-    left_in_phase: u32,
+/// A reindeer and its flying pattern.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Reindeer<'a> {
+    /// The name of the reindeer.
+    pub name: &'a str,
+    /// How far the reindeer flies every second (when it's flying).
+    pub speed: u64,
+    /// How many seconds the reindeer can fly before it needs to rest.
+    pub fly: u64,
+    /// How many seconds the reindeer needs to rest before flying again.
+    pub rest: u64,
 }
 
-impl Reindeer {
-    /// Initializes a new reindeer object, given their name, race speed, how
-    /// long they can race before needing to rest, and how long they need to
-    /// rest before they can race again. Reindeer then start in the racing
-    /// (flying phase) at distance `0` with the number of seconds that they can
-    /// race set as the remaining time to spend in the current phase.
-    fn new(
-        name: &str,
-        speed: u32,
-        race_seconds: u32,
-        rest_seconds: u32,
-    ) -> Reindeer {
-        Reindeer {
-            name: name.to_string(),
-            speed,
-            race_seconds,
-            rest_seconds,
-            distance: 0,
-            points: 0,
-            is_flying: true,
-            left_in_phase: race_seconds,
-        }
-    }
-
-    /// A simple function to increment the current points that a reindeer has.
-    fn award_point(&mut self) {
-        self.points += 1;
-    }
-
-    /// This function computes the state of the reindeer for the next second.
-    /// If the reindeer is currently in the racing (flying) phase then we
-    /// increment its current distance by its speed. Then we decrement the
-    /// phase counter. If we've reached the end of the phase (i.e., the counter
-    /// is `0`) then we switch the phase and set the counter to the new phase
-    /// time.
-    fn next(&mut self) {
-        if self.is_flying {
-            self.distance += self.speed;
-        }
-
-        self.left_in_phase -= 1;
+/// All of the reindeer in the race.
+#[derive(Clone, Debug)]
+pub struct Race<'a> {
+    reindeer: Vec<Reindeer<'a>>,
+}
 
-        if self.left_in_phase == 0 {
-            if self.is_flying {
-                self.left_in_phase = self.rest_seconds;
-            } else {
-                self.left_in_phase = self.race_seconds;
-            }
+/// How a reindeer is doing at some point in the race.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Standing<'a> {
+    /// The name of the reindeer.
+    pub name: &'a str,
+    /// How far the reindeer has flown.
+    pub distance: u64,
+    /// How many points the reindeer has.
+    pub points: u64,
+}
 
-            self.is_flying = !self.is_flying;
-        }
-    }
+/// A second at which the reindeer in the lead (the ones that get the points)
+/// changed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LeadChange<'a> {
+    /// The second where the reindeer took the lead.
+    pub second: u64,
+    /// The reindeer that are in the lead (tied if there's more than one).
+    pub leaders: Vec<&'a str>,
 }
 
 /// The solution for the day fourteen challenge.
 ///
 /// As usual we accept the input as a string. Then we take how many seconds of
 /// race to process and whether we're doing part `1` (return the maximum
-/// distance) or part `2` (return the maximum number of points). We start by
-/// parsing the input and creating our `Reindeer` objects to track the state
-/// as the race progresses. Then for the desired number or race seconds we
-/// process each second one-at-a-time. For each second we create a new max-heap
-/// (using the venerable [`std::collections::BinaryHeap`] to keep track of the
-/// current maximum travelled distance. We process each reindeer and add their
-/// distance to the heap. We then pop the heap to see what the maximum distance
-/// is across all reindeer and then any reindeer who is currently at that
-/// winning distance is awarded a point. To return the desired final answer we
-/// create a new max-heap and then add each reindeer's distance for part one or
-/// number of obtained points for part two. Popping the heap gives us the
-/// answer.
+/// distance) or part `2` (return the maximum number of points). We parse the
+/// [`Race`] and then get the leaderboard at the end of the race which has
+/// both the distances and the points.
 ///
 /// # Example
 /// ```rust
@@ -121,48 +89,270 @@ impl Reindeer {
 /// assert_eq!(y15d14(input, 100, 2), 100);
 /// ```
 pub fn y15d14(input: &str, seconds: u32, part: u32) -> u32 {
-    let lines: Vec<_> = input.lines().collect();
-    let mut reindeer = Vec::new();
+    let standings = Race::parse(input).leaderboard(seconds.into());
 
-    for line in lines {
-        let text: Vec<&str> = line.split_whitespace().collect();
-        let name = text[0];
-        let speed = text[3].parse().unwrap();
-        let race_seconds = text[6].parse().unwrap();
-        let rest_seconds = text[13].parse().unwrap();
+    let best = if part == 1 {
+        standings.iter().map(|s| s.distance).max()
+    } else {
+        standings.iter().map(|s| s.points).max()
+    };
+
+    best.unwrap().try_into().unwrap()
+}
+
+impl Reindeer<'_> {
+    /// Returns how far the reindeer has flown after the given number of
+    /// seconds: the distance of all of the full cycles of flying and resting
+    /// plus however much of the last cycle it spent flying.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use aoc::y15d14::Reindeer;
+    /// let comet = Reindeer { name: "Comet", speed: 14, fly: 10, rest: 127 };
+    /// assert_eq!(comet.distance(1000), 1120);
+    /// assert_eq!(comet.distance(1_000_000_000), 1021897940);
+    /// ```
+    pub fn distance(&self, seconds: u64) -> u64 {
+        if self.is_grounded() {
+            return 0;
+        }
 
-        reindeer.push(Reindeer::new(name, speed, race_seconds, rest_seconds));
+        let period = self.fly + self.rest;
+        let flying =
+            seconds / period * self.fly + (seconds % period).min(self.fly);
+        flying * self.speed
     }
 
-    for _ in 0..seconds {
-        let mut winning_distance = BinaryHeap::new();
+    /// Returns the first second where the reindeer has flown at least the
+    /// given distance (or `u64::MAX` if it never gets off the ground).
+    fn reaches(&self, distance: u64) -> u64 {
+        if distance == 0 {
+            return 0;
+        }
+        if self.is_grounded() {
+            return u64::MAX;
+        }
 
-        for r in &mut reindeer {
-            r.next();
-            winning_distance.push(r.distance);
+        let needed = distance.div_ceil(self.speed);
+        let cycles = (needed - 1) / self.fly;
+        cycles * (self.fly + self.rest) + needed - cycles * self.fly
+    }
+
+    /// Returns how far the reindeer flies during each of the seconds after
+    /// the given second and the last second until that changes.
+    fn phase(&self, second: u64) -> (u64, u64) {
+        if self.is_grounded() {
+            return (0, u64::MAX);
         }
 
-        let current_winner = winning_distance.pop().unwrap();
+        let period = self.fly + self.rest;
+        let into = second % period;
 
-        for r in &mut reindeer {
-            if r.distance == current_winner {
-                r.award_point();
-            }
+        if into < self.fly {
+            (self.speed, second + self.fly - into)
+        } else {
+            (0, second + period - into)
         }
     }
 
-    if part == 1 {
-        let mut distances = BinaryHeap::new();
-        for r in &reindeer {
-            distances.push(r.distance);
-        }
-        distances.pop().unwrap()
-    } else {
-        let mut points = BinaryHeap::new();
-        for r in &reindeer {
-            points.push(r.points);
+    /// Returns whether the reindeer never moves at all (because it either
+    /// has no speed or never spends any time flying).
+    fn is_grounded(&self) -> bool {
+        self.speed == 0 || self.fly == 0
+    }
+}
+
+impl<'a> Race<'a> {
+    /// Parses the reindeer from the input.
+    pub fn parse(input: &'a str) -> Self {
+        let reindeer = input
+            .lines()
+            .map(|line| {
+                let text: Vec<&str> = line.split_whitespace().collect();
+                Reindeer {
+                    name: text[0],
+                    speed: text[3].parse().unwrap(),
+                    fly: text[6].parse().unwrap(),
+                    rest: text[13].parse().unwrap(),
+                }
+            })
+            .collect();
+
+        Self { reindeer }
+    }
+
+    /// Returns the distance and points of every reindeer after the given
+    /// number of seconds, best first (by points and then by distance).
+    ///
+    /// Every second the reindeer that are furthest along get a point. While
+    /// no reindeer switches between flying and resting their distances just
+    /// grow linearly, so we jump from one interesting second to the next
+    /// instead of checking each of them (see [`Race::lead_changes`]).
+    ///
+    /// # Example
+    /// ```rust
+    /// # use aoc::y15d14::Race;
+    /// let input = concat!(
+    ///     "Comet can fly 14 km/s for 10 seconds, but ",
+    ///     "then must rest for 127 seconds.\n",
+    ///     "Dancer can fly 16 km/s for 11 seconds, but ",
+    ///     "then must rest for 162 seconds.\n",
+    /// );
+    /// let standings = Race::parse(input).leaderboard(4_000_000_000);
+    /// assert_eq!(standings[0].name, "Comet");
+    /// assert_eq!(standings[0].points, 3999992527);
+    /// assert_eq!(standings[1].points, 7925);
+    /// ```
+    pub fn leaderboard(&self, seconds: u64) -> Vec<Standing<'a>> {
+        let (points, _) = self.run(seconds, false);
+        let mut standings: Vec<_> = self
+            .reindeer
+            .iter()
+            .zip(points)
+            .map(|(reindeer, points)| Standing {
+                name: reindeer.name,
+                distance: reindeer.distance(seconds),
+                points,
+            })
+            .collect();
+
+        standings.sort_by(|a, b| {
+            (b.points, b.distance).cmp(&(a.points, a.distance))
+        });
+        standings
+    }
+
+    /// Returns every second (up to the given one) where the reindeer in the
+    /// lead changed and who took it.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use aoc::y15d14::{LeadChange, Race};
+    /// let input = concat!(
+    ///     "Comet can fly 14 km/s for 10 seconds, but ",
+    ///     "then must rest for 127 seconds.\n",
+    ///     "Dancer can fly 16 km/s for 11 seconds, but ",
+    ///     "then must rest for 162 seconds.\n",
+    /// );
+    /// let changes = Race::parse(input).lead_changes(200);
+    /// assert_eq!(changes[0], LeadChange { second: 1, leaders: vec!["Dancer"] });
+    /// assert_eq!(changes[1], LeadChange { second: 140, leaders: vec!["Comet"] });
+    /// assert_eq!(changes[2], LeadChange { second: 180, leaders: vec!["Dancer"] });
+    /// assert_eq!(changes.len(), 3);
+    /// ```
+    pub fn lead_changes(&self, seconds: u64) -> Vec<LeadChange<'a>> {
+        self.run(seconds, true).1
+    }
+
+    /// Runs the race, awarding the points (and keeping track of the lead
+    /// changes if asked to).
+    ///
+    /// At each interesting second we give a point to every reindeer that's
+    /// furthest along. Of those, the ones that are about to fly the fastest
+    /// will stay in the lead (and get all of the points) until either one of
+    /// them switches phases, another reindeer catches up (we know exactly
+    /// when since everyone moves at a constant speed until their phase
+    /// changes), or another reindeer switches phases. We only need to care
+    /// about the phases of the reindeer that could reach the leaders before
+    /// the leaders switch phases though (i.e., even flying the whole time),
+    /// which skips most of them. And when a single reindeer is in the lead it
+    /// stays there at least until another reindeer gets as far as it is now,
+    /// so once somebody pulls away we can skip over many cycles at a time.
+    fn run(
+        &self,
+        seconds: u64,
+        track: bool,
+    ) -> (Vec<u64>, Vec<LeadChange<'a>>) {
+        let n = self.reindeer.len();
+        let mut points = vec![0; n];
+        let mut changes: Vec<LeadChange> = Vec::new();
+        let mut record = |second, leaders: &[usize]| {
+            let leaders: Vec<_> =
+                leaders.iter().map(|i| self.reindeer[*i].name).collect();
+            if track && changes.last().map(|c| &c.leaders) != Some(&leaders) {
+                changes.push(LeadChange { second, leaders });
+            }
+        };
+
+        let mut second = 0;
+        loop {
+            let distances: Vec<_> =
+                self.reindeer.iter().map(|r| r.distance(second)).collect();
+            let Some(lead) = distances.iter().max().copied() else {
+                break;
+            };
+
+            if second > 0 {
+                let leaders: Vec<_> =
+                    (0..n).filter(|i| distances[*i] == lead).collect();
+                for leader in &leaders {
+                    points[*leader] += 1;
+                }
+                record(second, &leaders);
+            }
+
+            if second == seconds {
+                break;
+            }
+
+            let phases: Vec<_> =
+                self.reindeer.iter().map(|r| r.phase(second)).collect();
+            let speed = (0..n)
+                .filter(|i| distances[*i] == lead)
+                .map(|i| phases[i].0)
+                .max()
+                .unwrap();
+            let leaders: Vec<_> = (0..n)
+                .filter(|i| distances[*i] == lead && phases[*i].0 == speed)
+                .collect();
+
+            let mut next = leaders
+                .iter()
+                .map(|i| phases[*i].1)
+                .min()
+                .unwrap()
+                .min(seconds);
+
+            for (i, reindeer) in self.reindeer.iter().enumerate() {
+                let elapsed = next - second;
+                if leaders.contains(&i)
+                    || distances[i] + reindeer.speed * elapsed
+                        < lead + speed * elapsed
+                {
+                    continue;
+                }
+
+                next = next.min(phases[i].1);
+                if phases[i].0 > speed {
+                    let gap = lead - distances[i];
+                    next = next.min(second + gap.div_ceil(phases[i].0 - speed));
+                }
+            }
+
+            // a lone leader also stays in front at least until someone else
+            // gets to where it is now, which lets us skip over whole cycles
+            if let [leader] = leaders[..] {
+                if distances.iter().filter(|d| **d == lead).count() == 1 {
+                    let caught = (0..n)
+                        .filter(|i| *i != leader)
+                        .map(|i| self.reindeer[i].reaches(lead))
+                        .min()
+                        .unwrap_or(u64::MAX);
+                    next = next.max((caught - 1).min(seconds));
+                }
+            }
+
+            if next > second + 1 {
+                for leader in &leaders {
+                    points[*leader] += next - second - 1;
+                }
+                record(second + 1, &leaders);
+            }
+
+            second = next;
         }
-        points.pop().unwrap()
+
+        (points, changes)
     }
 }
 
@@ -172,42 +362,110 @@ mod tests {
     use std::fs;
 
     #[test]
-    fn test_reindeer_new() {
-        assert_eq!(
-            Reindeer::new("Test", 10, 15, 20),
-            Reindeer {
-                name: "Test".to_string(),
-                speed: 10,
-                race_seconds: 15,
-                rest_seconds: 20,
-                distance: 0,
-                points: 0,
-                is_flying: true,
-                left_in_phase: 15,
+    fn test_distance() {
+        let comet = Reindeer {
+            name: "Comet",
+            speed: 14,
+            fly: 10,
+            rest: 127,
+        };
+        let dancer = Reindeer {
+            name: "Dancer",
+            speed: 16,
+            fly: 11,
+            rest: 162,
+        };
+
+        assert_eq!(comet.distance(1000), 1120);
+        assert_eq!(dancer.distance(1000), 1056);
+
+        let mut distance = 0;
+        for second in 1..1000 {
+            if (second - 1) % 173 < 11 {
+                distance += 16;
             }
-        );
+            assert_eq!(dancer.distance(second), distance);
+        }
     }
 
     #[test]
-    fn test_reindeer_award_point() {
-        let mut r = Reindeer::new("Test", 1, 1, 1);
-        assert_eq!(r.points, 0);
-        r.award_point();
-        assert_eq!(r.points, 1);
+    fn test_leaderboard() {
+        let input = concat!(
+            "A can fly 5 km/s for 3 seconds, but then must rest for 4 seconds.\n",
+            "B can fly 3 km/s for 6 seconds, but then must rest for 1 seconds.\n",
+            "C can fly 9 km/s for 1 seconds, but then must rest for 5 seconds.\n",
+            "D can fly 2 km/s for 10 seconds, but then must rest for 2 seconds.\n",
+            "E can fly 15 km/s for 2 seconds, but then must rest for 11 seconds.\n",
+        );
+        let race = Race::parse(input);
+
+        for seconds in 0..300 {
+            let mut expected = points(&race, seconds);
+            let mut standings = race.leaderboard(seconds);
+            standings.sort_by_key(|s| s.name);
+            expected.sort();
+
+            let actual: Vec<_> =
+                standings.iter().map(|s| (s.name, s.points)).collect();
+            assert_eq!(actual, expected);
+        }
+
+        // every change really is a change and covers all of the seconds
+        let changes = race.lead_changes(300);
+        assert_eq!(changes[0].second, 1);
+        for pair in changes.windows(2) {
+            assert!(pair[0].second < pair[1].second);
+            assert_ne!(pair[0].leaders, pair[1].leaders);
+        }
     }
 
     #[test]
-    fn test_reindeer_next() {
-        let mut comet = Reindeer::new("Comet", 14, 10, 127);
-        let mut dancer = Reindeer::new("Dancer", 16, 11, 162);
+    fn test_grounded() {
+        // reindeer that never move are always tied for the lead with each
+        // other until somebody else gets off the ground
+        let input = concat!(
+            "A can fly 0 km/s for 3 seconds, but then must rest for 4 seconds.\n",
+            "B can fly 3 km/s for 0 seconds, but then must rest for 1 seconds.\n",
+            "C can fly 9 km/s for 0 seconds, but then must rest for 0 seconds.\n",
+        );
+        assert_eq!(y15d14(input, 10, 1), 0);
+        assert_eq!(y15d14(input, 10, 2), 10);
+        assert_eq!(
+            Race::parse(input).leaderboard(4_000_000_000)[0].points,
+            4_000_000_000
+        );
+
+        let flying =
+            input.replace("C can fly 9 km/s for 0", "C can fly 9 km/s for 2");
+        let race = Race::parse(&flying);
+        for seconds in 0..50 {
+            let mut expected = points(&race, seconds);
+            let mut standings = race.leaderboard(seconds);
+            standings.sort_by_key(|s| s.name);
+            expected.sort();
 
-        for _ in 0..1000 {
-            comet.next();
-            dancer.next();
+            let actual: Vec<_> =
+                standings.iter().map(|s| (s.name, s.points)).collect();
+            assert_eq!(actual, expected);
         }
+    }
 
-        assert_eq!(comet.distance, 1120);
-        assert_eq!(dancer.distance, 1056);
+    /// Brute force version of the points: step through every second.
+    fn points<'a>(race: &Race<'a>, seconds: u64) -> Vec<(&'a str, u64)> {
+        let mut points = vec![0; race.reindeer.len()];
+
+        for second in 1..=seconds {
+            let distances: Vec<_> =
+                race.reindeer.iter().map(|r| r.distance(second)).collect();
+            let lead = *distances.iter().max().unwrap();
+            for (i, distance) in distances.iter().enumerate() {
+                if *distance == lead {
+                    points[i] += 1;
+                }
+            }
+        }
+
+        race.reindeer.iter().map(|r| r.name).zip(points).collect()
     }
 
     #[test]
@@ -221,6 +479,13 @@ mod tests {
 
         assert_eq!(y15d14(input, 1000, 1), 1120);
         assert_eq!(y15d14(input, 1000, 2), 689);
+
+        let race = Race::parse(input);
+        let mut standings = race.leaderboard(20000);
+        standings.sort_by_key(|s| s.name);
+        let actual: Vec<_> =
+            standings.iter().map(|s| (s.name, s.points)).collect();
+        assert_eq!(actual, points(&race, 20000));
     }
 
     #[test]
@@ -229,5 +494,12 @@ mod tests {
 
         assert_eq!(y15d14(&contents, 2503, 1), 2640);
         assert_eq!(y15d14(&contents, 2503, 2), 1102);
+
+        let race = Race::parse(&contents);
+        let mut expected = points(&race, 2503);
+        expected
+            .sort_by_key(|(name, points)| (std::cmp::Reverse(*points), *name));
+        let standings = race.leaderboard(2503);
+        assert_eq!(standings[0].points, expected[0].1);
     }
 }
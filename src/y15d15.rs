@@ -35,51 +35,78 @@
 //! overall number of combinations that needed to be checked/excluded. While
 //! it still doesn't run extremely fast each part now runs in about 30 seconds
 //! which for a generic solution is acceptable to me.
+//!
+//! Even that was still testing every possible split of the teaspoons though,
+//! so now the [`Kitchen`] assigns the teaspoons one ingredient at a time and
+//! stops exploring as soon as it can tell that the rest of the ingredients
+//! can't possibly make a better (or valid) cookie. The total number of
+//! teaspoons, how much each property counts, and the constraints are all
+//! parameters instead of being hard-coded.
+
+use std::fmt;
+
+/// A requirement for the total of one of the properties of a cookie.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Constraint {
+    /// The total has to be exactly this.
+    Exactly(i64),
+    /// The total has to be at least this.
+    AtLeast(i64),
+    /// The total has to be at most this.
+    AtMost(i64),
+}
+
+/// The error for when the score of a cookie could be too big for an `i64`
+/// (e.g., with big weights or lots of teaspoons).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ScoreOverflow;
 
-use itertools::Itertools;
-use std::collections::BinaryHeap;
+/// The ingredients and the rules for making the best cookie out of them.
+#[derive(Clone, Debug)]
+pub struct Kitchen<'a> {
+    properties: Vec<&'a str>,
+    ingredients: Vec<(&'a str, Vec<i64>)>,
+    total: i64,
+    weights: Vec<u32>,
+    constraints: Vec<(usize, Constraint)>,
+}
+
+/// The best cookie: its score and how many teaspoons of each ingredient it
+/// uses.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cookie<'a> {
+    /// The score of the cookie.
+    pub score: i64,
+    /// The teaspoons of each ingredient (in the order of the input).
+    pub amounts: Vec<(&'a str, i64)>,
+}
+
+/// The state of the search that doesn't change as we go deeper.
+struct Search<'k, 'a> {
+    kitchen: &'k Kitchen<'a>,
+    // the smallest and largest amount of each property per teaspoon among
+    // the ingredients from each index to the end
+    smallest: Vec<Vec<i64>>,
+    largest: Vec<Vec<i64>>,
+    amounts: Vec<i64>,
+    best: Option<(i64, Vec<i64>)>,
+}
 
 /// The total number of teaspoons of ingredients that we must use to create
 /// cookies as provided by the prompt.
-const TOTAL: i32 = 100;
+const TOTAL: i64 = 100;
 
 /// The number of calories that a cookie must have to be a valid recipe as
 /// provided by the prompt.
-const CALORIES: i32 = 500;
-
-/// Ingredient represents an ingredient parsed from the input.
-#[derive(Debug)]
-struct Ingredient {
-    // name: String,
-    capacity: i32,
-    durability: i32,
-    flavor: i32,
-    texture: i32,
-    calories: i32,
-}
-
-/// Mix represents an `Ingredient` and a quantity of that ingredient.
-#[derive(Clone, Debug)]
-struct Mix<'a> {
-    ingredient: &'a Ingredient,
-    amount: i32,
-}
+const CALORIES: i64 = 500;
 
 /// The solution for the day fifteen challenge.
 ///
 /// As usual we take the input as a string and a variable to determine which
 /// part we're computing (in part `2` we need to limit the options to cookies
-/// that result in 500 calories). We start by processing the input and building
-/// `Ingredients` to keep track of the various qualities of the ingredients.
-/// We then create a vector of vectors, one for each ingredient where the
-/// sub-vector are all of the possible teaspoons of the ingredient to use to
-/// make the cookie (0 to 100). We then take the cartesian product of those
-/// vectors to get all of the possible recipes (both valid and invalid) and
-/// then filter the result to only the valid options (i.e., that use 100 total
-/// teaspoons, and in part `2` are 500 calories). With the list of valid
-/// recipes we compute the score of each one and add it to our
-/// [`std::collections::BinaryHeap`]. To get the highest possible score we just
-/// pop the heap.
+/// that result in 500 calories). Then we set up the [`Kitchen`] with the
+/// rules from the prompt: 100 teaspoons, every property except for calories
+/// counts towards the score, and in part two exactly 500 calories.
 ///
 /// # Example
 /// ```rust
@@ -93,113 +120,277 @@ struct Mix<'a> {
 /// assert_eq!(y15d15(input, 2), 125000000);
 /// ```
 pub fn y15d15(input: &str, part: u32) -> i32 {
-    let lines: Vec<_> = input.lines().collect();
-    let mut ingredients = Vec::new();
-    let mut scores = BinaryHeap::new();
-
-    for line in lines {
-        let text: Vec<&str> = line.split_whitespace().collect();
-        // let name = text[0].strip_suffix(':').unwrap().to_string();
-        let capacity = text[2].strip_suffix(',').unwrap().parse().unwrap();
-        let durability = text[4].strip_suffix(',').unwrap().parse().unwrap();
-        let flavor = text[6].strip_suffix(',').unwrap().parse().unwrap();
-        let texture = text[8].strip_suffix(',').unwrap().parse().unwrap();
-        let calories = text[10].parse().unwrap();
-
-        ingredients.push(Ingredient {
-            // name,
-            capacity,
-            durability,
-            flavor,
-            texture,
-            calories,
-        });
+    let mut kitchen = Kitchen::parse(input).total(TOTAL).weight("calories", 0);
+
+    if part == 2 {
+        kitchen = kitchen.constrain("calories", Constraint::Exactly(CALORIES));
     }
 
-    let mut mixes = Vec::new();
-    for ingredient in &ingredients {
-        let mut totals = Vec::new();
-        for amount in 0..TOTAL + 1 {
-            totals.push(Mix { amount, ingredient });
+    kitchen.best().unwrap().unwrap().score.try_into().unwrap()
+}
+
+impl<'a> Kitchen<'a> {
+    /// Parses the ingredients (each line is the name of an ingredient
+    /// followed by its properties and their amounts). By default we use
+    /// `100` teaspoons, every property has a weight of `1`, and there aren't
+    /// any constraints.
+    ///
+    /// # Panics
+    /// If the ingredients don't all have the same properties.
+    pub fn parse(input: &'a str) -> Self {
+        let mut properties = Vec::new();
+        let mut ingredients = Vec::new();
+
+        for line in input.lines() {
+            let (name, rest) = line.split_once(": ").unwrap();
+            let mut amounts = Vec::new();
+
+            for (i, property) in rest.split(", ").enumerate() {
+                let (property, amount) = property.split_once(' ').unwrap();
+                if ingredients.is_empty() {
+                    properties.push(property);
+                }
+                assert_eq!(properties.get(i), Some(&property));
+                amounts.push(amount.parse().unwrap());
+            }
+
+            assert_eq!(amounts.len(), properties.len());
+            ingredients.push((name, amounts));
+        }
+
+        Self {
+            weights: vec![1; properties.len()],
+            properties,
+            ingredients,
+            total: TOTAL,
+            constraints: Vec::new(),
         }
-        mixes.push(totals);
     }
 
-    let possibilities: Vec<Vec<Mix>> = mixes
-        .into_iter()
-        .multi_cartesian_product()
-        .filter(|p| valid_possibility(p, part))
-        .collect();
+    /// Sets the total number of teaspoons.
+    pub fn total(mut self, total: i64) -> Self {
+        self.total = total;
+        self
+    }
 
-    for possibility in possibilities {
-        scores.push(compute_score(&possibility));
+    /// Sets the weight of a property: the score is the product of the total
+    /// of each property (or zero if it's negative) raised to its weight, so a
+    /// weight of `0` means that the property doesn't count at all.
+    ///
+    /// # Panics
+    /// If the property doesn't exist.
+    pub fn weight(mut self, property: &str, weight: u32) -> Self {
+        let index = self.property(property);
+        self.weights[index] = weight;
+        self
     }
 
-    scores.pop().unwrap()
-}
+    /// Adds a constraint on the total of a property.
+    ///
+    /// # Panics
+    /// If the property doesn't exist.
+    pub fn constrain(mut self, property: &str, constraint: Constraint) -> Self {
+        let index = self.property(property);
+        self.constraints.push((index, constraint));
+        self
+    }
+
+    /// Returns the best cookie that meets all of the constraints (if there
+    /// is one).
+    ///
+    /// We go through the ingredients in order and try every amount of each
+    /// one (the last one just gets whatever is left). Before going any
+    /// deeper we figure out the range that the total of each property could
+    /// still end up in: the rest of the teaspoons could all go to the
+    /// ingredient with the smallest or the largest amount of the property. If
+    /// that range doesn't include a value that the constraints allow or if
+    /// the best score that it allows (using the largest possible total of
+    /// each property) isn't better than the best cookie that we've already
+    /// found then nothing down this path can help so we skip it. This is what
+    /// makes any property that is always negative cut off the search right
+    /// away.
+    ///
+    /// # Errors
+    /// If the total of a property or the score could be too big for an
+    /// `i64`. We check this before searching using the largest total that
+    /// each property could possibly have (the scores of all of the cookies
+    /// and of all of the ranges in between are at most that).
+    ///
+    /// # Example
+    /// ```rust
+    /// # use aoc::y15d15::{Constraint, Cookie, Kitchen, ScoreOverflow};
+    /// let input = concat!(
+    ///     "Candy: capacity -1, durability 3, flavor 1, texture 1, calories 2\n",
+    ///     "Chocolate: capacity 2, durability 2, flavor 1, texture 1, calories 8",
+    /// );
+    /// let kitchen = Kitchen::parse(input).total(10).weight("calories", 0);
+    /// assert_eq!(
+    ///     kitchen.best(),
+    ///     Ok(Some(Cookie {
+    ///         score: 40000,
+    ///         amounts: vec![("Candy", 0), ("Chocolate", 10)]
+    ///     }))
+    /// );
+    ///
+    /// let exactly = Constraint::Exactly(50);
+    /// assert_eq!(
+    ///     kitchen
+    ///         .clone()
+    ///         .constrain("calories", exactly)
+    ///         .best()
+    ///         .unwrap()
+    ///         .unwrap()
+    ///         .amounts,
+    ///     vec![("Candy", 5), ("Chocolate", 5)]
+    /// );
+    ///
+    /// let kitchen = kitchen.constrain("capacity", Constraint::AtMost(-1));
+    /// assert_eq!(kitchen.best().unwrap().unwrap().score, 0);
+    ///
+    /// let kitchen = kitchen.constrain("calories", Constraint::AtLeast(100));
+    /// assert_eq!(kitchen.best(), Ok(None));
+    ///
+    /// let kitchen = kitchen.weight("flavor", 40);
+    /// assert_eq!(kitchen.best(), Err(ScoreOverflow));
+    /// ```
+    pub fn best(&self) -> Result<Option<Cookie<'a>>, ScoreOverflow> {
+        let n = self.ingredients.len();
+        let bounds = |pick: fn(i64, i64) -> i64| {
+            let mut bounds: Vec<Vec<i64>> = Vec::new();
+            for (_, properties) in self.ingredients.iter().rev() {
+                let bound = match bounds.last() {
+                    Some(after) => properties
+                        .iter()
+                        .zip(after)
+                        .map(|(a, b)| pick(*a, *b))
+                        .collect(),
+                    None => properties.clone(),
+                };
+                bounds.push(bound);
+            }
+            bounds.reverse();
+            bounds
+        };
+
+        let mut search = Search {
+            kitchen: self,
+            smallest: bounds(i64::min),
+            largest: bounds(i64::max),
+            amounts: Vec::new(),
+            best: None,
+        };
+        if n > 0 && self.total >= 0 {
+            let mut highest = Vec::new();
+            for (smallest, largest) in
+                search.smallest[0].iter().zip(&search.largest[0])
+            {
+                self.total.checked_mul(*smallest).ok_or(ScoreOverflow)?;
+                highest.push(
+                    self.total.checked_mul(*largest).ok_or(ScoreOverflow)?,
+                );
+            }
+            self.score(&highest).ok_or(ScoreOverflow)?;
+
+            search.explore(&vec![0; self.properties.len()], self.total);
+        }
 
-/// This function determines whether the provided vector of ingredients and
-/// their quantities are valid: the quantities should add up to 100 and if
-/// we're in part `2` then the sum of the calories should add up to 500.
-fn valid_possibility(possibility: &[Mix], part: u32) -> bool {
-    let sum: i32 = possibility.iter().map(|p| p.amount).sum();
-    if sum != TOTAL {
-        return false;
+        let Some((score, amounts)) = search.best else {
+            return Ok(None);
+        };
+        Ok(Some(Cookie {
+            score,
+            amounts: self
+                .ingredients
+                .iter()
+                .zip(amounts)
+                .map(|((name, _), amount)| (*name, amount))
+                .collect(),
+        }))
     }
 
-    if part == 2 {
-        let calories: i32 = possibility
+    /// Returns the index of a property.
+    fn property(&self, property: &str) -> usize {
+        self.properties
             .iter()
-            .map(|p| p.ingredient.calories * p.amount)
-            .sum();
-        if calories != CALORIES {
-            return false;
-        }
+            .position(|p| *p == property)
+            .expect("unknown property")
     }
 
-    true
+    /// Computes the score of a cookie from the totals of its properties (or
+    /// `None` if it doesn't fit in an `i64`).
+    fn score(&self, totals: &[i64]) -> Option<i64> {
+        totals.iter().zip(&self.weights).try_fold(
+            1i64,
+            |score, (total, weight)| {
+                score.checked_mul((*total).max(0).checked_pow(*weight)?)
+            },
+        )
+    }
 }
 
-/// This function computes the score of a combination of ingredients and their
-/// quantities. For each of the qualities of an ingredient it's multiplied by
-/// the quantity and then summed with the result of that quality for the other
-/// ingredients in the combination. If any quality results in a negative
-/// number then it turns to zero and the entire score is zero (as described by
-/// the prompt).
-fn compute_score(possibility: &[Mix]) -> i32 {
-    let capacity: i32 = possibility
-        .iter()
-        .map(|p| p.ingredient.capacity * p.amount)
-        .sum();
-    if capacity <= 0 {
-        return 0;
-    }
+impl Search<'_, '_> {
+    /// Tries every amount of the next ingredient given the totals of the
+    /// properties so far and the teaspoons that are left.
+    fn explore(&mut self, totals: &[i64], left: i64) {
+        let kitchen = self.kitchen;
+        let i = self.amounts.len();
+
+        let lowest: Vec<_> = (0..totals.len())
+            .map(|p| totals[p] + left * self.smallest[i][p])
+            .collect();
+        let highest: Vec<_> = (0..totals.len())
+            .map(|p| totals[p] + left * self.largest[i][p])
+            .collect();
+
+        let possible =
+            kitchen.constraints.iter().all(
+                |(p, constraint)| match constraint {
+                    Constraint::Exactly(value) => {
+                        lowest[*p] <= *value && *value <= highest[*p]
+                    }
+                    Constraint::AtLeast(value) => highest[*p] >= *value,
+                    Constraint::AtMost(value) => lowest[*p] <= *value,
+                },
+            );
+        if !possible {
+            return;
+        }
+        if let Some((best, _)) = self.best {
+            if kitchen.score(&highest).unwrap() <= best {
+                return;
+            }
+        }
 
-    let durability: i32 = possibility
-        .iter()
-        .map(|p| p.ingredient.durability * p.amount)
-        .sum();
-    if durability <= 0 {
-        return 0;
-    }
+        let properties = &kitchen.ingredients[i].1;
+        if i == kitchen.ingredients.len() - 1 {
+            // the last ingredient gets the rest (and then the range of each
+            // property is just the total)
+            self.amounts.push(left);
+            // best already checked that the largest score fits
+            let score = kitchen.score(&highest).unwrap();
+            self.best = Some((score, self.amounts.clone()));
+            self.amounts.pop();
+            return;
+        }
 
-    let flavor: i32 = possibility
-        .iter()
-        .map(|p| p.ingredient.flavor * p.amount)
-        .sum();
-    if flavor <= 0 {
-        return 0;
-    }
+        for amount in 0..=left {
+            let next: Vec<_> = totals
+                .iter()
+                .zip(properties)
+                .map(|(total, property)| total + amount * property)
+                .collect();
 
-    let texture: i32 = possibility
-        .iter()
-        .map(|p| p.ingredient.texture * p.amount)
-        .sum();
-    if texture <= 0 {
-        return 0;
+            self.amounts.push(amount);
+            self.explore(&next, left - amount);
+            self.amounts.pop();
+        }
     }
+}
 
-    capacity * durability * flavor * texture
+impl fmt::Display for ScoreOverflow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "the score doesn't fit in an i64")
+    }
 }
 
 #[cfg(test)]
@@ -208,135 +399,96 @@ mod tests {
     use std::fs;
 
     #[test]
-    fn test_compute_score() {
-        let mut input = vec![Mix {
-            ingredient: &Ingredient {
-                capacity: 3,
-                durability: 4,
-                flavor: 5,
-                texture: 6,
-                calories: 7,
-            },
-            amount: 2,
-        }];
-
-        assert_eq!(compute_score(&input), 5760);
-
-        input = vec![Mix {
-            ingredient: &Ingredient {
-                capacity: 2,
-                durability: 4,
-                flavor: 2,
-                texture: -6,
-                calories: 7,
-            },
-            amount: 3,
-        }];
-
-        assert_eq!(compute_score(&input), 0);
+    fn test_score() {
+        let kitchen = Kitchen::parse("A: a 3, b 4, c 5, d 6, e 7");
+        assert_eq!(kitchen.score(&[6, 8, 10, 12, 14]), Some(80640));
+
+        let kitchen = kitchen.weight("e", 0).weight("a", 2);
+        assert_eq!(kitchen.score(&[6, 8, 10, 12, 14]), Some(34560));
+        assert_eq!(kitchen.score(&[6, 8, 10, -12, 14]), Some(0));
+        assert_eq!(kitchen.weight("b", 21).score(&[6, 8, 10, 12, 14]), None);
     }
 
     #[test]
-    fn test_valid_possibility() {
-        let mut input = vec![
-            Mix {
-                ingredient: &Ingredient {
-                    capacity: 1,
-                    durability: 1,
-                    flavor: 1,
-                    texture: 1,
-                    calories: 1,
-                },
-                amount: 1,
-            },
-            Mix {
-                ingredient: &Ingredient {
-                    capacity: 1,
-                    durability: 1,
-                    flavor: 1,
-                    texture: 1,
-                    calories: 1,
-                },
-                amount: 1,
-            },
-        ];
-
-        assert!(!valid_possibility(&input, 1));
-
-        input = vec![
-            Mix {
-                ingredient: &Ingredient {
-                    capacity: 1,
-                    durability: 1,
-                    flavor: 1,
-                    texture: 1,
-                    calories: 1,
-                },
-                amount: 50,
-            },
-            Mix {
-                ingredient: &Ingredient {
-                    capacity: 1,
-                    durability: 1,
-                    flavor: 1,
-                    texture: 1,
-                    calories: 1,
-                },
-                amount: 50,
-            },
-        ];
-
-        assert!(valid_possibility(&input, 1));
-
-        input = vec![
-            Mix {
-                ingredient: &Ingredient {
-                    capacity: 1,
-                    durability: 1,
-                    flavor: 1,
-                    texture: 1,
-                    calories: 4,
-                },
-                amount: 50,
-            },
-            Mix {
-                ingredient: &Ingredient {
-                    capacity: 1,
-                    durability: 1,
-                    flavor: 1,
-                    texture: 1,
-                    calories: 5,
-                },
-                amount: 50,
-            },
-        ];
-
-        assert!(!valid_possibility(&input, 2));
-
-        input = vec![
-            Mix {
-                ingredient: &Ingredient {
-                    capacity: 1,
-                    durability: 1,
-                    flavor: 1,
-                    texture: 1,
-                    calories: 1,
-                },
-                amount: 0,
-            },
-            Mix {
-                ingredient: &Ingredient {
-                    capacity: 1,
-                    durability: 1,
-                    flavor: 1,
-                    texture: 1,
-                    calories: 5,
-                },
-                amount: 100,
-            },
-        ];
+    fn test_best() {
+        let input = "A: a 1, b 1, c 1\nB: a 1, b 1, c 5\nC: a 2, b -1, c 0\n";
+        let kitchen = Kitchen::parse(input).total(12).weight("c", 0);
+
+        // check against trying every split
+        for constraint in [
+            None,
+            Some(Constraint::Exactly(20)),
+            Some(Constraint::AtLeast(40)),
+            Some(Constraint::AtMost(10)),
+            Some(Constraint::Exactly(7)),
+        ] {
+            let kitchen = match constraint {
+                Some(constraint) => kitchen.clone().constrain("c", constraint),
+                None => kitchen.clone(),
+            };
+
+            let mut best = None;
+            for a in 0..=12 {
+                for b in 0..=12 - a {
+                    let c = 12 - a - b;
+                    let totals = [a + b + 2 * c, a + b - c, a + 5 * b];
+                    let allowed = match constraint {
+                        Some(Constraint::Exactly(v)) => totals[2] == v,
+                        Some(Constraint::AtLeast(v)) => totals[2] >= v,
+                        Some(Constraint::AtMost(v)) => totals[2] <= v,
+                        None => true,
+                    };
+                    if allowed {
+                        best = best.max(kitchen.score(&totals));
+                    }
+                }
+            }
+
+            let cookie = kitchen.best().unwrap();
+            assert_eq!(cookie.as_ref().map(|c| c.score), best);
+            if let Some(cookie) = cookie {
+                let teaspoons: i64 =
+                    cookie.amounts.iter().map(|(_, a)| a).sum();
+                assert_eq!(teaspoons, 12);
+            }
+        }
+
+        assert_eq!(Kitchen::parse("").best(), Ok(None));
+        assert_eq!(
+            Kitchen::parse("A: a 1").total(0).best(),
+            Ok(Some(Cookie {
+                score: 0,
+                amounts: vec![("A", 0)]
+            }))
+        );
+    }
 
-        assert!(valid_possibility(&input, 2));
+    #[test]
+    fn test_overflow() {
+        let kitchen = Kitchen::parse(
+            "A: a 100
+B: a -1",
+        );
+        assert_eq!(
+            kitchen
+                .clone()
+                .weight("a", 4)
+                .best()
+                .unwrap()
+                .unwrap()
+                .score,
+            10i64.pow(16)
+        );
+        assert_eq!(kitchen.clone().weight("a", 5).best(), Err(ScoreOverflow));
+        assert_eq!(kitchen.clone().total(i64::MAX).best(), Err(ScoreOverflow));
+        // the smallest total doesn't count towards the score but it still
+        // has to fit
+        let kitchen = Kitchen::parse(
+            "A: a 1
+B: a -2",
+        )
+        .total(i64::MAX / 2 + 2);
+        assert_eq!(kitchen.best(), Err(ScoreOverflow));
     }
 
     #[test]
@@ -350,10 +502,18 @@ mod tests {
 
         assert_eq!(y15d15(input, 1), 62842880);
         assert_eq!(y15d15(input, 2), 57600000);
+        assert_eq!(
+            Kitchen::parse(input)
+                .weight("calories", 0)
+                .best()
+                .unwrap()
+                .unwrap()
+                .amounts,
+            vec![("Butterscotch", 44), ("Cinnamon", 56)]
+        );
     }
 
     #[test]
-    #[ignore]
     fn the_solution() {
         let contents = fs::read_to_string("input/2015/day15.txt").unwrap();
 
@@ -21,88 +21,96 @@
 //! disqualifying characteristic. The solution is therefore simple, check the
 //! information that we have about each Sue, if we can't find anything that
 //! disqualifies here then she's the one that we're looking for!
+//!
+//! The compounds themselves aren't special though, so the Sues are parsed
+//! into a map of whatever compounds we remember and the reading from the
+//! MFCSAM is a set of rules (one per compound) that we can check them
+//! against. That way we can also rank the Sues by how well they match and
+//! explain exactly what ruled out each of the others.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// The MFCSAM reading provided by the prompt.
+const MFCSAM: &str = "children: 3
+cats: 7
+samoyeds: 2
+pomeranians: 3
+akitas: 0
+vizslas: 0
+goldfish: 5
+trees: 3
+cars: 2
+perfumes: 1";
+
+/// The rules that replace the ones in the [`MFCSAM`] reading in part two:
+/// the prompt tells us that the reading is really a lower bound for cats and
+/// trees and an upper bound for pomeranians and goldfish.
+const OUTDATED: &str = "cats: >7
+trees: >3
+pomeranians: <3
+goldfish: <5";
+
+/// How a value that we remember about an Aunt Sue is compared to the reading.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Comparator {
+    /// The value must be exactly this.
+    Equal(u32),
+    /// The value must be greater than this.
+    Greater(u32),
+    /// The value must be less than this.
+    Less(u32),
+    /// The value must be between these two (inclusive).
+    Range(u32, u32),
+}
 
-use regex::Regex;
-
-/// How many children Aunt Sue has (provided by the prompt).
-const CHILDREN: u32 = 3;
-
-/// How many cats Aunt Sue has (provided by the prompt).
-const CATS: u32 = 7;
-
-/// How many samoyeds Aunt Sue has (provided by the prompt).
-const SAMOYEDS: u32 = 2;
-
-/// How many pomeranians Aunt Sue has (provided by the prompt).
-const POMERANIANS: u32 = 3;
-
-/// How many akitas Aunt Sue has (provided by the prompt).
-const AKITAS: u32 = 0;
-
-/// How many vizslas Aunt Sue has (provided by the prompt).
-const VIZSLAS: u32 = 0;
-
-/// How many goldfish Aunt Sue has (provided by the prompt).
-const GOLDFISH: u32 = 5;
-
-/// How many trees Aunt Sue has (provided by the prompt).
-const TREES: u32 = 3;
-
-/// How many cars Aunt Sue has (provided by the prompt).
-const CARS: u32 = 2;
+/// A reading from the MFCSAM: a rule for each of the compounds it detected.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Reading<'a> {
+    rules: BTreeMap<&'a str, Comparator>,
+}
 
-/// How many perfumes Aunt Sue has (provided by the prompt).
-const PERFUMES: u32 = 1;
+/// One of the Aunt Sues from the input list. She has an identifying number
+/// and whatever compounds we remember about her.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AuntSue<'a> {
+    /// The number that identifies this Aunt Sue.
+    pub number: u32,
+    /// The compounds we remember about her and how many of each there were.
+    pub compounds: BTreeMap<&'a str, u32>,
+}
 
-/// AuntSue represents one of the Aunt Sues from the input list. She has an
-/// identifying number, and then optional information that we remember about
-/// her.
-#[derive(Debug, PartialEq)]
-struct AuntSue {
-    number: u32,
-    children: Option<u32>,
-    cats: Option<u32>,
-    samoyeds: Option<u32>,
-    pomeranians: Option<u32>,
-    akitas: Option<u32>,
-    vizslas: Option<u32>,
-    goldfish: Option<u32>,
-    trees: Option<u32>,
-    cars: Option<u32>,
-    perfumes: Option<u32>,
+/// Something we remember about an Aunt Sue that doesn't match the reading.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Mismatch<'a> {
+    /// The compound that doesn't match.
+    pub compound: &'a str,
+    /// How many of the compound we remember.
+    pub value: u32,
+    /// The rule from the reading that the value breaks.
+    pub rule: Comparator,
 }
 
-impl AuntSue {
-    /// Creates a new `AuntSue` with the given identifying number and no
-    /// information remembered about her (to be filled in later).
-    fn new(number: u32) -> AuntSue {
-        AuntSue {
-            number,
-            children: None,
-            cats: None,
-            samoyeds: None,
-            pomeranians: None,
-            akitas: None,
-            vizslas: None,
-            goldfish: None,
-            trees: None,
-            cars: None,
-            perfumes: None,
-        }
-    }
+/// How well an Aunt Sue matches a reading.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Candidate<'a> {
+    /// The number that identifies the Aunt Sue.
+    pub number: u32,
+    /// How many of the compounds that we remember match the reading.
+    pub matches: usize,
+    /// The compounds that we remember that rule her out.
+    pub mismatches: Vec<Mismatch<'a>>,
 }
 
 /// The solution for the day sixteen challenge.
 ///
 /// Starting with the input as a string and the part we're solving (which only
 /// affects some of the comparisons that we do to find the real aunt sue) we
-/// parse the input into `AuntSue` objects and then try to find out if we have
-/// the one we're looking for. In theory we could optimize this further by not
-/// creating objects but running the comparison as we try to check each field,
-/// but I don't think that it's worth it. Leaving it as-is creates a cleaner,
-/// easier-to-reason-about solution. If we get `0` back (which is not a valid
-/// Aunt Sue) then it means that we exhausted the input list of Sues and were
-/// unable to find the one that we were looking for.
+/// build the [`Reading`] for that part and then rank all of the Aunt Sues
+/// against it (see [`y15d16_rank`]). If the best one doesn't match then we
+/// return `0` (which is not a valid Aunt Sue) to say that we exhausted the
+/// input list of Sues and were unable to find the one that we were looking
+/// for.
 ///
 /// # Example
 /// ```rust
@@ -116,138 +124,197 @@ impl AuntSue {
 /// assert_eq!(y15d16(input, 2), 2);
 /// ```
 pub fn y15d16(input: &str, part: u32) -> u32 {
-    let lines: Vec<_> = input.lines().collect();
-
-    let number_regex = Regex::new(r"^Sue (\d+):").unwrap();
-    let children_regex = Regex::new(r"children: (\d+)").unwrap();
-    let cats_regex = Regex::new(r"cats: (\d+)").unwrap();
-    let samoyeds_regex = Regex::new(r"samoyeds: (\d+)").unwrap();
-    let pomeranians_regex = Regex::new(r"pomeranians: (\d+)").unwrap();
-    let akitas_regex = Regex::new(r"akitas: (\d+)").unwrap();
-    let vizslas_regex = Regex::new(r"vizslas: (\d+)").unwrap();
-    let goldfish_regex = Regex::new(r"goldfish: (\d+)").unwrap();
-    let trees_regex = Regex::new(r"trees: (\d+)").unwrap();
-    let cars_regex = Regex::new(r"cars: (\d+)").unwrap();
-    let perfumes_regex = Regex::new(r"perfumes: (\d+)").unwrap();
-
-    for line in lines {
-        let number = number_regex.captures(line).unwrap();
-        let mut sue = AuntSue::new(number[1].parse().unwrap());
-
-        if let Some(c) = children_regex.captures(line) {
-            sue.children = Some(c[1].parse().unwrap());
-        }
-
-        if let Some(c) = cats_regex.captures(line) {
-            sue.cats = Some(c[1].parse().unwrap());
-        }
-
-        if let Some(s) = samoyeds_regex.captures(line) {
-            sue.samoyeds = Some(s[1].parse().unwrap());
-        }
-
-        if let Some(p) = pomeranians_regex.captures(line) {
-            sue.pomeranians = Some(p[1].parse().unwrap());
-        }
-
-        if let Some(a) = akitas_regex.captures(line) {
-            sue.akitas = Some(a[1].parse().unwrap());
-        }
-
-        if let Some(v) = vizslas_regex.captures(line) {
-            sue.vizslas = Some(v[1].parse().unwrap());
-        }
-
-        if let Some(g) = goldfish_regex.captures(line) {
-            sue.goldfish = Some(g[1].parse().unwrap());
-        }
-
-        if let Some(t) = trees_regex.captures(line) {
-            sue.trees = Some(t[1].parse().unwrap());
-        }
-
-        if let Some(c) = cars_regex.captures(line) {
-            sue.cars = Some(c[1].parse().unwrap());
-        }
-
-        if let Some(p) = perfumes_regex.captures(line) {
-            sue.perfumes = Some(p[1].parse().unwrap());
-        }
+    let candidates = y15d16_rank(input, &Reading::mfcsam(part));
 
-        if the_real_aunt_sue(&sue, part) {
-            return sue.number;
-        }
+    match candidates.first() {
+        Some(candidate) if candidate.mismatches.is_empty() => candidate.number,
+        _ => 0,
     }
+}
 
-    0
+/// Checks every Aunt Sue in the input against the reading and returns them
+/// ranked from best to worst: first by how many things rule them out, then by
+/// how many things match (the more we know about a Sue that matches, the
+/// more likely it is to really be her) and finally by number.
+///
+/// # Example
+/// ```rust
+/// # use aoc::y15d16::{y15d16_rank, Reading};
+/// let input = concat!(
+///     "Sue 1: cats: 9, cars: 2\n",
+///     "Sue 2: cats: 2, trees: 1\n",
+///     "Sue 3: cars: 1\n",
+/// );
+/// let reading = Reading::parse("cats: 5..10\ntrees: <3\ncars: 2");
+/// let ranked = y15d16_rank(input, &reading);
+///
+/// assert_eq!(ranked[0].number, 1);
+/// assert!(ranked[0].mismatches.is_empty());
+/// // both of the others are ruled out by one thing, but sue 2 has a match
+/// assert_eq!(ranked[1].number, 2);
+/// assert_eq!(ranked[1].mismatches[0].to_string(), "cats: 2 is not 5..10");
+/// assert_eq!(ranked[2].number, 3);
+/// assert_eq!(ranked[2].mismatches[0].to_string(), "cars: 1 is not 2");
+/// ```
+pub fn y15d16_rank<'a>(
+    input: &'a str,
+    reading: &Reading<'a>,
+) -> Vec<Candidate<'a>> {
+    let mut candidates: Vec<_> = input
+        .lines()
+        .map(|line| reading.check(&AuntSue::parse(line)))
+        .collect();
+
+    candidates.sort_by_key(|c| {
+        (c.mismatches.len(), std::cmp::Reverse(c.matches), c.number)
+    });
+    candidates
 }
 
-/// This function determines if the passed Aunt Sue is the _real_ Aunt Sue. We
-/// check each of her characteristics and if we find one that doesn't match
-/// the input we were given then we disqualify her. If we can't disqualify her
-/// then we have found the one we're looking for.
-fn the_real_aunt_sue(sue: &AuntSue, part: u32) -> bool {
-    if let Some(c) = sue.children {
-        if c != CHILDREN {
-            return false;
+impl Comparator {
+    /// Parses a comparator: either a plain number (`3`), a number with a `>`
+    /// or `<` in front of it, or an inclusive range (`2..5`).
+    ///
+    /// # Panics
+    /// If any of the numbers are invalid.
+    pub fn parse(text: &str) -> Self {
+        let text = text.trim();
+
+        if let Some(value) = text.strip_prefix('>') {
+            Self::Greater(value.trim().parse().unwrap())
+        } else if let Some(value) = text.strip_prefix('<') {
+            Self::Less(value.trim().parse().unwrap())
+        } else if let Some((low, high)) = text.split_once("..") {
+            Self::Range(
+                low.trim().parse().unwrap(),
+                high.trim().parse().unwrap(),
+            )
+        } else {
+            Self::Equal(text.parse().unwrap())
         }
     }
 
-    if let Some(c) = sue.cats {
-        if (part == 2 && c <= CATS) || (part == 1 && c != CATS) {
-            return false;
+    /// Returns whether the value satisfies the comparator.
+    pub fn matches(&self, value: u32) -> bool {
+        match *self {
+            Self::Equal(expected) => value == expected,
+            Self::Greater(bound) => value > bound,
+            Self::Less(bound) => value < bound,
+            Self::Range(low, high) => (low..=high).contains(&value),
         }
     }
+}
 
-    if let Some(s) = sue.samoyeds {
-        if s != SAMOYEDS {
-            return false;
+impl fmt::Display for Comparator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Equal(value) => write!(f, "{}", value),
+            Self::Greater(bound) => write!(f, ">{}", bound),
+            Self::Less(bound) => write!(f, "<{}", bound),
+            Self::Range(low, high) => write!(f, "{}..{}", low, high),
         }
     }
+}
 
-    if let Some(p) = sue.pomeranians {
-        if (part == 2 && p >= POMERANIANS) || (part == 1 && p != POMERANIANS) {
-            return false;
-        }
+impl<'a> Reading<'a> {
+    /// Parses a reading with one `compound: comparator` per line (see
+    /// [`Comparator::parse`]).
+    ///
+    /// # Panics
+    /// If a line is missing its comparator.
+    pub fn parse(input: &'a str) -> Self {
+        let rules = input
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                let (compound, comparator) = line.split_once(':').unwrap();
+                (compound.trim(), Comparator::parse(comparator))
+            })
+            .collect();
+
+        Self { rules }
     }
 
-    if let Some(a) = sue.akitas {
-        if a != AKITAS {
-            return false;
-        }
-    }
+    /// Returns the reading given by the prompt. In part two the cats and
+    /// trees readings are lower bounds and the pomeranians and goldfish
+    /// readings are upper bounds, which we read as a second reading whose
+    /// rules replace the ones from the first.
+    pub fn mfcsam(part: u32) -> Reading<'static> {
+        let mut reading = Reading::parse(MFCSAM);
 
-    if let Some(v) = sue.vizslas {
-        if v != VIZSLAS {
-            return false;
+        if part == 2 {
+            for (compound, comparator) in Reading::parse(OUTDATED).rules {
+                reading = reading.rule(compound, comparator);
+            }
         }
+
+        reading
     }
 
-    if let Some(g) = sue.goldfish {
-        if (part == 2 && g >= GOLDFISH) || (part == 1 && g != GOLDFISH) {
-            return false;
-        }
+    /// Sets (or replaces) the rule for the given compound.
+    pub fn rule(mut self, compound: &'a str, comparator: Comparator) -> Self {
+        self.rules.insert(compound, comparator);
+        self
     }
 
-    if let Some(t) = sue.trees {
-        if (part == 2 && t <= TREES) || (part == 1 && t != TREES) {
-            return false;
+    /// Checks what we remember about an Aunt Sue against the reading.
+    /// Compounds that the reading doesn't have a rule for can't tell us
+    /// anything and so they're neither matches nor mismatches.
+    pub fn check(&self, sue: &AuntSue<'a>) -> Candidate<'a> {
+        let mut matches = 0;
+        let mut mismatches = Vec::new();
+
+        for (compound, value) in &sue.compounds {
+            let Some(rule) = self.rules.get(compound) else {
+                continue;
+            };
+
+            if rule.matches(*value) {
+                matches += 1;
+            } else {
+                mismatches.push(Mismatch {
+                    compound,
+                    value: *value,
+                    rule: *rule,
+                });
+            }
         }
-    }
 
-    if let Some(c) = sue.cars {
-        if c != CARS {
-            return false;
+        Candidate {
+            number: sue.number,
+            matches,
+            mismatches,
         }
     }
+}
 
-    if let Some(p) = sue.perfumes {
-        if p != PERFUMES {
-            return false;
-        }
+impl<'a> AuntSue<'a> {
+    /// Parses an Aunt Sue from a line of the input, e.g.,
+    /// `Sue 1: cars: 9, akitas: 3, goldfish: 0`.
+    ///
+    /// # Panics
+    /// If the line is malformed.
+    pub fn parse(line: &'a str) -> Self {
+        let (name, compounds) = line.split_once(':').unwrap();
+        let number = name.trim_start_matches("Sue").trim().parse().unwrap();
+
+        let compounds = compounds
+            .split(',')
+            .filter(|compound| !compound.trim().is_empty())
+            .map(|compound| {
+                let (name, count) = compound.split_once(':').unwrap();
+                (name.trim(), count.trim().parse().unwrap())
+            })
+            .collect();
+
+        Self { number, compounds }
     }
+}
 
-    true
+impl fmt::Display for Mismatch<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {} is not {}", self.compound, self.value, self.rule)
+    }
 }
 
 #[cfg(test)]
@@ -256,102 +323,79 @@ mod tests {
     use std::fs;
 
     #[test]
-    fn test_auntsue_new() {
+    fn test_auntsue_parse() {
         assert_eq!(
-            AuntSue::new(10),
+            AuntSue::parse("Sue 10: cars: 9, akitas: 3, goldfish: 0"),
             AuntSue {
                 number: 10,
-                children: None,
-                cats: None,
-                samoyeds: None,
-                pomeranians: None,
-                akitas: None,
-                vizslas: None,
-                goldfish: None,
-                trees: None,
-                cars: None,
-                perfumes: None,
+                compounds: BTreeMap::from([
+                    ("cars", 9),
+                    ("akitas", 3),
+                    ("goldfish", 0)
+                ]),
             }
         );
     }
 
     #[test]
-    fn test_the_real_aunt_sue() {
-        let mut input = AuntSue::new(10);
-        input.children = Some(CHILDREN);
-        input.cats = Some(CATS);
-        input.samoyeds = Some(SAMOYEDS);
-        input.pomeranians = Some(POMERANIANS);
-        input.akitas = Some(AKITAS);
-        input.vizslas = Some(VIZSLAS);
-        input.goldfish = Some(GOLDFISH);
-        input.trees = Some(TREES);
-        input.cars = Some(CARS);
-        input.perfumes = Some(PERFUMES);
-
-        assert!(the_real_aunt_sue(&input, 1));
-
-        input.children = Some(CHILDREN + 1);
-        assert!(!the_real_aunt_sue(&input, 1));
-        input.children = Some(CHILDREN);
-
-        input.cats = Some(CATS + 1);
-        assert!(!the_real_aunt_sue(&input, 1));
-        input.cats = Some(CATS);
-
-        input.samoyeds = Some(SAMOYEDS + 1);
-        assert!(!the_real_aunt_sue(&input, 1));
-        input.samoyeds = Some(SAMOYEDS);
-
-        input.pomeranians = Some(POMERANIANS + 1);
-        assert!(!the_real_aunt_sue(&input, 1));
-        input.pomeranians = Some(POMERANIANS);
-
-        input.akitas = Some(AKITAS + 1);
-        assert!(!the_real_aunt_sue(&input, 1));
-        input.akitas = Some(AKITAS);
-
-        input.vizslas = Some(VIZSLAS + 1);
-        assert!(!the_real_aunt_sue(&input, 1));
-        input.vizslas = Some(VIZSLAS);
-
-        input.goldfish = Some(GOLDFISH + 1);
-        assert!(!the_real_aunt_sue(&input, 1));
-        input.goldfish = Some(GOLDFISH);
-
-        input.trees = Some(TREES + 1);
-        assert!(!the_real_aunt_sue(&input, 1));
-        input.trees = Some(TREES);
-
-        input.cars = Some(CARS + 1);
-        assert!(!the_real_aunt_sue(&input, 1));
-        input.cars = Some(CARS);
-
-        input.perfumes = Some(PERFUMES + 1);
-        assert!(!the_real_aunt_sue(&input, 1));
-        input.perfumes = Some(PERFUMES);
-
-        input.cats = Some(CATS + 1);
-        input.pomeranians = Some(POMERANIANS - 1);
-        input.goldfish = Some(GOLDFISH - 1);
-        input.trees = Some(TREES + 1);
-        assert!(the_real_aunt_sue(&input, 2));
-
-        input.cats = Some(CATS);
-        assert!(!the_real_aunt_sue(&input, 2));
-        input.cats = Some(CATS + 1);
-
-        input.pomeranians = Some(POMERANIANS);
-        assert!(!the_real_aunt_sue(&input, 2));
-        input.pomeranians = Some(POMERANIANS - 1);
-
-        input.goldfish = Some(GOLDFISH);
-        assert!(!the_real_aunt_sue(&input, 2));
-        input.goldfish = Some(GOLDFISH - 1);
-
-        input.trees = Some(TREES);
-        assert!(!the_real_aunt_sue(&input, 2));
-        input.trees = Some(TREES + 1);
+    fn test_comparator() {
+        assert_eq!(Comparator::parse("3"), Comparator::Equal(3));
+        assert_eq!(Comparator::parse(" > 7"), Comparator::Greater(7));
+        assert_eq!(Comparator::parse("<3"), Comparator::Less(3));
+        assert_eq!(Comparator::parse("2..5"), Comparator::Range(2, 5));
+
+        assert!(Comparator::Equal(3).matches(3));
+        assert!(!Comparator::Equal(3).matches(4));
+        assert!(Comparator::Greater(7).matches(8));
+        assert!(!Comparator::Greater(7).matches(7));
+        assert!(Comparator::Less(3).matches(2));
+        assert!(!Comparator::Less(3).matches(3));
+        assert!(Comparator::Range(2, 5).matches(2));
+        assert!(Comparator::Range(2, 5).matches(5));
+        assert!(!Comparator::Range(2, 5).matches(6));
+    }
+
+    #[test]
+    fn test_reading() {
+        let part1 = Reading::mfcsam(1);
+        let part2 = Reading::mfcsam(2);
+
+        assert_eq!(part1.rules.len(), 10);
+        assert_eq!(part1.rules["cats"], Comparator::Equal(7));
+        assert_eq!(part2.rules["cats"], Comparator::Greater(7));
+        assert_eq!(part2.rules["trees"], Comparator::Greater(3));
+        assert_eq!(part2.rules["pomeranians"], Comparator::Less(3));
+        assert_eq!(part2.rules["goldfish"], Comparator::Less(5));
+        assert_eq!(part2.rules["cars"], Comparator::Equal(2));
+
+        // part two only changes the direction of some of the rules, not the
+        // compounds or the values
+        assert_eq!(part2.rules.len(), 10);
+        for (compound, rule) in &part2.rules {
+            let value = match *rule {
+                Comparator::Greater(value) | Comparator::Less(value) => value,
+                _ => continue,
+            };
+            assert_eq!(part1.rules[compound], Comparator::Equal(value));
+        }
+
+        let sue = AuntSue::parse("Sue 4: cats: 8, cars: 2, trees: 3, pets: 9");
+        let candidate = part2.check(&sue);
+        assert_eq!(candidate.matches, 2);
+        assert_eq!(
+            candidate.mismatches,
+            vec![Mismatch {
+                compound: "trees",
+                value: 3,
+                rule: Comparator::Greater(3),
+            }]
+        );
+        assert_eq!(candidate.mismatches[0].to_string(), "trees: 3 is not >3");
+
+        let reading = part1.rule("pets", Comparator::Range(1, 3));
+        let candidate = reading.check(&sue);
+        assert_eq!(candidate.matches, 2);
+        assert_eq!(candidate.mismatches.len(), 2);
     }
 
     #[test]
@@ -360,5 +404,9 @@ mod tests {
 
         assert_eq!(y15d16(&contents, 1), 40);
         assert_eq!(y15d16(&contents, 2), 241);
+
+        let ranked = y15d16_rank(&contents, &Reading::mfcsam(1));
+        assert_eq!(ranked.len(), 500);
+        assert!(!ranked[1].mismatches.is_empty());
     }
 }
@@ -18,8 +18,16 @@
 //! A very easy challenge today that amounts to using all of the combinations
 //! of containers that we can use and seeing if we can use that combination to
 //! transport the desired number of liters of eggnog.
+//!
+//! Trying every combination doesn't scale past a few dozen containers though,
+//! so we count them instead: going through the containers one at a time we
+//! keep track of how many ways there are to fill each amount of liters with
+//! each number of containers. The combinations themselves can still be
+//! listed (lazily) if we need them. With hundreds of containers there can be
+//! way more combinations than fit in any integer type so the counts are
+//! [`Natural`]s.
 
-use itertools::Itertools;
+use crate::util::big::Natural;
 
 /// The solution for the day seventeen challenge.
 ///
@@ -29,13 +37,9 @@ use itertools::Itertools;
 /// possible combinations and part `2` for the number of combinations using the
 /// fewest number of containers). We start by parsing the input to create a
 /// vector of integers representing the capacity of each container. Then we
-/// find all of the possible combinations using from one to the total number
-/// of containers that we have. For each combination we check to see if it adds
-/// up to the correct number of liters. If it does then we increment our
-/// counter. If we're on part two as soon as we have a non-zero number of
-/// combinations we can return the answer because we take combinations from
-/// one to number of containers and the prompt only asks how many combinations
-/// are possible using the fewest number of containers.
+/// count the combinations for every number of containers at once (see
+/// [`y15d17_counts`]) and either add them all up or take the first one that
+/// isn't zero.
 ///
 /// # Example
 /// ```rust
@@ -46,38 +50,265 @@ use itertools::Itertools;
 /// assert_eq!(y15d17(input, 10, 2), 3);
 /// ```
 pub fn y15d17(input: &str, liters: u32, part: u32) -> u32 {
-    let lines: Vec<_> = input.lines().collect();
-    let mut containers: Vec<u32> = Vec::new();
-    let mut total = 0;
+    let containers = parse(input);
+    let counts = y15d17_counts(&containers, liters);
+
+    let combinations = if part == 1 {
+        counts.iter().sum()
+    } else {
+        counts
+            .into_iter()
+            .find(|count| !count.is_zero())
+            .unwrap_or_default()
+    };
+
+    combinations.to_u128().unwrap().try_into().unwrap()
+}
+
+/// Returns how many combinations of containers hold exactly the given number
+/// of liters for each number of containers (i.e., the first element is the
+/// number of combinations with no containers, the second with one container,
+/// and so on).
+///
+/// For each container (in order) and each amount that we could have already
+/// filled with each number of containers, the container can either be left
+/// out or used to fill that many more liters with one more container. That's
+/// `containers * liters * containers` additions of counts that can have up to
+/// one digit (in base `2^64`) for every 64 containers, so the only limits are
+/// time and the memory for the table of counts.
+///
+/// # Example
+/// ```rust
+/// # use aoc::y15d17::y15d17_counts;
+/// let counts = y15d17_counts(&[20, 15, 10, 5, 5], 25);
+/// let counts: Vec<_> = counts.iter().map(|c| c.to_u128().unwrap()).collect();
+/// assert_eq!(counts, vec![0, 0, 3, 1, 0, 0]);
+///
+/// let counts = y15d17_counts(&[1; 200], 100);
+/// assert_eq!(
+///     counts[100].to_string(),
+///     "90548514656103281165404177077484163874504589675413336841320"
+/// );
+/// ```
+pub fn y15d17_counts(containers: &[u32], liters: u32) -> Vec<Natural> {
+    let liters = liters as usize;
+    let n = containers.len();
+
+    // ways[filled * (n + 1) + used]
+    let mut ways = vec![Natural::default(); (liters + 1) * (n + 1)];
+    ways[0] = Natural::from(1);
 
-    for line in lines {
-        containers.push(line.parse().unwrap());
+    for (i, capacity) in containers.iter().enumerate() {
+        let capacity = *capacity as usize;
+        if capacity > liters {
+            continue;
+        }
+
+        for filled in (capacity..=liters).rev() {
+            for used in (0..=i).rev() {
+                let from = (filled - capacity) * (n + 1) + used;
+                let to = filled * (n + 1) + used + 1;
+                if !ways[from].is_zero() {
+                    // `from` always comes before `to` so we can split there
+                    let (before, after) = ways.split_at_mut(to);
+                    after[0] += &before[from];
+                }
+            }
+        }
     }
 
-    for i in 1..containers.len() {
-        let mut combinations = 0;
+    ways[liters * (n + 1)..].to_vec()
+}
+
+/// Lazily lists the combinations of containers that hold exactly the given
+/// number of liters as the (sorted) indices of the containers used.
+///
+/// # Example
+/// ```rust
+/// # use aoc::y15d17::y15d17_combinations;
+/// let mut combinations = y15d17_combinations(&[20, 15, 10, 5, 5], 25);
+/// assert_eq!(combinations.next(), Some(vec![0, 3]));
+/// assert_eq!(combinations.next(), Some(vec![0, 4]));
+/// assert_eq!(combinations.next(), Some(vec![1, 2]));
+/// assert_eq!(combinations.next(), Some(vec![1, 3, 4]));
+/// assert_eq!(combinations.next(), None);
+/// ```
+pub fn y15d17_combinations(
+    containers: &[u32],
+    liters: u32,
+) -> Combinations<'_> {
+    Combinations::new(containers, liters)
+}
 
-        for combination in containers.iter().combinations(i) {
-            if combination.into_iter().sum::<u32>() == liters {
-                combinations += 1;
+/// An iterator over the combinations of containers that hold exactly some
+/// number of liters (see [`y15d17_combinations`]).
+///
+/// Before we start we work out which amounts can be filled by the containers
+/// from each position on so that the search never has to backtrack out of a
+/// dead end: every step gets us closer to the next combination.
+#[derive(Debug)]
+pub struct Combinations<'a> {
+    containers: &'a [u32],
+    liters: usize,
+    reachable: Vec<bool>,
+    chosen: Vec<usize>,
+    stack: Vec<(usize, usize, usize)>,
+}
+
+impl<'a> Combinations<'a> {
+    /// Creates the iterator, working backwards through the containers to find
+    /// which amounts can be filled from each one on.
+    fn new(containers: &'a [u32], liters: u32) -> Self {
+        let liters = liters as usize;
+        let n = containers.len();
+
+        // reachable[i * (liters + 1) + amount]
+        let mut reachable = vec![false; (n + 1) * (liters + 1)];
+        reachable[n * (liters + 1)] = true;
+
+        for i in (0..n).rev() {
+            let capacity = containers[i] as usize;
+            for amount in 0..=liters {
+                reachable[i * (liters + 1) + amount] = reachable
+                    [(i + 1) * (liters + 1) + amount]
+                    || (capacity <= amount
+                        && reachable
+                            [(i + 1) * (liters + 1) + amount - capacity]);
             }
         }
 
-        if part == 2 && combinations != 0 {
-            return combinations;
+        let stack = if reachable[liters] {
+            vec![(0, liters, 0)]
+        } else {
+            Vec::new()
+        };
+
+        Self {
+            containers,
+            liters,
+            reachable,
+            chosen: Vec::new(),
+            stack,
+        }
+    }
+
+    /// Whether the given amount can be filled using the containers from the
+    /// given one on.
+    fn can_fill(&self, from: usize, amount: usize) -> bool {
+        self.reachable[from * (self.liters + 1) + amount]
+    }
+}
+
+impl Iterator for Combinations<'_> {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Vec<usize>> {
+        let (mut i, mut remaining, depth) = self.stack.pop()?;
+        self.chosen.truncate(depth);
+
+        while i < self.containers.len() {
+            let capacity = self.containers[i] as usize;
+
+            if self.can_fill(i + 1, remaining) {
+                self.stack.push((i + 1, remaining, self.chosen.len()));
+            }
+
+            if capacity <= remaining
+                && self.can_fill(i + 1, remaining - capacity)
+            {
+                self.chosen.push(i);
+                remaining -= capacity;
+            } else {
+                // the only way forward is leaving this container out which
+                // we just saved for later, so pick it back up
+                let (next, left, _) = self.stack.pop().unwrap();
+                i = next;
+                remaining = left;
+                continue;
+            }
+
+            i += 1;
         }
 
-        total += combinations;
+        Some(self.chosen.clone())
     }
+}
 
-    total
+/// Parses the capacity of each container from the input.
+fn parse(input: &str) -> Vec<u32> {
+    input.lines().map(|line| line.parse().unwrap()).collect()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use itertools::Itertools;
     use std::fs;
 
+    #[test]
+    fn test_counts() {
+        let containers = [3, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5, 8, 9, 7];
+
+        for liters in 0..40 {
+            let mut expected = vec![0; containers.len() + 1];
+            for combination in (0..containers.len()).powerset() {
+                if combination.iter().map(|i| containers[*i]).sum::<u32>()
+                    == liters
+                {
+                    expected[combination.len()] += 1;
+                }
+            }
+
+            let counts = y15d17_counts(&containers, liters);
+            let counts: Vec<_> =
+                counts.iter().map(|c| c.to_u128().unwrap()).collect();
+            assert_eq!(counts, expected);
+
+            let listed: Vec<_> =
+                y15d17_combinations(&containers, liters).collect();
+            assert_eq!(listed.len() as u128, expected.iter().sum::<u128>());
+            assert!(listed.iter().all_unique());
+            for combination in listed {
+                assert_eq!(
+                    combination.iter().map(|i| containers[*i]).sum::<u32>(),
+                    liters
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_large_inventory() {
+        let containers: Vec<u32> =
+            (0..300).map(|i| i * 37 % 197 + 100).collect();
+        let counts = y15d17_counts(&containers, 3000);
+
+        assert!(counts[0].is_zero());
+        assert!(counts.iter().any(|count| !count.is_zero()));
+
+        let first = y15d17_combinations(&containers, 3000).next().unwrap();
+        assert_eq!(first.iter().map(|i| containers[*i]).sum::<u32>(), 3000);
+    }
+
+    #[test]
+    fn test_huge_counts() {
+        // more combinations than fit in a u128: every one of the 2^200 ways
+        // to pick out of 200 identical containers is counted by size
+        let counts = y15d17_counts(&[1; 200], 200);
+        assert_eq!(counts[200].to_u128(), Some(1));
+
+        let mut total = Natural::from(1);
+        for _ in 0..200 {
+            total *= 2;
+        }
+        let counts: Vec<_> = (0..=200)
+            .map(|liters| y15d17_counts(&[1; 200], liters))
+            .collect();
+        let sum: Natural = counts.iter().enumerate().map(|(i, c)| &c[i]).sum();
+        assert_eq!(sum, total);
+        assert_eq!(counts[100][100].to_u128(), None);
+    }
+
     #[test]
     fn it_works() {
         let input = "20\n15\n10\n5\n5\n";
@@ -91,5 +322,6 @@ mod tests {
 
         assert_eq!(y15d17(&contents, 150, 1), 1638);
         assert_eq!(y15d17(&contents, 150, 2), 17);
+        assert_eq!(y15d17_combinations(&parse(&contents), 150).count(), 1638);
     }
 }
@@ -23,27 +23,76 @@
 //! HashMap lookup: if we don't have a neighbor because it doesn't exist in the
 //! grid then don't increment the turned-on-neighbor count. In part two we
 //! just continually always set the corners to on.
+//!
+//! The lights are also fun to watch, so [`y15d18_render`] sends each step to
+//! a [`crate::render::Render`].
+//!
+//! The HashMap grid has since been replaced by a general [`Automaton`] for
+//! any life-like rule (see [`Rule`]) on a flat vector of cells. It supports
+//! boards whose edges are off, boards that wrap around and boards that grow
+//! as needed, with any set of pinned lights (like the corners in part two),
+//! and can hash its state to find out when the lights start repeating.
 
+use crate::render::{Frame, NoRender, Render};
+use std::collections::hash_map::DefaultHasher;
 use std::collections::HashMap;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+/// A life-like rule: how many neighbors that are on it takes for a light that
+/// is off to turn on (birth) and for a light that is on to stay on
+/// (survival).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rule {
+    birth: [bool; 9],
+    survival: [bool; 9],
+}
+
+/// What happens at the edges of the board.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Topology {
+    /// Everything past the edges is always off.
+    Bounded,
+    /// The edges wrap around to the opposite side.
+    Toroidal,
+    /// There are no edges, the board grows to fit the lights that are on.
+    Infinite,
+}
+
+/// When the state of an [`Automaton`] started repeating.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cycle {
+    /// The first generation of the repeating states.
+    pub start: u64,
+    /// How many generations it takes to repeat (`1` for a still life).
+    pub period: u64,
+}
+
+/// A life-like cellular automaton.
+///
+/// The cells are stored as a flat vector of `0`s and `1`s (row by row) so
+/// that counting the neighbors is just adding up numbers. For an infinite
+/// board the vector covers the area around the lights that are on and
+/// `origin` is the coordinate of its first cell.
+#[derive(Clone, Debug)]
+pub struct Automaton {
+    rule: Rule,
+    topology: Topology,
+    width: usize,
+    height: usize,
+    origin: (i64, i64),
+    cells: Vec<u8>,
+    pinned: Vec<((i64, i64), bool)>,
+    generation: u64,
+    rows: Vec<u8>,
+}
 
 /// The solution for the day eighteen challenge.
 ///
 /// We take the input as a string as usual, and then accept the number of steps
 /// to process and the part of the problem that we're solving (in part `2` the
-/// corners are always on). We start by parsing the input to build a grid of
-/// the lights and their state. If we're in part `2` then we turn on the lights
-/// in the corner regardless of the initial input. Then for each step until we
-/// reach the total number of steps we build a new grid. We process all of the
-/// current lights and check their neighbors to see if they are on or not (if
-/// we're in part two and on a corner we just skip as we always leave the
-/// corner lights on). After we've counted the number of lights that are on we
-/// turn the light on or off based on the constraints from the prompt: if the
-/// light is currently on and it has two or three neighbors that are on then it
-/// stays on otherwise it turns off, and if it's currently off and it has three
-/// neighbors that are on then it turns on otherwise it stays off. If we're in
-/// part two we turn on the corner lights before replacing the current grid
-/// with the new grid that we've just built and moving on to the next step.
-/// Finally, we count the number of lights that are on and return the result.
+/// corners are always on). See [`y15d18_render`] for how the lights are
+/// set up.
 ///
 /// # Example
 /// ```rust
@@ -54,87 +103,450 @@ use std::collections::HashMap;
 /// assert_eq!(y15d18(input, 3, 2), 18);
 /// ```
 pub fn y15d18(input: &str, steps: u32, part: u32) -> u32 {
-    let lines: Vec<_> = input.lines().collect();
-    let size: i32 = lines.len().try_into().unwrap();
-    let mut grid = HashMap::new();
+    y15d18_render(input, steps, part, &mut NoRender)
+}
+
+/// The solution for the day eighteen challenge, with visualization.
+///
+/// We build a bounded [`Automaton`] with Conway's rules (if a light is on and
+/// it has two or three neighbors that are on then it stays on, and if it's
+/// off and it has three neighbors that are on then it turns on) and in part
+/// two we pin the four corners on. Then we run it for the given number of
+/// steps (rendering the initial state of the lights and then their state
+/// after every step) and count the lights that are on.
+///
+/// # Example
+/// ```rust
+/// # use aoc::y15d18::y15d18_render;
+/// # use aoc::render::NoRender;
+/// // probably read this from the input file...
+/// let input = ".#.#.#\n...##.\n#....#\n..#...\n#.#..#\n####..";
+/// assert_eq!(y15d18_render(input, 1, 1, &mut NoRender), 11);
+/// ```
+pub fn y15d18_render(
+    input: &str,
+    steps: u32,
+    part: u32,
+    render: &mut impl Render,
+) -> u32 {
+    let mut lights = Automaton::parse(input, Rule::conway(), Topology::Bounded);
+
+    if part == 2 && lights.width > 0 && lights.height > 0 {
+        // in part two the corners are always on (if there are any)
+        let right = lights.width as i64 - 1;
+        let bottom = lights.height as i64 - 1;
+        lights = lights
+            .pin(0, 0, true)
+            .pin(right, 0, true)
+            .pin(0, bottom, true)
+            .pin(right, bottom, true);
+    }
+
+    render.frame_with(|| lights.frame());
+
+    for _ in 0..steps {
+        lights.step();
+
+        render.frame_with(|| lights.frame());
+    }
+
+    render.finish(&|| lights.frame());
+
+    lights.alive().try_into().unwrap()
+}
+
+impl Rule {
+    /// Conway's Game of Life: `B3/S23`.
+    pub fn conway() -> Rule {
+        Rule::parse("B3/S23").unwrap()
+    }
 
-    for (y, line) in lines.iter().enumerate() {
-        for (x, light) in line.chars().enumerate() {
-            grid.insert((x as i32, y as i32), light == '#');
+    /// Parses a rule string in the `B36/S23` notation (the parts can come in
+    /// either order and either part can be empty). Returns `None` if the rule
+    /// string is invalid.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use aoc::y15d18::Rule;
+    /// let highlife = Rule::parse("B36/S23").unwrap();
+    /// assert_eq!(highlife.to_string(), "B36/S23");
+    /// assert_eq!(Rule::parse("s23/b3"), Some(Rule::conway()));
+    /// assert_eq!(Rule::parse("B39/S23"), None);
+    /// ```
+    pub fn parse(text: &str) -> Option<Rule> {
+        let mut rule = Rule {
+            birth: [false; 9],
+            survival: [false; 9],
+        };
+        let mut seen = [false; 2];
+
+        for part in text.trim().split('/') {
+            let mut chars = part.chars();
+            let (counts, index) = match chars.next()?.to_ascii_uppercase() {
+                'B' => (&mut rule.birth, 0),
+                'S' => (&mut rule.survival, 1),
+                _ => return None,
+            };
+
+            if seen[index] {
+                return None;
+            }
+            seen[index] = true;
+
+            for c in chars {
+                let count = c.to_digit(10).filter(|count| *count <= 8)?;
+                counts[count as usize] = true;
+            }
+        }
+
+        if seen == [true, true] {
+            Some(rule)
+        } else {
+            None
         }
     }
 
-    if part == 2 {
-        // in part two the corners are always on
-        grid.insert((0, 0), true);
-        grid.insert((size - 1, 0), true);
-        grid.insert((0, size - 1), true);
-        grid.insert((size - 1, size - 1), true);
+    /// Returns the next state of a cell given whether it's on and how many of
+    /// its neighbors are on.
+    fn next(&self, alive: bool, neighbors: u8) -> bool {
+        if alive {
+            self.survival[neighbors as usize]
+        } else {
+            self.birth[neighbors as usize]
+        }
     }
+}
 
-    for _ in 0..steps {
-        let mut newgrid = HashMap::new();
-
-        for ((x, y), light) in &grid {
-            if part == 2
-                && ((*x == size - 1 || *x == 0) && *y == 0
-                    || (*x == 0 && *y == -1)
-                    || (*x == size - 1 && *y == size - 1))
-            {
-                continue;
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "B")?;
+        for (count, _) in self.birth.iter().enumerate().filter(|(_, b)| **b) {
+            write!(f, "{}", count)?;
+        }
+
+        write!(f, "/S")?;
+        for (count, _) in self.survival.iter().enumerate().filter(|(_, s)| **s)
+        {
+            write!(f, "{}", count)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Automaton {
+    /// Creates an automaton from a grid of lights (`#` for on, `.` for off)
+    /// with its top-left corner at `(0, 0)`.
+    ///
+    /// # Panics
+    /// If the rule turns on lights with no neighbors (`B0`) on an
+    /// infinite board as the whole (infinite) board would turn on.
+    pub fn parse(input: &str, rule: Rule, topology: Topology) -> Automaton {
+        assert!(
+            !(topology == Topology::Infinite && rule.birth[0]),
+            "B0 rules need a finite board"
+        );
+
+        let lines: Vec<_> = input.lines().collect();
+        let width = lines.iter().map(|line| line.len()).max().unwrap_or(0);
+        let height = lines.len();
+        let mut cells = vec![0; width * height];
+
+        for (y, line) in lines.iter().enumerate() {
+            for (x, light) in line.chars().enumerate() {
+                cells[y * width + x] = u8::from(light == '#');
             }
+        }
 
-            let neighbors = vec![
-                (*x - 1, *y - 1),
-                (*x, *y - 1),
-                (*x + 1, *y - 1),
-                (*x + 1, *y),
-                (*x + 1, *y + 1),
-                (*x, *y + 1),
-                (*x - 1, *y + 1),
-                (*x - 1, *y),
-            ];
-            let mut on = 0;
-
-            for neighbor in neighbors {
-                if let Some(switch) = grid.get(&neighbor) {
-                    if *switch {
-                        on += 1;
-                    }
-                }
+        let mut automaton = Automaton {
+            rule,
+            topology,
+            width,
+            height,
+            origin: (0, 0),
+            cells,
+            pinned: Vec::new(),
+            generation: 0,
+            rows: Vec::new(),
+        };
+        automaton.refit();
+        automaton
+    }
+
+    /// Pins the light at the given coordinate so that it's always on (or
+    /// always off), starting right away.
+    ///
+    /// # Panics
+    /// If the coordinate is outside of a finite board.
+    pub fn pin(mut self, x: i64, y: i64, alive: bool) -> Automaton {
+        assert!(
+            self.topology == Topology::Infinite || self.index(x, y).is_some(),
+            "can't pin a light outside of the board"
+        );
+
+        self.pinned.push(((x, y), alive));
+        self.apply_pins();
+        self
+    }
+
+    /// Returns whether the light at the given coordinate is on.
+    pub fn get(&self, x: i64, y: i64) -> bool {
+        self.index(x, y).is_some_and(|i| self.cells[i] == 1)
+    }
+
+    /// Returns how many lights are on.
+    pub fn alive(&self) -> usize {
+        self.cells.iter().filter(|cell| **cell == 1).count()
+    }
+
+    /// Returns how many steps have been taken.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Moves all of the lights forward by one step.
+    ///
+    /// The neighbor counts are done in two passes: first each cell gets the
+    /// sum of itself and its left and right neighbors, and then each cell
+    /// adds up those sums for the rows above, at and below it (and takes
+    /// itself back out). That's six additions per cell instead of eight
+    /// lookups.
+    pub fn step(&mut self) {
+        let (width, height) = (self.width, self.height);
+        if width == 0 || height == 0 {
+            self.generation += 1;
+            return;
+        }
+
+        let wrap = self.topology == Topology::Toroidal;
+        self.rows.resize(width * height, 0);
+
+        for y in 0..height {
+            let row = &self.cells[y * width..(y + 1) * width];
+            let sums = &mut self.rows[y * width..(y + 1) * width];
+            for x in 0..width {
+                let left = match x {
+                    0 if wrap => row[width - 1],
+                    0 => 0,
+                    _ => row[x - 1],
+                };
+                let right = match x + 1 {
+                    r if r < width => row[r],
+                    _ if wrap => row[0],
+                    _ => 0,
+                };
+                sums[x] = left + row[x] + right;
             }
+        }
+
+        let mut next = vec![0; width * height];
+        for y in 0..height {
+            let above = match y {
+                0 if wrap => Some(height - 1),
+                0 => None,
+                _ => Some(y - 1),
+            };
+            let below = match y + 1 {
+                b if b < height => Some(b),
+                _ if wrap => Some(0),
+                _ => None,
+            };
 
-            if *light {
-                if on == 2 || on == 3 {
-                    newgrid.insert((*x, *y), true);
-                } else {
-                    newgrid.insert((*x, *y), false);
+            for x in 0..width {
+                let i = y * width + x;
+                let mut neighbors = self.rows[i] - self.cells[i];
+                if let Some(above) = above {
+                    neighbors += self.rows[above * width + x];
                 }
-            } else if on == 3 {
-                newgrid.insert((*x, *y), true);
-            } else {
-                newgrid.insert((*x, *y), false);
+                if let Some(below) = below {
+                    neighbors += self.rows[below * width + x];
+                }
+
+                next[i] =
+                    u8::from(self.rule.next(self.cells[i] == 1, neighbors));
+            }
+        }
+
+        self.cells = next;
+        self.generation += 1;
+        self.apply_pins();
+        self.refit();
+    }
+
+    /// Takes the given number of steps.
+    pub fn steps(&mut self, steps: u64) {
+        for _ in 0..steps {
+            self.step();
+        }
+    }
+
+    /// Returns a hash of the lights that are on (and where they are).
+    /// Different states could in theory have the same hash, so use
+    /// [`Automaton::state`] when that matters.
+    pub fn state_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        for point in self.state() {
+            point.hash(&mut hasher);
+        }
+
+        hasher.finish()
+    }
+
+    /// Returns the coordinates of the lights that are on (row by row).
+    pub fn state(&self) -> Vec<(i64, i64)> {
+        (0..self.cells.len())
+            .filter(|i| self.cells[*i] == 1)
+            .map(|i| {
+                let x = self.origin.0 + (i % self.width) as i64;
+                let y = self.origin.1 + (i / self.width) as i64;
+                (x, y)
+            })
+            .collect()
+    }
+
+    /// Steps the automaton until its state repeats (or until the given
+    /// number of steps have been taken, in which case we return `None`). A
+    /// period of one is a still life and anything longer is an oscillator.
+    ///
+    /// Every state that we've seen is kept (and not just its hash) so that a
+    /// collision can't give us the wrong period.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use aoc::y15d18::{Automaton, Cycle, Rule, Topology};
+    /// let mut blinker =
+    ///     Automaton::parse("...\n###\n...", Rule::conway(), Topology::Infinite);
+    /// let cycle = blinker.find_cycle(10);
+    /// assert_eq!(cycle, Some(Cycle { start: 0, period: 2 }));
+    /// ```
+    pub fn find_cycle(&mut self, steps: u64) -> Option<Cycle> {
+        let mut seen = HashMap::from([(self.state(), self.generation)]);
+
+        for _ in 0..steps {
+            self.step();
+            if let Some(start) = seen.insert(self.state(), self.generation) {
+                return Some(Cycle {
+                    start,
+                    period: self.generation - start,
+                });
             }
         }
 
-        if part == 2 {
-            // in part two the corners are always on
-            newgrid.insert((0, 0), true);
-            newgrid.insert((size - 1, 0), true);
-            newgrid.insert((0, size - 1), true);
-            newgrid.insert((size - 1, size - 1), true);
+        None
+    }
+
+    /// Draws the lights (on lights are yellow, off lights are dark blue).
+    pub fn frame(&self) -> Frame {
+        Frame::from_fn(self.width, self.height, |x, y| {
+            if self.cells[y * self.width + x] == 1 {
+                [255, 221, 87]
+            } else {
+                [15, 15, 35]
+            }
+        })
+    }
+
+    /// Returns the index of the cell at the given coordinate if it's in the
+    /// vector.
+    fn index(&self, x: i64, y: i64) -> Option<usize> {
+        let x = usize::try_from(x - self.origin.0).ok()?;
+        let y = usize::try_from(y - self.origin.1).ok()?;
+        (x < self.width && y < self.height).then(|| y * self.width + x)
+    }
+
+    /// Sets all of the pinned lights.
+    fn apply_pins(&mut self) {
+        if self.topology == Topology::Infinite {
+            self.refit();
         }
 
-        grid = newgrid;
+        for i in 0..self.pinned.len() {
+            let ((x, y), alive) = self.pinned[i];
+            if let Some(index) = self.index(x, y) {
+                self.cells[index] = u8::from(alive);
+            }
+        }
     }
 
-    grid.retain(|_, x| *x); // retain lights that are on
-    grid.len().try_into().unwrap()
+    /// Makes sure that an infinite board has room for everything that could
+    /// turn on in the next step: if there's a light that's on (or pinned) at
+    /// the edge of the vector we re-center the vector on the lights with some
+    /// room to spare (which also shrinks it if the lights moved away).
+    fn refit(&mut self) {
+        const MARGIN: i64 = 8;
+
+        if self.topology != Topology::Infinite {
+            return;
+        }
+
+        let (width, height) = (self.width, self.height);
+        let on = |x: usize, y: usize| self.cells[y * width + x] == 1;
+        let pinned = self.pinned.iter().filter(|(_, alive)| *alive);
+        let edge = width > 0
+            && height > 0
+            && ((0..width).any(|x| on(x, 0) || on(x, height - 1))
+                || (0..height).any(|y| on(0, y) || on(width - 1, y)));
+        let outside = pinned.clone().any(|((x, y), _)| {
+            let (x, y) = (x - self.origin.0, y - self.origin.1);
+            x <= 0 || y <= 0 || x >= width as i64 - 1 || y >= height as i64 - 1
+        });
+        if !edge && !outside {
+            return;
+        }
+
+        let (mut left, mut top) = (i64::MAX, i64::MAX);
+        let (mut right, mut bottom) = (i64::MIN, i64::MIN);
+        let mut include = |(x, y): (i64, i64)| {
+            left = left.min(x);
+            right = right.max(x);
+            top = top.min(y);
+            bottom = bottom.max(y);
+        };
+        for ((x, y), _) in pinned {
+            include((*x, *y));
+        }
+        for y in 0..height {
+            let row = &self.cells[y * width..(y + 1) * width];
+            if let Some(first) = row.iter().position(|cell| *cell == 1) {
+                let last = row.iter().rposition(|cell| *cell == 1).unwrap();
+                include((
+                    self.origin.0 + first as i64,
+                    self.origin.1 + y as i64,
+                ));
+                include((
+                    self.origin.0 + last as i64,
+                    self.origin.1 + y as i64,
+                ));
+            }
+        }
+
+        let (left, top) = (left - MARGIN, top - MARGIN);
+        let fitted = (
+            (right + MARGIN - left + 1) as usize,
+            (bottom + MARGIN - top + 1) as usize,
+        );
+        let mut cells = vec![0; fitted.0 * fitted.1];
+        for y in 0..height {
+            for x in 0..width {
+                if on(x, y) {
+                    let nx = (self.origin.0 + x as i64 - left) as usize;
+                    let ny = (self.origin.1 + y as i64 - top) as usize;
+                    cells[ny * fitted.0 + nx] = 1;
+                }
+            }
+        }
+
+        self.origin = (left, top);
+        (self.width, self.height) = fitted;
+        self.cells = cells;
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::generate;
+    use crate::render::Sampled;
     use std::fs;
 
     #[test]
@@ -144,11 +556,234 @@ mod tests {
         assert_eq!(y15d18(input, 5, 2), 17);
     }
 
+    /// Counts the frames that it's given (and keeps the last one).
+    #[derive(Default)]
+    struct Counter {
+        frames: usize,
+        last: Option<Frame>,
+        finished: bool,
+    }
+
+    impl Render for Counter {
+        fn frame(&mut self, frame: &Frame) {
+            self.frames += 1;
+            self.last = Some(frame.clone());
+        }
+
+        fn finish(&mut self, _last: &dyn Fn() -> Frame) {
+            self.finished = true;
+        }
+    }
+
+    #[test]
+    fn test_empty() {
+        for part in [1, 2] {
+            assert_eq!(y15d18("", 3, part), 0);
+        }
+    }
+
+    #[test]
+    fn test_render() {
+        let input = ".#.#.#\n...##.\n#....#\n..#...\n#.#..#\n####..\n";
+        let mut counter = Counter::default();
+
+        // the initial state plus one frame for every step
+        assert_eq!(y15d18_render(input, 3, 1, &mut counter), 4);
+        assert_eq!(counter.frames, 4);
+        assert!(counter.finished);
+        let last = counter.last.unwrap();
+        assert_eq!((last.width(), last.height()), (6, 6));
+
+        // sampling keeps the initial state and the second step, and then
+        // the (skipped) final state is only built when it finishes
+        let mut sampled = Sampled::new(Counter::default(), 2);
+        assert_eq!(y15d18_render(input, 3, 1, &mut sampled), 4);
+        let counter = sampled.into_inner();
+        assert_eq!(counter.frames, 3);
+        assert_eq!(counter.last, Some(last));
+    }
+
+    #[test]
+    fn properties() {
+        for input in generate::cases("y15d18", 60, 8) {
+            let height = input.lines().count();
+            let width = input.lines().next().unwrap().len();
+            for steps in 0..4 {
+                assert_eq!(
+                    y15d18(&input, steps, 1),
+                    life(&input, steps, false)
+                );
+                assert_eq!(y15d18(&input, steps, 2), life(&input, steps, true));
+            }
+
+            assert!(y15d18(&input, 3, 1) as usize <= width * height);
+            assert!(
+                y15d18(&input, 3, 2) as usize >= width.min(2) * height.min(2)
+            );
+        }
+    }
+
+    #[test]
+    fn test_rule() {
+        assert_eq!(Rule::conway().to_string(), "B3/S23");
+        assert_eq!(Rule::parse("B/S").unwrap().to_string(), "B/S");
+        assert_eq!(Rule::parse("B36/S23").unwrap().to_string(), "B36/S23");
+        assert_eq!(
+            Rule::parse("S012345678/B3").unwrap().to_string(),
+            "B3/S012345678"
+        );
+        assert_eq!(Rule::parse("B3"), None);
+        assert_eq!(Rule::parse("B3/B3"), None);
+        assert_eq!(Rule::parse("B3/S2/S3"), None);
+        assert_eq!(Rule::parse("X3/S23"), None);
+        assert_eq!(Rule::parse(""), None);
+    }
+
+    #[test]
+    fn test_topologies() {
+        let glider = ".#.\n..#\n###\n";
+
+        // on a torus the glider comes back where it started after moving one
+        // cell diagonally every four steps
+        let input: String = (0..8)
+            .map(|y| {
+                let line = glider.lines().nth(y).unwrap_or("");
+                format!("{:.<8}\n", line)
+            })
+            .collect();
+        let mut torus =
+            Automaton::parse(&input, Rule::conway(), Topology::Toroidal);
+        assert_eq!(
+            torus.find_cycle(100),
+            Some(Cycle {
+                start: 0,
+                period: 32
+            })
+        );
+        assert_eq!(torus.alive(), 5);
+
+        // on a bounded board it turns into a block in the corner
+        let mut bounded =
+            Automaton::parse(&input, Rule::conway(), Topology::Bounded);
+        let cycle = bounded.find_cycle(100).unwrap();
+        assert_eq!(cycle.period, 1);
+        assert_eq!(bounded.alive(), 4);
+        assert!(bounded.get(6, 6) && bounded.get(7, 7));
+
+        // and on an infinite board it never stops
+        let mut infinite =
+            Automaton::parse(glider, Rule::conway(), Topology::Infinite);
+        assert_eq!(infinite.find_cycle(1000), None);
+        assert_eq!(infinite.generation(), 1000);
+        assert_eq!(infinite.alive(), 5);
+        assert!(infinite.get(251, 252) && infinite.get(250, 252));
+        assert!(!infinite.get(1, 2));
+        assert!(infinite.width < 24 && infinite.height < 24);
+    }
+
+    #[test]
+    fn test_pinned() {
+        // a single pinned light keeps a block next to it alive and a pinned
+        // off light breaks up a blinker
+        let mut lights =
+            Automaton::parse("#.\n..", Rule::conway(), Topology::Infinite)
+                .pin(-5, -5, true)
+                .pin(-5, -4, true)
+                .pin(-4, -5, true);
+        lights.step();
+        assert_eq!(lights.alive(), 4);
+        assert!(lights.get(-4, -4));
+        assert_eq!(lights.find_cycle(10).unwrap().period, 1);
+
+        let mut blinker = Automaton::parse(
+            ".#.\n.#.\n.#.",
+            Rule::conway(),
+            Topology::Bounded,
+        )
+        .pin(1, 0, false);
+        assert_eq!(blinker.alive(), 2);
+        blinker.step();
+        assert_eq!(blinker.alive(), 0);
+    }
+
+    #[test]
+    fn test_infinite() {
+        // an infinite board should act just like a big enough bounded one
+        let highlife = Rule::parse("B36/S23").unwrap();
+        let pattern = ["###", "#..", "#.."];
+        let padded: String = (0..60)
+            .map(|y| match y {
+                29..=31 => format!("{:.<29}{:.<31}\n", "", pattern[y - 29]),
+                _ => format!("{:.<60}\n", ""),
+            })
+            .collect();
+
+        let mut infinite =
+            Automaton::parse(&pattern.join("\n"), highlife, Topology::Infinite);
+        let mut bounded =
+            Automaton::parse(&padded, highlife, Topology::Bounded);
+        for _ in 0..20 {
+            infinite.step();
+            bounded.step();
+            assert_eq!(infinite.alive(), bounded.alive());
+            for y in -29..31 {
+                for x in -29..31 {
+                    assert_eq!(infinite.get(x, y), bounded.get(x + 29, y + 29));
+                }
+            }
+        }
+    }
+
+    /// A brute force version of the game of life on a fixed grid (with
+    /// optionally stuck corners) to check the solution against.
+    fn life(input: &str, steps: u32, stuck: bool) -> u32 {
+        let mut grid: Vec<Vec<bool>> = input
+            .lines()
+            .map(|line| line.chars().map(|c| c == '#').collect())
+            .collect();
+        let (width, height) = (grid[0].len(), grid.len());
+        let stick = |grid: &mut Vec<Vec<bool>>| {
+            if stuck {
+                for (x, y) in [(0, 0), (0, height - 1), (width - 1, 0)] {
+                    grid[y][x] = true;
+                }
+                grid[height - 1][width - 1] = true;
+            }
+        };
+
+        stick(&mut grid);
+        for _ in 0..steps {
+            let mut next = grid.clone();
+            for y in 0..height {
+                for x in 0..width {
+                    let mut on = 0;
+                    for ny in y.saturating_sub(1)..=(y + 1).min(height - 1) {
+                        for nx in x.saturating_sub(1)..=(x + 1).min(width - 1) {
+                            if (nx, ny) != (x, y) && grid[ny][nx] {
+                                on += 1;
+                            }
+                        }
+                    }
+                    next[y][x] = on == 3 || (grid[y][x] && on == 2);
+                }
+            }
+            grid = next;
+            stick(&mut grid);
+        }
+
+        grid.iter().flatten().filter(|on| **on).count() as u32
+    }
+
     #[test]
     fn the_solution() {
         let contents = fs::read_to_string("input/2015/day18.txt").unwrap();
 
         assert_eq!(y15d18(&contents, 100, 1), 1061);
         assert_eq!(y15d18(&contents, 100, 2), 1006);
+
+        let mut lights =
+            Automaton::parse(&contents, Rule::conway(), Topology::Toroidal);
+        lights.steps(2000);
+        assert_eq!(lights.generation(), 2000);
     }
 }
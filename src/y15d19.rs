@@ -20,10 +20,19 @@
 //! reading the prompt several more times and looking at some solutions on the
 //! subreddit I was able to figure out enough to be able to implement my
 //! solution.
+//!
+//! My first solution to part two randomly shuffled the replacements whenever
+//! it got stuck, which made it nondeterministic (and it would never finish
+//! if the molecule couldn't be made at all). Every replacement turns a single
+//! element into one or more elements, so the replacements are really a
+//! context-free grammar and the molecule is a sentence in that grammar. Now
+//! we parse the molecule with the
+//! [CYK algorithm](https://en.wikipedia.org/wiki/CYK_algorithm) (keeping the
+//! fewest replacements needed for every part of the molecule) which finds
+//! the minimum number of steps, tells us if the molecule can't be made, and
+//! gives us the actual replacements.
 
-use rand::seq::SliceRandom;
-use rand::thread_rng;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 /// The solution for part one of the day nineteen challenge.
 ///
@@ -85,30 +94,64 @@ pub fn y15d19p1(input: &str) -> u32 {
     set.len().try_into().unwrap()
 }
 
+/// A single replacement in the process of making the molecule: the element
+/// at the (byte) position in the molecule so far is replaced.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Replacement<'a> {
+    /// Where the element is in the molecule (before the replacement).
+    pub position: usize,
+    /// The element that gets replaced.
+    pub from: &'a str,
+    /// What the element gets replaced with.
+    pub to: &'a str,
+}
+
+/// Two adjacent parts of the molecule that combine into one symbol; the
+/// last production of each replacement carries its index (and costs a step)
+/// while the others are just the (shared) prefixes of longer replacements.
+struct Production {
+    left: usize,
+    right: usize,
+    result: usize,
+    rule: Option<usize>,
+}
+
+/// The replacement grammar turned into binary productions (and the
+/// replacements that turn one element into exactly one other element).
+struct Grammar<'a> {
+    rules: Vec<(&'a str, &'a str)>,
+    symbols: HashMap<&'a str, usize>,
+    count: usize,
+    productions: Vec<Production>,
+    by_left: Vec<Vec<usize>>,
+    prefix: Vec<bool>,
+    units: Vec<(usize, usize, usize)>,
+}
+
+/// The table of the CYK algorithm: for every part of the molecule and every
+/// symbol the fewest steps needed to make that part from that symbol and a
+/// pointer back to how we did it (a split and a production, or a single
+/// element replacement when the split is [`NONE`]).
+struct Table {
+    n: usize,
+    symbols: usize,
+    cost: Vec<u32>,
+    back: Vec<(u32, u32)>,
+}
+
+/// Marks a back pointer without a split (or without anything at all).
+const NONE: u32 = u32::MAX;
+
+/// Marks the symbols that haven't been made (yet) for a part of the molecule.
+const UNREACHABLE: u32 = u32::MAX;
+
 /// The solution for part two of the day nineteen challenge.
 ///
-/// This was very challenging and I had to rely on inspiration from the
-/// subreddit to figure out exactly how to solve this problem. As in part one
-/// we start by parsing the problem input string to get our replacements and
-/// molecule. Then we start a loop that we run until we reach `e`. The strategy
-/// here is to work backwards from the end molecule to the start instead of
-/// vice-versa as we'd need to implement a breadth-first-search or similar
-/// which would quickly explode in how many checks that we need to do and how
-/// long that it would take. Then we loop through all of the replacements
-/// keeping track of how many times we're able to make a substitution. When we
-/// can't make any more substitutions for that replacement (new == original)
-/// then we move on to the next one. If, after processing all of the
-/// replacements, the length of our "new" molecule after the replacements is
-/// the same as the length of our "old" molecule before the replacements (i.e.,
-/// we weren't able to do any replacements) then we reset our molecule
-/// replacement string back to the original, reset our substitution counter,
-/// shuffle the replacements array, and try again. This is the insight that I
-/// was able to get from the subreddit, it seems that many people took a
-/// similar approach, but as I understand it, it is technically possible to get
-/// a non-optimal solution however, it seems that based on many peoples' inputs
-/// there is actually only one path from `e` to the molecule so it should be
-/// okay. Once we've reached `e` the loop ends and we can return the number of
-/// substitutions that we made.
+/// We find the shortest way to make the molecule (see
+/// [`y15d19_replacements`]) and return how many steps it takes.
+///
+/// # Panics
+/// If the molecule can't be made from `e`.
 ///
 /// # Example
 /// ```rust
@@ -118,45 +161,350 @@ pub fn y15d19p1(input: &str) -> u32 {
 /// assert_eq!(y15d19p2(input), 2);
 /// ```
 pub fn y15d19p2(input: &str) -> u32 {
-    let mut rng = thread_rng();
-    let lines: Vec<_> = input.lines().collect();
-    let num_replacements = lines.len() - 2;
-    let molecule = lines[lines.len() - 1].to_string();
-    let mut reduced = molecule.clone();
-    let mut count = 0;
+    y15d19_replacements(input)
+        .expect("the molecule can't be made from e")
+        .len()
+        .try_into()
+        .unwrap()
+}
 
-    let mut replacements = Vec::new();
-    for (i, line) in lines.into_iter().enumerate() {
-        if i >= num_replacements {
-            break;
+/// Returns the fewest replacements that make the molecule starting from
+/// `e` in the order that they need to be applied, or `None` if there's no
+/// way to make the molecule.
+///
+/// We start by splitting the molecule into its elements (an uppercase
+/// letter followed by any lowercase letters) and the replacements into a
+/// grammar of binary productions: a replacement like `A => BCD` becomes
+/// `BC => BC` (a new symbol that costs nothing) and then `BC D => A` (which
+/// costs a step). Then we fill the CYK table from the shortest parts of the
+/// molecule to the whole thing: each single element can be made from itself
+/// for free, and every longer part can be made by splitting it in two and
+/// combining the symbols on either side with a production. After each part
+/// we also apply the replacements that turn one element into another until
+/// nothing gets any cheaper. If `e` can make the whole molecule then we walk
+/// the back pointers from there to list the replacements (always expanding
+/// the leftmost element first so that we know where it is).
+///
+/// # Example
+/// ```rust
+/// # use aoc::y15d19::{y15d19_replacements, Replacement};
+/// // probably read this from the input file...
+/// let input = "e => H\ne => O\nH => HO\nH => OH\nO => HH\n\nHOH";
+/// assert_eq!(
+///     y15d19_replacements(input),
+///     Some(vec![
+///         Replacement { position: 0, from: "e", to: "O" },
+///         Replacement { position: 0, from: "O", to: "HH" },
+///         Replacement { position: 1, from: "H", to: "OH" },
+///     ])
+/// );
+///
+/// let input = "e => H\nH => HH\n\nO";
+/// assert_eq!(y15d19_replacements(input), None);
+/// ```
+pub fn y15d19_replacements(input: &str) -> Option<Vec<Replacement<'_>>> {
+    // the replacements and the molecule are separated by a blank line
+    let mut lines = input.lines();
+    let grammar = Grammar::new(
+        lines
+            .by_ref()
+            .take_while(|line| !line.trim().is_empty())
+            .map(|line| line.split_once(" => ").unwrap())
+            .collect(),
+    );
+    let molecule = lines.find(|line| !line.trim().is_empty()).unwrap_or("");
+
+    let elements = elements(molecule.trim());
+    let mut positions = vec![0];
+    let mut tokens = Vec::new();
+    for element in &elements {
+        positions.push(positions.last().unwrap() + element.len());
+        tokens.push(*grammar.symbols.get(element)?);
+    }
+
+    let start = *grammar.symbols.get("e")?;
+    let table = Table::new(&grammar, &tokens);
+    if table.cost(0, tokens.len(), start) == UNREACHABLE {
+        return None;
+    }
+
+    let mut steps = Vec::new();
+    table.expand(&grammar, &positions, (0, tokens.len(), start), &mut steps);
+    Some(steps)
+}
+
+impl<'a> Grammar<'a> {
+    /// Builds the grammar from the replacements (sharing the symbols for
+    /// the common prefixes of the replacements).
+    fn new(rules: Vec<(&'a str, &'a str)>) -> Self {
+        let mut grammar = Self {
+            rules: Vec::new(),
+            symbols: HashMap::new(),
+            count: 0,
+            productions: Vec::new(),
+            by_left: Vec::new(),
+            prefix: Vec::new(),
+            units: Vec::new(),
+        };
+        let mut prefixes = HashMap::new();
+
+        for (rule, (from, to)) in rules.iter().enumerate() {
+            let result = grammar.symbol(from);
+            let to: Vec<_> = elements(to)
+                .into_iter()
+                .map(|e| grammar.symbol(e))
+                .collect();
+
+            if to.len() == 1 {
+                grammar.units.push((result, to[0], rule));
+                continue;
+            }
+
+            let mut left = to[0];
+            for i in 1..to.len() - 1 {
+                left = match prefixes.get(&to[..=i]) {
+                    Some(prefix) => *prefix,
+                    None => {
+                        let prefix = grammar.add_symbol(true);
+                        prefixes.insert(to[..=i].to_vec(), prefix);
+                        grammar.add_production(left, to[i], prefix, None);
+                        prefix
+                    }
+                };
+            }
+
+            grammar.add_production(left, to[to.len() - 1], result, Some(rule));
         }
 
-        let text: Vec<_> = line.split_whitespace().collect();
-        replacements.push((text[0], text[2]));
+        grammar.rules = rules;
+        grammar
+    }
+
+    /// Returns the symbol for an element (adding it if it's new).
+    fn symbol(&mut self, element: &'a str) -> usize {
+        match self.symbols.get(element) {
+            Some(symbol) => *symbol,
+            None => {
+                let symbol = self.add_symbol(false);
+                self.symbols.insert(element, symbol);
+                symbol
+            }
+        }
+    }
+
+    /// Adds a new symbol (either an element or the prefix of a
+    /// replacement).
+    fn add_symbol(&mut self, prefix: bool) -> usize {
+        self.by_left.push(Vec::new());
+        self.prefix.push(prefix);
+        self.count += 1;
+        self.count - 1
+    }
+
+    /// Adds a new production and indexes it by its left symbol.
+    fn add_production(
+        &mut self,
+        left: usize,
+        right: usize,
+        result: usize,
+        rule: Option<usize>,
+    ) {
+        self.by_left[left].push(self.productions.len());
+        self.productions.push(Production {
+            left,
+            right,
+            result,
+            rule,
+        });
     }
+}
+
+impl Table {
+    /// Fills the table for the given molecule (as symbols).
+    fn new(grammar: &Grammar, tokens: &[usize]) -> Self {
+        let n = tokens.len();
+
+        // the back pointers need to fit (with room for the marker)
+        assert!(
+            n < NONE as usize
+                && grammar.productions.len() < NONE as usize
+                && grammar.rules.len() < NONE as usize,
+            "molecule or replacements too big"
+        );
+
+        let mut table = Self {
+            n,
+            symbols: grammar.count,
+            cost: vec![UNREACHABLE; (n + 1) * (n + 1) * grammar.count],
+            back: vec![(NONE, NONE); (n + 1) * (n + 1) * grammar.count],
+        };
+        let mut present: Vec<Vec<usize>> = vec![Vec::new(); (n + 1) * (n + 1)];
+
+        for length in 1..=n {
+            for i in 0..=n - length {
+                let j = i + length;
+
+                if length == 1 {
+                    table.set(i, j, tokens[i], 0, (NONE, NONE));
+                }
+
+                for k in i + 1..j {
+                    for &left in &present[i * (n + 1) + k] {
+                        let left_cost = table.cost(i, k, left);
+
+                        for &p in &grammar.by_left[left] {
+                            let production = &grammar.productions[p];
+                            let right_cost = table.cost(k, j, production.right);
+                            if right_cost == UNREACHABLE {
+                                continue;
+                            }
+
+                            let cost = left_cost
+                                + right_cost
+                                + production.rule.is_some() as u32;
+                            if cost < table.cost(i, j, production.result) {
+                                table.set(
+                                    i,
+                                    j,
+                                    production.result,
+                                    cost,
+                                    (k as u32, p as u32),
+                                );
+                            }
+                        }
+                    }
+                }
 
-    while reduced != "e" {
-        let start_len = reduced.len();
+                let mut changed = true;
+                while changed {
+                    changed = false;
 
-        for (find, replace) in &replacements {
-            loop {
-                let new = reduced.replacen(replace, find, 1);
-                if new == reduced {
-                    break;
+                    for &(result, from, rule) in &grammar.units {
+                        let cost = table.cost(i, j, from);
+                        if cost != UNREACHABLE
+                            && cost + 1 < table.cost(i, j, result)
+                        {
+                            table.set(
+                                i,
+                                j,
+                                result,
+                                cost + 1,
+                                (NONE, rule as u32),
+                            );
+                            changed = true;
+                        }
+                    }
                 }
-                count += 1;
-                reduced = new;
+
+                present[i * (n + 1) + j] = (0..table.symbols)
+                    .filter(|symbol| table.cost(i, j, *symbol) != UNREACHABLE)
+                    .collect();
             }
         }
 
-        if start_len == reduced.len() {
-            replacements.shuffle(&mut rng);
-            reduced = molecule.clone();
-            count = 0;
+        table
+    }
+
+    /// Returns the index of a symbol for a part of the molecule.
+    fn index(&self, i: usize, j: usize, symbol: usize) -> usize {
+        (i * (self.n + 1) + j) * self.symbols + symbol
+    }
+
+    /// Returns the fewest steps to make a part of the molecule from a symbol.
+    fn cost(&self, i: usize, j: usize, symbol: usize) -> u32 {
+        self.cost[self.index(i, j, symbol)]
+    }
+
+    /// Records a (cheaper) way to make a part of the molecule from a symbol.
+    fn set(
+        &mut self,
+        i: usize,
+        j: usize,
+        symbol: usize,
+        cost: u32,
+        back: (u32, u32),
+    ) {
+        let index = self.index(i, j, symbol);
+        self.cost[index] = cost;
+        self.back[index] = back;
+    }
+
+    /// Adds the replacements that make a part of the molecule from a
+    /// symbol: first the replacement of the symbol itself and then the
+    /// replacements for each of the elements that it was replaced with.
+    fn expand<'a>(
+        &self,
+        grammar: &Grammar<'a>,
+        positions: &[usize],
+        (i, j, symbol): (usize, usize, usize),
+        steps: &mut Vec<Replacement<'a>>,
+    ) {
+        let (split, p) = self.back[self.index(i, j, symbol)];
+        if p == NONE {
+            return;
+        }
+
+        let (rule, children) = if split == NONE {
+            let (_, from, _) =
+                grammar.units.iter().find(|u| u.2 == p as usize).unwrap();
+            (p as usize, vec![(i, j, *from)])
+        } else {
+            let mut children = Vec::new();
+            self.children(grammar, (i, j, symbol), &mut children);
+            (grammar.productions[p as usize].rule.unwrap(), children)
+        };
+
+        let (from, to) = grammar.rules[rule];
+        steps.push(Replacement {
+            position: positions[i],
+            from,
+            to,
+        });
+
+        for child in children {
+            self.expand(grammar, positions, child, steps);
+        }
+    }
+
+    /// Collects the elements that a replacement made (by following the
+    /// productions for its prefixes back to the first element).
+    fn children(
+        &self,
+        grammar: &Grammar,
+        (i, j, symbol): (usize, usize, usize),
+        children: &mut Vec<(usize, usize, usize)>,
+    ) {
+        let (split, p) = self.back[self.index(i, j, symbol)];
+        let production = &grammar.productions[p as usize];
+        let k = split as usize;
+
+        if grammar.prefix[production.left] {
+            self.children(grammar, (i, k, production.left), children);
+        } else {
+            children.push((i, k, production.left));
+        }
+        children.push((k, j, production.right));
+    }
+}
+
+/// Splits a molecule into its elements (an uppercase letter followed by any
+/// lowercase letters, or a lone `e`).
+fn elements(molecule: &str) -> Vec<&str> {
+    let mut elements = Vec::new();
+    let mut start = 0;
+
+    for (i, c) in molecule.char_indices().skip(1) {
+        if c.is_uppercase() {
+            elements.push(&molecule[start..i]);
+            start = i;
         }
     }
 
-    count
+    if !molecule.is_empty() {
+        elements.push(&molecule[start..]);
+    }
+
+    elements
 }
 
 /// This function simply returns a substring of the given size from the given
@@ -193,5 +541,45 @@ mod tests {
 
         assert_eq!(y15d19p1(&contents), 509);
         assert_eq!(y15d19p2(&contents), 195);
+
+        let molecule = contents.lines().last().unwrap();
+        assert_eq!(apply(&y15d19_replacements(&contents).unwrap()), molecule);
+    }
+
+    #[test]
+    fn test_replacements() {
+        let input = "e => H\ne => O\nH => HO\nH => OH\nO => HH\n\nHOHOHO\n";
+        let steps = y15d19_replacements(input).unwrap();
+        assert_eq!(steps.len(), 6);
+        assert_eq!(apply(&steps), "HOHOHO");
+
+        let input = "e => NAl\nAl => ThF\nTh => Ca\nCa => Th\n\nCaF\n";
+        assert_eq!(y15d19_replacements(input), None);
+        let input = "e => NAl\nAl => ThF\nTh => Ca\nCa => Th\n\nNCaF\n";
+        assert_eq!(apply(&y15d19_replacements(input).unwrap()), "NCaF");
+        assert_eq!(y15d19p2(input), 3);
+        let input = "e => NAl\n\nNAr\n";
+        assert_eq!(y15d19_replacements(input), None);
+
+        let input = "e => H\r\ne => O\r\nH => HO\r\n\r\nHOO\r\n";
+        assert_eq!(apply(&y15d19_replacements(input).unwrap()), "HOO");
+
+        assert_eq!(elements("CRnCaSi"), vec!["C", "Rn", "Ca", "Si"]);
+        assert_eq!(elements("e"), vec!["e"]);
+    }
+
+    /// Applies the replacements starting from `e`.
+    fn apply(steps: &[Replacement]) -> String {
+        let mut molecule = String::from("e");
+
+        for step in steps {
+            assert!(molecule[step.position..].starts_with(step.from));
+            molecule.replace_range(
+                step.position..step.position + step.from.len(),
+                step.to,
+            );
+        }
+
+        molecule
     }
 }
@@ -83,6 +83,7 @@ pub fn y22d01(input: &str, top: u32) -> u32 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::generate;
     use std::fs;
 
     #[test]
@@ -104,6 +105,22 @@ mod tests {
         assert_eq!(y22d01(input, 1), 4);
     }
 
+    #[test]
+    fn properties() {
+        for input in generate::cases("y22d01", 100, 20) {
+            let mut elves: Vec<u32> = input
+                .split("\n\n")
+                .map(|elf| elf.lines().map(|l| l.parse::<u32>().unwrap()).sum())
+                .collect();
+            elves.sort();
+            elves.reverse();
+
+            assert_eq!(y22d01(&input, 1), elves[0]);
+            assert_eq!(y22d01(&input, 3), elves[0] + elves[1] + elves[2]);
+            assert!(y22d01(&input, 3) >= y22d01(&input, 1));
+        }
+    }
+
     #[test]
     fn the_solution() {
         let contents = fs::read_to_string("input/2022/day01.txt").unwrap();
@@ -80,6 +80,7 @@ pub fn y22d02(input: &str, part: u32) -> u32 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::generate;
     use std::fs;
 
     #[test]
@@ -93,6 +94,31 @@ mod tests {
         assert_eq!(y22d02(input, 2), 15);
     }
 
+    #[test]
+    fn properties() {
+        for input in generate::cases("y22d02", 100, 20) {
+            let mut totals = (0, 0);
+
+            // score every round from the rules instead of the lookup table
+            for line in input.lines() {
+                let bytes = line.as_bytes();
+                let theirs = (bytes[0] - b'A') as u32;
+                let second = (bytes[2] - b'X') as u32;
+
+                // 0 for a loss, 1 for a draw, and 2 for a win
+                let outcome = (second + 4 - theirs) % 3;
+                totals.0 += second + 1 + outcome * 3;
+                let ours = (theirs + second + 2) % 3;
+                totals.1 += ours + 1 + second * 3;
+            }
+
+            let rounds = input.lines().count() as u32;
+            assert_eq!(y22d02(&input, 1), totals.0);
+            assert_eq!(y22d02(&input, 2), totals.1);
+            assert!((rounds..=rounds * 9).contains(&totals.0));
+        }
+    }
+
     #[test]
     fn the_solution() {
         let contents = fs::read_to_string("input/2022/day02.txt").unwrap();
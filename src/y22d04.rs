@@ -89,6 +89,8 @@ pub fn y22d04(input: &str, part: u32) -> u32 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::generate;
+    use std::collections::HashSet;
     use std::fs;
 
     #[test]
@@ -102,6 +104,43 @@ mod tests {
         assert_eq!(y22d04(input, 2), 3);
     }
 
+    #[test]
+    fn properties() {
+        for input in generate::cases("y22d04", 100, 20) {
+            let contained = y22d04(&input, 1);
+            let overlapping = y22d04(&input, 2);
+
+            // a range that contains the other also overlaps with it
+            assert!(overlapping >= contained);
+            assert!(overlapping as usize <= input.lines().count());
+
+            // brute force: compare the actual sets of sections
+            let (mut expected_contained, mut expected_overlapping) = (0, 0);
+            for line in input.lines() {
+                let sections: Vec<HashSet<u32>> = line
+                    .split(',')
+                    .map(|range| {
+                        let (a, b) = range.split_once('-').unwrap();
+                        (a.parse().unwrap()..=b.parse().unwrap()).collect()
+                    })
+                    .collect();
+
+                if sections[0].is_subset(&sections[1])
+                    || sections[1].is_subset(&sections[0])
+                {
+                    expected_contained += 1;
+                }
+
+                if !sections[0].is_disjoint(&sections[1]) {
+                    expected_overlapping += 1;
+                }
+            }
+
+            assert_eq!(contained, expected_contained);
+            assert_eq!(overlapping, expected_overlapping);
+        }
+    }
+
     #[test]
     fn the_solution() {
         let contents = fs::read_to_string("input/2022/day04.txt").unwrap();
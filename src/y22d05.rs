@@ -27,25 +27,80 @@
 //! from one stack to another, so we just move them one at a time into a
 //! temporary vector, reverse the order of that and then play them back into
 //! the destination vector.
+//!
+//! The two ways of moving crates are now [`Crane`]s of their own (so that
+//! other kinds of cranes can be plugged in, e.g., [`Limited`]) and a
+//! [`Dock`] can replay the moves one at a time with any of them and draw the
+//! stacks back out in the same format as the input.
+
+use std::fmt::Write;
+
+/// The crates in each stack from the bottom of the stack to the top.
+pub type Stacks = Vec<Vec<char>>;
+
+/// A single instruction from the input (with the stacks counted from zero).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Move {
+    /// How many crates to move.
+    pub count: usize,
+    /// The stack to take them from.
+    pub from: usize,
+    /// The stack to put them on.
+    pub to: usize,
+}
+
+/// Something that can carry out a [`Move`].
+pub trait Crane {
+    /// Moves the crates between the stacks.
+    ///
+    /// # Panics
+    /// If there aren't enough crates on the stack to move.
+    fn apply(&self, stacks: &mut Stacks, step: Move);
+}
+
+/// The crane from part one: it moves crates one at a time (so they end up
+/// in reverse order).
+#[derive(Clone, Copy, Debug)]
+pub struct CrateMover9000;
+
+/// The crane from part two: it moves all of the crates at once (so they keep
+/// their order).
+#[derive(Clone, Copy, Debug)]
+pub struct CrateMover9001;
+
+/// A crane that can only lift so many crates at once so bigger moves are
+/// split up into several smaller moves (each carried out by the inner crane).
+#[derive(Clone, Copy, Debug)]
+pub struct Limited<C: Crane> {
+    /// The crane that does the lifting.
+    pub crane: C,
+    /// The most crates that can be lifted at once.
+    pub capacity: usize,
+}
+
+/// The starting stacks and the moves to make from the input.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Dock {
+    /// The stacks before any of the moves.
+    pub stacks: Stacks,
+    /// The moves to make, in order.
+    pub moves: Vec<Move>,
+}
+
+/// An iterator over the stacks after each move (see [`Dock::replay`]).
+pub struct Replay<'a> {
+    crane: &'a dyn Crane,
+    stacks: Stacks,
+    moves: std::slice::Iter<'a, Move>,
+}
 
 /// The solution for the day five challenge.
 ///
 /// As the arguments correspond to the input text and the method of operation:
-/// the reordering of crates (part `1`) or the maintaining of the order (part
-/// `2`).
-///
-/// # Parsing the Initial State
-/// To parse the initial state we first need to get all of the lines before
-/// the first empty line which we can treat as the delimiter between the state
-/// representation and the moves that we will need to make. The last non-empty
-/// line of the state representation gives us a numbering of the columns
-/// (which we can use to determine the total number of columns) that needs to
-/// be discarded as it is not actually a part of the internal state
-/// representation. Once we have the lines that represent the state we can
-/// loop through them (in reverse order so that the items on the bottom of the
-/// stacks are the first items in the vector) checking each column to see if
-/// there is an element defined there or not (e.g, empty space or end-of-input)
-/// and if there is then add it to the appropriate column vector.
+/// the reordering of crates (part `1`, a [`CrateMover9000`]) or the
+/// maintaining of the order (part `2`, a [`CrateMover9001`]). We parse the
+/// [`Dock`], make all of the moves, and return the crates on top of each
+/// stack (or a space for empty stacks).
 ///
 /// # Example
 /// ```rust
@@ -66,64 +121,181 @@
 /// assert_eq!(y22d05(&input, 2), "MCD");
 /// ```
 pub fn y22d05(input: &str, part: u32) -> String {
-    let lines: Vec<_> = input.lines().collect();
+    let dock = Dock::parse(input);
+    let stacks = if part == 1 {
+        dock.run(&CrateMover9000)
+    } else {
+        dock.run(&CrateMover9001)
+    };
 
-    let mut state = parse_initial_state(&lines);
-    let mut in_moves = false; // track if we've hit the state/moves separator
-    let mut output = String::new();
+    tops(&stacks)
+}
 
-    for line in lines {
-        if in_moves {
-            // "move X from Y to Z": X is index 1, Y is index 3, Z is index 5
-            let text: Vec<&str> = line.split_whitespace().collect();
-            let how_many_to_move = text[1].parse().unwrap();
-            let from_index: u32 = text[3].parse().unwrap();
-            let to_index: u32 = text[5].parse().unwrap();
-
-            if part == 1 {
-                for _ in 0..how_many_to_move {
-                    let from: &mut Vec<String> =
-                        &mut state[(from_index - 1) as usize];
-                    let to_move = from.pop().unwrap();
-                    let to: &mut Vec<String> =
-                        &mut state[(to_index - 1) as usize];
-                    to.push(to_move);
-                }
-            } else {
-                let mut holding: Vec<String> = Vec::new();
-                let from: &mut Vec<String> =
-                    &mut state[(from_index - 1) as usize];
-                for _ in 0..how_many_to_move {
-                    let to_move = from.pop().unwrap();
-                    holding.push(to_move);
-                }
+/// Returns the crate on top of each stack (or a space if the stack is
+/// empty).
+pub fn tops(stacks: &Stacks) -> String {
+    stacks
+        .iter()
+        .map(|stack| stack.last().copied().unwrap_or(' '))
+        .collect()
+}
+
+impl Crane for CrateMover9000 {
+    fn apply(&self, stacks: &mut Stacks, step: Move) {
+        for _ in 0..step.count {
+            let item = stacks[step.from].pop().expect("not enough crates");
+            stacks[step.to].push(item);
+        }
+    }
+}
+
+impl Crane for CrateMover9001 {
+    fn apply(&self, stacks: &mut Stacks, step: Move) {
+        let from = &mut stacks[step.from];
+        let split = from
+            .len()
+            .checked_sub(step.count)
+            .expect("not enough crates");
+        let holding = from.split_off(split);
+        stacks[step.to].extend(holding);
+    }
+}
+
+impl<C: Crane> Crane for Limited<C> {
+    fn apply(&self, stacks: &mut Stacks, step: Move) {
+        let mut left = step.count;
+        while left > 0 {
+            let count = left.min(self.capacity.max(1));
+            self.crane.apply(stacks, Move { count, ..step });
+            left -= count;
+        }
+    }
+}
 
-                let to: &mut Vec<String> = &mut state[(to_index - 1) as usize];
-                for to_move in holding.into_iter().rev() {
-                    to.push(to_move);
+impl Dock {
+    /// Parses the input: the drawing of the stacks, an empty line, and then
+    /// the moves.
+    ///
+    /// # Parsing the Initial State
+    /// To parse the initial state we first need to get all of the lines
+    /// before the first empty line which we can treat as the delimiter
+    /// between the state representation and the moves that we will need to
+    /// make. The last non-empty line of the state representation gives us a
+    /// numbering of the columns (which we can use to determine the total
+    /// number of columns) that needs to be discarded as it is not actually a
+    /// part of the internal state representation. Once we have the lines that
+    /// represent the state we can loop through them (in reverse order so that
+    /// the items on the bottom of the stacks are the first items in the
+    /// vector) checking each column to see if there is an element defined
+    /// there or not (e.g, empty space or end-of-input) and if there is then
+    /// add it to the appropriate column vector.
+    ///
+    /// # Panics
+    /// If the input is malformed.
+    pub fn parse(input: &str) -> Dock {
+        let lines: Vec<_> = input.lines().collect();
+        let stacks = parse_initial_state(&lines);
+
+        let moves = lines
+            .iter()
+            .skip_while(|line| !line.is_empty())
+            .filter(|line| !line.is_empty())
+            .map(|line| {
+                // "move X from Y to Z": X is index 1, Y is index 3, Z is
+                // index 5
+                let text: Vec<&str> = line.split_whitespace().collect();
+                let stack = |text: &str| text.parse::<usize>().unwrap() - 1;
+                Move {
+                    count: text[1].parse().unwrap(),
+                    from: stack(text[3]),
+                    to: stack(text[5]),
                 }
-            }
-        } else if line.is_empty() {
-            // we've hit the empty line, switch "modes"
-            in_moves = true;
-        } // else continue
+            })
+            .collect();
+
+        Dock { stacks, moves }
     }
 
-    for mut stack in state {
-        output += match &stack.pop() {
-            Some(item) => item,
-            None => " ",
-        };
+    /// Returns an iterator over the stacks after each of the moves made by
+    /// the given crane.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use aoc::y22d05::{tops, CrateMover9001, Dock};
+    /// let input = "[A]\n[B] [C]\n 1   2\n\nmove 2 from 1 to 2\nmove 1 from 2 to 1\n";
+    /// let dock = Dock::parse(input);
+    /// let states: Vec<_> = dock.replay(&CrateMover9001).map(|s| tops(&s)).collect();
+    /// assert_eq!(states, vec![" A", "AB"]);
+    /// ```
+    pub fn replay<'a>(&'a self, crane: &'a dyn Crane) -> Replay<'a> {
+        Replay {
+            crane,
+            stacks: self.stacks.clone(),
+            moves: self.moves.iter(),
+        }
     }
 
-    output
+    /// Returns the stacks after all of the moves made by the given crane.
+    /// Unlike [`Dock::replay`] the stacks are only copied once and then moved
+    /// around in place.
+    pub fn run(&self, crane: &dyn Crane) -> Stacks {
+        let mut stacks = self.stacks.clone();
+        for step in &self.moves {
+            crane.apply(&mut stacks, *step);
+        }
+        stacks
+    }
+}
+
+impl Iterator for Replay<'_> {
+    type Item = Stacks;
+
+    fn next(&mut self) -> Option<Stacks> {
+        let step = self.moves.next()?;
+        self.crane.apply(&mut self.stacks, *step);
+        Some(self.stacks.clone())
+    }
+}
+
+/// Draws the stacks the same way as the input: a row for each level of
+/// crates (the highest first) and then a row with the number of each stack.
+/// Trailing whitespace is left off.
+///
+/// # Example
+/// ```rust
+/// # use aoc::y22d05::render;
+/// let stacks = vec![vec!['Z', 'N'], vec!['M', 'C', 'D'], vec!['P']];
+/// assert_eq!(
+///     render(&stacks),
+///     "    [D]\n[N] [C]\n[Z] [M] [P]\n 1   2   3\n"
+/// );
+/// ```
+pub fn render(stacks: &Stacks) -> String {
+    let height = stacks.iter().map(Vec::len).max().unwrap_or(0);
+    let mut drawing = String::new();
+
+    for level in (0..height).rev() {
+        let row: Vec<_> = stacks
+            .iter()
+            .map(|stack| match stack.get(level) {
+                Some(item) => format!("[{}]", item),
+                None => "   ".to_string(),
+            })
+            .collect();
+        writeln!(drawing, "{}", row.join(" ").trim_end()).unwrap();
+    }
+
+    let numbers: Vec<_> =
+        (1..=stacks.len()).map(|i| format!(" {} ", i)).collect();
+    writeln!(drawing, "{}", numbers.join(" ").trim_end()).unwrap();
+    drawing
 }
 
-/// The process of processing the initial state is described in [`y22d05`]
-/// under the "Parsing the Initial State" header.
-fn parse_initial_state(lines: &Vec<&str>) -> Vec<Vec<String>> {
+/// The process of processing the initial state is described in
+/// [`Dock::parse`] under the "Parsing the Initial State" header.
+fn parse_initial_state(lines: &[&str]) -> Stacks {
     let mut state_lines: Vec<&str> = Vec::new();
-    let mut state: Vec<Vec<String>> = Vec::new();
+    let mut state: Stacks = Vec::new();
 
     for line in lines {
         // once we find the empty line we _remove_ (pop) the previous line
@@ -152,6 +324,8 @@ fn parse_initial_state(lines: &Vec<&str>) -> Vec<Vec<String>> {
     // (meaning that we start at the _bottom_ of the columns so that we can
     // add each element to the column vector
     for line in state_lines.iter().rev() {
+        let line: Vec<_> = line.chars().collect();
+
         // whitespace doesn't extend to the end of each line for all columns
         // so we need to calculate how many actual columns are in a given line
         //
@@ -166,8 +340,8 @@ fn parse_initial_state(lines: &Vec<&str>) -> Vec<Vec<String>> {
         for (i, column) in
             state.iter_mut().enumerate().take(number_of_columns_in_line)
         {
-            if line.chars().nth(i * 4).unwrap() == '[' {
-                column.push(line.chars().nth(i * 4 + 1).unwrap().to_string());
+            if line[i * 4] == '[' {
+                column.push(line[i * 4 + 1]);
             }
         }
     }
@@ -191,20 +365,73 @@ mod tests {
             "we don't care about this...\n",
             "or this...\n",
         );
-        let lines = input.lines().collect();
+        let lines: Vec<_> = input.lines().collect();
 
         assert_eq!(
             parse_initial_state(&lines),
             vec![
-                vec!["G", "C", "A"],
-                vec!["H", "D"],
-                vec!["I", "E", "B"],
-                vec!["J", "F"],
-                vec!["K"],
+                vec!['G', 'C', 'A'],
+                vec!['H', 'D'],
+                vec!['I', 'E', 'B'],
+                vec!['J', 'F'],
+                vec!['K'],
             ]
         );
     }
 
+    #[test]
+    fn test_cranes() {
+        let input = concat!(
+            "[A]\n",
+            "[B]\n",
+            "[C]\n",
+            "[D]\n",
+            "[E] [F]\n",
+            " 1   2\n",
+            "\n",
+            "move 5 from 1 to 2\n",
+        );
+        let dock = Dock::parse(input);
+
+        let run =
+            |crane: &dyn Crane| dock.run(crane)[1].iter().collect::<String>();
+        assert_eq!(run(&CrateMover9000), "FABCDE");
+        assert_eq!(run(&CrateMover9001), "FEDCBA");
+        assert_eq!(
+            run(&Limited {
+                crane: CrateMover9001,
+                capacity: 2
+            }),
+            "FBADCE"
+        );
+        assert_eq!(
+            run(&Limited {
+                crane: CrateMover9001,
+                capacity: 1
+            }),
+            run(&CrateMover9000)
+        );
+
+        let states: Vec<_> = dock.replay(&CrateMover9000).collect();
+        assert_eq!(states.len(), 1);
+        assert!(states[0][0].is_empty());
+        assert_eq!(states.last(), Some(&dock.run(&CrateMover9000)));
+    }
+
+    #[test]
+    fn test_render() {
+        let contents = fs::read_to_string("input/2022/day05.txt").unwrap();
+        let (diagram, _) = contents.split_once("\n\n").unwrap();
+        let dock = Dock::parse(&contents);
+        assert_eq!(render(&dock.stacks), format!("{}\n", diagram));
+
+        // every intermediate state draws and parses back the same
+        for stacks in dock.replay(&CrateMover9001) {
+            let drawing = format!("{}\n", render(&stacks));
+            assert_eq!(Dock::parse(&drawing).stacks, stacks);
+        }
+    }
+
     #[test]
     fn it_works() {
         let input = concat!(
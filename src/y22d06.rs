@@ -23,15 +23,45 @@
 //! then all elements are distinct and we've found the "start" and return the
 //! current counter plus the size as an offset to account for the `size`
 //! characters at the beginning of the string.
+//!
+//! Building a new set for every window does `size` times more work than it
+//! needs to though, so now we slide a single window along instead and keep a
+//! count of each byte in it along with how many bytes are in there more than
+//! once. Each new byte only changes two counts (the one coming in and the one
+//! falling out) so finding markers is linear no matter how big they are, and
+//! since we only ever look at one byte at a time it works just as well on a
+//! stream as it does on a string.
+
+/// Finds markers in a stream of bytes, one byte at a time.
+///
+/// # Example
+/// ```rust
+/// # use aoc::y22d06::Detector;
+/// let mut detector = Detector::new(3);
+/// let found: Vec<_> = b"aabcb".iter().map(|b| detector.push(*b)).collect();
+/// assert_eq!(found, vec![None, None, None, Some(4), None]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Detector {
+    window: Vec<u8>,
+    counts: [usize; 256],
+    repeated: usize,
+    position: usize,
+}
 
-use std::collections::HashSet;
+/// An iterator over the positions of every marker in a stream of bytes (see
+/// [`markers`]).
+#[derive(Clone, Debug)]
+pub struct Markers<I> {
+    bytes: I,
+    detector: Detector,
+}
 
 /// The solution for the day six challenge.
 ///
-/// Given the input as a string it splits it into characters and then evaluates
-/// each window of `size` characters while maintaining a counter of the current
-/// window. Once all of the elements are distinct then we return the counter
-/// plus the `size` offset.
+/// Given the input as a string we feed each character into a [`Detector`] and
+/// return the position just after the first window of `size` distinct
+/// characters (if there is one).
 ///
 /// # Example
 /// ```rust
@@ -40,24 +70,107 @@ use std::collections::HashSet;
 /// assert_eq!(y22d06(&input, 4), Some(8));
 /// ```
 pub fn y22d06(input: &str, size: usize) -> Option<u32> {
-    let chars: Vec<_> = input.trim().chars().collect();
-    for (i, window) in chars.windows(size).enumerate() {
-        let mut set = HashSet::new();
-        for c in window {
-            set.insert(c);
+    markers(input.trim().bytes(), size)
+        .next()
+        .map(|position| position as u32)
+}
+
+/// Returns the position just after every marker of `size` distinct bytes,
+/// overlapping ones included.
+///
+/// Any iterator of bytes works so to read from something like standard input
+/// just pass it `reader.bytes().map_while(Result::ok)`. Every byte counts
+/// (even a trailing newline) so trim beforehand if that matters.
+///
+/// # Panics
+/// If `size` is zero.
+///
+/// # Example
+/// ```rust
+/// # use aoc::y22d06::markers;
+/// # use std::io::{Cursor, Read};
+/// let reader = Cursor::new("aabcbd");
+/// let found: Vec<_> = markers(reader.bytes().map_while(Result::ok), 3).collect();
+/// assert_eq!(found, vec![4, 6]);
+/// ```
+pub fn markers<I: IntoIterator<Item = u8>>(
+    bytes: I,
+    size: usize,
+) -> Markers<I::IntoIter> {
+    Markers {
+        bytes: bytes.into_iter(),
+        detector: Detector::new(size),
+    }
+}
+
+impl Detector {
+    /// Creates a detector for markers of `size` distinct bytes.
+    ///
+    /// # Panics
+    /// If `size` is zero.
+    pub fn new(size: usize) -> Detector {
+        assert!(size > 0, "markers need at least one byte");
+
+        Detector {
+            window: vec![0; size],
+            counts: [0; 256],
+            repeated: 0,
+            position: 0,
         }
-        if set.len() == size {
-            return Some((i + size) as u32);
+    }
+
+    /// Adds the next byte of the stream and returns how many bytes have been
+    /// seen if the last `size` of them are all distinct.
+    pub fn push(&mut self, byte: u8) -> Option<usize> {
+        let size = self.window.len();
+        let slot = self.position % size;
+
+        if self.position >= size {
+            let old = self.window[slot] as usize;
+            self.counts[old] -= 1;
+            if self.counts[old] == 1 {
+                self.repeated -= 1;
+            }
+        }
+
+        self.window[slot] = byte;
+        self.counts[byte as usize] += 1;
+        if self.counts[byte as usize] == 2 {
+            self.repeated += 1;
+        }
+        self.position += 1;
+
+        if self.position >= size && self.repeated == 0 {
+            Some(self.position)
+        } else {
+            None
         }
     }
 
-    // we didn't find any distinct sequences of "size" length
-    None
+    /// Returns how many bytes have been seen so far.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+}
+
+impl<I: Iterator<Item = u8>> Iterator for Markers<I> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        for byte in self.bytes.by_ref() {
+            if let Some(position) = self.detector.push(byte) {
+                return Some(position);
+            }
+        }
+
+        None
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::generate;
     use std::fs;
 
     #[test]
@@ -82,6 +195,64 @@ mod tests {
         assert_eq!(y22d06(input, 14), Some(19));
     }
 
+    #[test]
+    fn properties() {
+        for input in generate::cases("y22d06", 200, 30) {
+            let packet = y22d06(&input, 4);
+            let message = y22d06(&input, 14);
+
+            // the last four characters of a start-of-message marker are
+            // also a start-of-packet marker
+            if let Some(message) = message {
+                assert!(packet.unwrap() <= message);
+            }
+
+            // brute force: compare every window with every other character
+            let chars: Vec<_> = input.trim().chars().collect();
+            for (size, marker) in [(4, packet), (14, message)] {
+                let expected = (size..=chars.len())
+                    .find(|&end| {
+                        let window = &chars[end - size..end];
+                        window.iter().enumerate().all(|(i, a)| {
+                            window.iter().skip(i + 1).all(|b| a != b)
+                        })
+                    })
+                    .map(|end| end as u32);
+                assert_eq!(marker, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_markers() {
+        // every marker, overlapping or not, including ones bigger than the
+        // alphabet that can never be found
+        let input = "mjqjpqmgbljsphdztnvjfqwrcgsmlb";
+        let all: Vec<_> = markers(input.bytes(), 4).collect();
+        assert_eq!(all[..3], [7, 8, 9]);
+        assert_eq!(markers(input.bytes(), 1).count(), input.len());
+        assert_eq!(markers("ab".bytes(), 3).next(), None);
+        assert_eq!(markers((0..=255).cycle().take(1000), 256).count(), 745);
+
+        // compare every marker against the brute force for random inputs
+        for input in generate::cases("y22d06", 50, 30) {
+            let bytes = input.trim().as_bytes();
+            for size in [1, 2, 4, 14] {
+                let expected: Vec<_> = (size..=bytes.len())
+                    .filter(|&end| {
+                        let window = &bytes[end - size..end];
+                        window.iter().enumerate().all(|(i, a)| {
+                            window.iter().skip(i + 1).all(|b| a != b)
+                        })
+                    })
+                    .collect();
+                let found: Vec<_> =
+                    markers(bytes.iter().copied(), size).collect();
+                assert_eq!(found, expected);
+            }
+        }
+    }
+
     #[test]
     fn the_solution() {
         let contents = fs::read_to_string("input/2022/day06.txt").unwrap();
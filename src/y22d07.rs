@@ -23,47 +23,66 @@
 //! [`std::collections::HashMap`] of full directory paths and then as it sees
 //! each file it adds the size to each of the directory components stored in
 //! the hash all the way up to the root (`/`) entry.
+//!
+//! That approach couldn't cope with transcripts that list a directory twice
+//! or `cd` through more than one directory at once though, so it's been
+//! replaced with a proper tree after all. To keep the borrow checker happy
+//! all of the directories and files live in one vector (an arena) and refer
+//! to their parents and children by index.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+/// The total size of the disk (provided by the prompt).
+const DISK: u64 = 70000000;
+
+/// How much free space the update needs (provided by the prompt).
+const UPDATE: u64 = 30000000;
+
+/// The largest directory that counts in part one (provided by the prompt).
+const SMALL: u64 = 100000;
+
+/// The index of a directory or file in a [`FileSystem`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NodeId(usize);
+
+/// Whether a node is a directory (and what's in it) or a file (and how big
+/// it is).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Kind {
+    /// A directory and its children by name.
+    Directory(BTreeMap<String, NodeId>),
+    /// A file and its size.
+    File(u64),
+}
+
+/// A directory or a file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Node {
+    /// The name of the directory or file (`/` for the root).
+    pub name: String,
+    /// The directory that contains it (`None` for the root).
+    pub parent: Option<NodeId>,
+    /// What it is.
+    pub kind: Kind,
+}
 
-use std::cmp::Reverse;
-use std::collections::{BinaryHeap, HashMap};
-use std::path::PathBuf;
+/// A tree of directories and files.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FileSystem {
+    nodes: Vec<Node>,
+}
 
 /// The solution for the day seven challenge.
 ///
 /// Given the usual parameters of the input as a string and which part `1` or
-/// `2` we start by parsing the input to build a representation of the total
-/// file size contained in each directory as described above.
+/// `2` we start by building the [`FileSystem`] from the terminal transcript
+/// and working out the (cumulative) size of every directory.
 ///
-/// We start by analyzing each line (skipping the first line which we assume
-/// will be changing into the root directory which we've used to setup the
-/// initial state i.e., set the current path to the root and initialize its
-/// size to zero). If we have a change directory command then simply we update
-/// the current path marker. If we have a list directory command then we
-/// insert the path into a list of directories that we have already visited.
-/// We do this to ensure that we don't double count any directories if we list
-/// them more than once. The provided input doesn't actually do this but I
-/// wasn't sure if that was the case or not when I started and wanted a guard.
-/// In theory it's possible to remove it now, but it doesn't hurt to keep
-/// around (just increases the memory requirements).
-///
-/// Otherwise, we can assume that we're reading the results from listing the
-/// directory. Other directories start with the string `dir` which we can use
-/// to then initialize those directories with size `0` in our sizes map. If
-/// we don't have a directory then we must have a file so we add its size to
-/// the current directory and then explode the directory and add its size to
-/// every component back up to the root which gives us the desired cumulative
-/// size for each directory part.
-///
-/// After computing the (cumulative) size of each directory we can respond to
-/// the challenge prompt. In part one we just loop through all of the
-/// directories and if their total size is greater than `1000000` (provided by
-/// the prompt) then we add the size to the total. In part two we instead
-/// compute the free space (total space minus the total size of the root
-/// directory) and then loop through our directories. If deleting the directory
-/// would give us enough free space (both total space and required space are
-/// provided by the prompt) then we add the directory size to a min-heap.
-/// Finally, we can simply pop the smallest directory that satisfies the
-/// minimum space requirements.
+/// In part one we just add up the size of every directory that is at most
+/// `100000` (provided by the prompt). In part two we find the smallest
+/// directory that we could delete to free up enough space for the update (see
+/// [`FileSystem::smallest_to_free`]).
 ///
 /// # Example
 /// ```rust
@@ -87,87 +106,352 @@ use std::path::PathBuf;
 /// assert_eq!(y22d07(&input, 2), 10000000);
 /// ```
 pub fn y22d07(input: &str, part: u32) -> u32 {
-    let lines: Vec<_> = input.lines().collect();
+    let fs = FileSystem::parse(input);
 
-    // we assume that the first line/command is to change into the root
-    let mut current_path = PathBuf::from("/");
-    let mut sizes = HashMap::new();
-    let mut visited = Vec::new();
-    sizes.insert(current_path.to_str().unwrap().to_string(), 0);
+    let answer = if part == 1 {
+        let sizes = fs.sizes();
+        fs.directories()
+            .map(|id| sizes[id.0])
+            .filter(|size| *size <= SMALL)
+            .sum()
+    } else {
+        fs.smallest_to_free(DISK, UPDATE).unwrap().1
+    };
 
-    // enforce our assumption above...
-    if lines[0] != "$ cd /" || lines[1] != "$ ls" {
-        panic!("Didn't get expected starting input!");
-    }
+    answer.try_into().unwrap()
+}
 
-    for line in lines.iter().skip(1) {
-        let current_path_string = current_path.to_str().unwrap().to_string();
+impl FileSystem {
+    /// Creates a file system with nothing but the root directory.
+    pub fn new() -> FileSystem {
+        FileSystem {
+            nodes: vec![Node {
+                name: "/".to_string(),
+                parent: None,
+                kind: Kind::Directory(BTreeMap::new()),
+            }],
+        }
+    }
 
-        if line.starts_with("$ cd ") {
-            let cmd: Vec<&str> = line.split_whitespace().collect();
-            let dir = cmd[2];
+    /// Builds a file system from a terminal transcript.
+    ///
+    /// `cd` can take any path (absolute, relative, with `..` and `.`) and
+    /// the directories that it goes through don't need to have been listed
+    /// first. Listing a directory again just confirms what we already know
+    /// about it.
+    ///
+    /// # Panics
+    /// If the transcript has a line that isn't a command or the output
+    /// of `ls`, or if something is listed as both a file and a directory.
+    pub fn parse(transcript: &str) -> FileSystem {
+        let mut fs = FileSystem::new();
+        let mut current = fs.root();
 
-            if dir == ".." {
-                current_path.pop();
-            } else {
-                current_path.push(dir);
-            }
-        } else if line == &"$ ls" {
-            if visited.contains(&current_path_string) {
-                panic!("We already visited this directory");
-            } else {
-                visited.push(current_path_string);
-            }
-        } else {
+        for line in transcript.lines().filter(|line| !line.trim().is_empty()) {
             let parts: Vec<&str> = line.split_whitespace().collect();
-            let name = parts[1];
-
-            if parts[0] == "dir" {
-                let fulldir =
-                    current_path.join(name).to_str().unwrap().to_string();
-                let status = sizes.insert(fulldir, 0);
-                if status.is_some() {
-                    panic!("Tried to insert a directory that already exists");
+
+            match parts[..] {
+                ["$", "cd", path] => current = fs.mkdir_all(current, path),
+                ["$", "ls"] => {}
+                ["dir", name] => {
+                    fs.mkdir(current, name);
                 }
-            } else {
-                let fsize: u32 = parts[0].parse().unwrap();
-
-                sizes
-                    .entry(current_path_string)
-                    .and_modify(|size| *size += fsize);
-                let mut paths = current_path.clone();
-                while paths.pop() {
-                    let path_str = paths.to_str().unwrap().to_string();
-                    sizes.entry(path_str).and_modify(|size| *size += fsize);
+                [size, name] => {
+                    let size = size.parse().expect("invalid file size");
+                    fs.add_file(current, name, size);
                 }
+                _ => panic!("unexpected line: {}", line),
             }
         }
+
+        fs
+    }
+
+    /// Returns the root directory.
+    pub fn root(&self) -> NodeId {
+        NodeId(0)
     }
 
-    if part == 1 {
-        let mut total = 0;
+    /// Returns the directory or file with the given identifier.
+    pub fn node(&self, id: NodeId) -> &Node {
+        &self.nodes[id.0]
+    }
 
-        for (_dir, size) in sizes {
-            if size <= 100000 {
-                total += size;
+    /// Returns the child of the directory with the given name (if there is
+    /// one).
+    pub fn child(&self, id: NodeId, name: &str) -> Option<NodeId> {
+        match &self.node(id).kind {
+            Kind::Directory(children) => children.get(name).copied(),
+            Kind::File(_) => None,
+        }
+    }
+
+    /// Follows the path from the given directory (or from the root if the
+    /// path starts with `/`) and returns where it ends up.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use aoc::y22d07::FileSystem;
+    /// let fs = FileSystem::parse("$ cd /a/b\n$ ls\n1 c\n");
+    /// let b = fs.resolve(fs.root(), "a/./b").unwrap();
+    /// assert_eq!(fs.path(b), "/a/b");
+    /// assert_eq!(fs.resolve(b, "../../a"), fs.resolve(fs.root(), "/a"));
+    /// assert_eq!(fs.resolve(b, "c/d"), None);
+    /// ```
+    pub fn resolve(&self, from: NodeId, path: &str) -> Option<NodeId> {
+        let mut current = if path.starts_with('/') {
+            self.root()
+        } else {
+            from
+        };
+
+        for component in path.split('/') {
+            current = match component {
+                "" | "." => current,
+                ".." => self.node(current).parent.unwrap_or(current),
+                name => self.child(current, name)?,
+            };
+        }
+
+        Some(current)
+    }
+
+    /// Returns the directory with the given name in the given directory,
+    /// creating it if it doesn't exist yet.
+    ///
+    /// # Panics
+    /// If there's already a file with that name.
+    pub fn mkdir(&mut self, parent: NodeId, name: &str) -> NodeId {
+        let id = self.insert(parent, name, Kind::Directory(BTreeMap::new()));
+        assert!(
+            matches!(self.node(id).kind, Kind::Directory(_)),
+            "{} is a file",
+            self.path(id)
+        );
+        id
+    }
+
+    /// Adds a file (or updates its size if it already exists) to the given
+    /// directory.
+    ///
+    /// # Panics
+    /// If there's already a directory with that name.
+    pub fn add_file(
+        &mut self,
+        parent: NodeId,
+        name: &str,
+        size: u64,
+    ) -> NodeId {
+        let id = self.insert(parent, name, Kind::File(size));
+        match &mut self.nodes[id.0].kind {
+            Kind::File(existing) => *existing = size,
+            Kind::Directory(_) => panic!("{} is a directory", self.path(id)),
+        }
+        id
+    }
+
+    /// Returns the path of the directory or file from the root.
+    pub fn path(&self, id: NodeId) -> String {
+        let mut names = Vec::new();
+        let mut current = id;
+        while let Some(parent) = self.node(current).parent {
+            names.push(self.node(current).name.as_str());
+            current = parent;
+        }
+
+        names.reverse();
+        format!("/{}", names.join("/"))
+    }
+
+    /// Returns every directory (in the order that they were found).
+    pub fn directories(&self) -> impl Iterator<Item = NodeId> + '_ {
+        (0..self.nodes.len())
+            .filter(|i| matches!(self.nodes[*i].kind, Kind::Directory(_)))
+            .map(NodeId)
+    }
+
+    /// Returns the size of every node (indexed by identifier): the size of
+    /// a file or the total size of everything inside of a directory.
+    ///
+    /// Everything is added to the arena after the directory that contains it
+    /// so going through it backwards we always have the total for a node
+    /// before we need to add it to its parent.
+    pub fn sizes(&self) -> Vec<u64> {
+        let mut sizes = vec![0; self.nodes.len()];
+
+        for (i, node) in self.nodes.iter().enumerate().rev() {
+            if let Kind::File(size) = node.kind {
+                sizes[i] = size;
+            }
+            if let Some(parent) = node.parent {
+                sizes[parent.0] += sizes[i];
             }
         }
 
-        total
-    } else {
-        let total_disk_space = 70000000;
-        let required_disk_space = 30000000;
-        let current_free_space = total_disk_space - sizes.get("/").unwrap();
-        let mut heap = BinaryHeap::new();
-
-        for (_dir, size) in sizes {
-            if size + current_free_space >= required_disk_space {
-                heap.push(Reverse(size))
+        sizes
+    }
+
+    /// Returns the smallest directory (and its size) that we could delete to
+    /// have at least the needed amount of free space on a disk of the given
+    /// size.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use aoc::y22d07::FileSystem;
+    /// let fs = FileSystem::parse("$ cd /\n$ ls\n60 a\ndir b\n$ cd b\n$ ls\n30 c\n");
+    /// let (dir, size) = fs.smallest_to_free(100, 25).unwrap();
+    /// assert_eq!((fs.path(dir).as_str(), size), ("/b", 30));
+    /// assert_eq!(fs.smallest_to_free(100, 50).unwrap().1, 90);
+    /// assert_eq!(fs.smallest_to_free(100, 200), None);
+    /// ```
+    pub fn smallest_to_free(
+        &self,
+        disk: u64,
+        needed: u64,
+    ) -> Option<(NodeId, u64)> {
+        let sizes = self.sizes();
+        let free = disk.saturating_sub(sizes[0]);
+
+        self.directories()
+            .map(|id| (id, sizes[id.0]))
+            .filter(|(_, size)| free + size >= needed)
+            .min_by_key(|(id, size)| (*size, *id))
+    }
+
+    /// Draws the file system like the `tree` command (with the size of each
+    /// file) followed by how many directories and files there are.
+    pub fn tree(&self) -> String {
+        let mut drawing = "/\n".to_string();
+        self.draw(self.root(), "", &mut drawing);
+
+        let directories = self.directories().count() - 1;
+        let files = self.nodes.len() - directories - 1;
+        writeln!(drawing, "\n{} directories, {} files", directories, files)
+            .unwrap();
+        drawing
+    }
+
+    /// Lists the total size of every directory (deepest first) like the
+    /// `du -h` command: sizes are rounded up to a power of `1024` with one
+    /// decimal place when that's less than ten.
+    pub fn du(&self) -> String {
+        let sizes = self.sizes();
+        let mut listing = String::new();
+        self.list(self.root(), &sizes, &mut listing);
+        listing
+    }
+
+    /// Adds a child to a directory, unless it already has one by that name
+    /// in which case that's returned instead.
+    fn insert(&mut self, parent: NodeId, name: &str, kind: Kind) -> NodeId {
+        if let Some(id) = self.child(parent, name) {
+            return id;
+        }
+
+        let id = NodeId(self.nodes.len());
+        let Kind::Directory(children) = &mut self.nodes[parent.0].kind else {
+            panic!("{} is a file", self.path(parent));
+        };
+        children.insert(name.to_string(), id);
+        self.nodes.push(Node {
+            name: name.to_string(),
+            parent: Some(parent),
+            kind,
+        });
+
+        id
+    }
+
+    /// Follows a path (like [`FileSystem::resolve`]), creating any of the
+    /// directories that don't exist yet.
+    fn mkdir_all(&mut self, from: NodeId, path: &str) -> NodeId {
+        let mut current = if path.starts_with('/') {
+            self.root()
+        } else {
+            from
+        };
+
+        for component in path.split('/') {
+            current = match component {
+                "" | "." => current,
+                ".." => self.node(current).parent.unwrap_or(current),
+                name => self.mkdir(current, name),
+            };
+        }
+
+        current
+    }
+
+    /// Draws the children of a directory for [`FileSystem::tree`].
+    fn draw(&self, id: NodeId, prefix: &str, drawing: &mut String) {
+        let Kind::Directory(children) = &self.node(id).kind else {
+            return;
+        };
+
+        for (i, (name, child)) in children.iter().enumerate() {
+            let last = i == children.len() - 1;
+            let branch = if last { "└── " } else { "├── " };
+
+            match self.node(*child).kind {
+                Kind::File(size) => {
+                    writeln!(drawing, "{}{}{} ({})", prefix, branch, name, size)
+                        .unwrap()
+                }
+                Kind::Directory(_) => {
+                    writeln!(drawing, "{}{}{}", prefix, branch, name).unwrap();
+                    let indent = if last { "    " } else { "│   " };
+                    self.draw(
+                        *child,
+                        &format!("{}{}", prefix, indent),
+                        drawing,
+                    );
+                }
             }
         }
+    }
+
+    /// Lists a directory after all of its subdirectories for
+    /// [`FileSystem::du`].
+    fn list(&self, id: NodeId, sizes: &[u64], listing: &mut String) {
+        let Kind::Directory(children) = &self.node(id).kind else {
+            return;
+        };
+
+        for child in children.values() {
+            self.list(*child, sizes, listing);
+        }
 
-        let Reverse(smallest) = heap.pop().unwrap();
-        smallest
+        writeln!(listing, "{}\t{}", human(sizes[id.0]), self.path(id)).unwrap();
+    }
+}
+
+impl Default for FileSystem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Formats a size the way that `du -h` does.
+fn human(size: u64) -> String {
+    const UNITS: [&str; 6] = ["K", "M", "G", "T", "P", "E"];
+
+    if size < 1024 {
+        return size.to_string();
+    }
+
+    let mut value = size as f64;
+    let mut unit = 0;
+    value /= 1024.0;
+    while value > 1023.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    let tenths = (value * 10.0).ceil() / 10.0;
+    if tenths < 10.0 {
+        format!("{:.1}{}", tenths, UNITS[unit])
+    } else {
+        format!("{}{}", value.ceil(), UNITS[unit])
     }
 }
 
@@ -176,36 +460,106 @@ mod tests {
     use super::*;
     use std::fs;
 
+    const EXAMPLE: &str = concat!(
+        "$ cd /\n",
+        "$ ls\n",
+        "dir a\n",
+        "14848514 b.txt\n",
+        "8504156 c.dat\n",
+        "dir d\n",
+        "$ cd a\n",
+        "$ ls\n",
+        "dir e\n",
+        "29116 f\n",
+        "2557 g\n",
+        "62596 h.lst\n",
+        "$ cd e\n",
+        "$ ls\n",
+        "584 i\n",
+        "$ cd ..\n",
+        "$ cd ..\n",
+        "$ cd d\n",
+        "$ ls\n",
+        "4060174 j\n",
+        "8033020 d.log\n",
+        "5626152 d.ext\n",
+        "7214296 k\n",
+    );
+
     #[test]
-    fn it_works() {
+    fn test_transcript() {
+        // listing twice, relative paths and directories that were never
+        // listed by their parent
         let input = concat!(
             "$ cd /\n",
             "$ ls\n",
-            "dir a\n",
-            "14848514 b.txt\n",
-            "8504156 c.dat\n",
-            "dir d\n",
-            "$ cd a\n",
+            "10 x\n",
             "$ ls\n",
-            "dir e\n",
-            "29116 f\n",
-            "2557 g\n",
-            "62596 h.lst\n",
-            "$ cd e\n",
+            "10 x\n",
+            "$ cd a/b\n",
             "$ ls\n",
-            "584 i\n",
-            "$ cd ..\n",
-            "$ cd ..\n",
-            "$ cd d\n",
+            "5 y\n",
+            "$ cd ../../c/.\n",
             "$ ls\n",
-            "4060174 j\n",
-            "8033020 d.log\n",
-            "5626152 d.ext\n",
-            "7214296 k\n",
+            "1 z\n",
+            "$ cd /a\n",
+            "$ ls\n",
+            "dir b\n",
+            "2 w\n",
+        );
+        let fs = FileSystem::parse(input);
+        let sizes = fs.sizes();
+
+        assert_eq!(sizes[fs.root().0], 18);
+        let a = fs.resolve(fs.root(), "a").unwrap();
+        assert_eq!(sizes[a.0], 7);
+        assert_eq!(fs.path(fs.resolve(a, "b/y").unwrap()), "/a/b/y");
+        assert_eq!(
+            fs.node(fs.resolve(a, "../c/z").unwrap()).kind,
+            Kind::File(1)
         );
+        assert_eq!(fs.directories().count(), 4);
+    }
 
-        assert_eq!(y22d07(input, 1), 95437);
-        assert_eq!(y22d07(input, 2), 24933642);
+    #[test]
+    fn test_rendering() {
+        let fs = FileSystem::parse(EXAMPLE);
+
+        assert_eq!(
+            fs.tree(),
+            concat!(
+                "/\n",
+                "├── a\n",
+                "│   ├── e\n",
+                "│   │   └── i (584)\n",
+                "│   ├── f (29116)\n",
+                "│   ├── g (2557)\n",
+                "│   └── h.lst (62596)\n",
+                "├── b.txt (14848514)\n",
+                "├── c.dat (8504156)\n",
+                "└── d\n",
+                "    ├── d.ext (5626152)\n",
+                "    ├── d.log (8033020)\n",
+                "    ├── j (4060174)\n",
+                "    └── k (7214296)\n",
+                "\n",
+                "3 directories, 10 files\n",
+            )
+        );
+
+        assert_eq!(fs.du(), "584\t/a/e\n93K\t/a\n24M\t/d\n47M\t/\n");
+
+        assert_eq!(human(1023), "1023");
+        assert_eq!(human(1024), "1.0K");
+        assert_eq!(human(1025), "1.1K");
+        assert_eq!(human(10 * 1024), "10K");
+        assert_eq!(human(3 * 1024 * 1024 * 1024), "3.0G");
+    }
+
+    #[test]
+    fn it_works() {
+        assert_eq!(y22d07(EXAMPLE, 1), 95437);
+        assert_eq!(y22d07(EXAMPLE, 2), 24933642);
     }
 
     #[test]
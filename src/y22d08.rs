@@ -66,8 +66,14 @@ pub fn y22d08(input: &str, part: u32) -> u32 {
     let grid = parse_input(input);
     let mut scenic_scores = BinaryHeap::new();
 
-    // calculate the outer edge which is always visible
-    let mut total = 2 * grid.len() as u32 + 2 * (grid[0].len() as u32 - 2);
+    // calculate the outer edge which is always visible (a single row or
+    // column is nothing but edge)
+    let (height, width) = (grid.len() as u32, grid[0].len() as u32);
+    let mut total = if height <= 2 || width <= 2 {
+        height * width
+    } else {
+        2 * height + 2 * (width - 2)
+    };
 
     for y in 1..grid.len() - 1 {
         for x in 1..grid[0].len() - 1 {
@@ -155,7 +161,8 @@ pub fn y22d08(input: &str, part: u32) -> u32 {
     if part == 1 {
         total
     } else {
-        scenic_scores.pop().unwrap()
+        // edge trees always have a score of zero
+        scenic_scores.pop().unwrap_or(0)
     }
 }
 
@@ -183,6 +190,7 @@ fn parse_input(input: &str) -> Vec<Vec<u32>> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::generate;
     use std::fs;
 
     #[test]
@@ -192,6 +200,47 @@ mod tests {
         assert_eq!(y22d08(input, 2), 8);
     }
 
+    #[test]
+    fn properties() {
+        for input in generate::cases("y22d08", 100, 12) {
+            let grid = parse_input(&input);
+            let (height, width) = (grid.len(), grid[0].len());
+
+            // brute force: look in every direction from every tree
+            let mut visible = 0;
+            let mut best = 0;
+            for y in 0..height {
+                for x in 0..width {
+                    let tree = grid[y][x];
+                    let lines: [Vec<u32>; 4] = [
+                        (0..x).rev().map(|i| grid[y][i]).collect(),
+                        (x + 1..width).map(|i| grid[y][i]).collect(),
+                        (0..y).rev().map(|j| grid[j][x]).collect(),
+                        (y + 1..height).map(|j| grid[j][x]).collect(),
+                    ];
+
+                    if lines.iter().any(|line| line.iter().all(|t| *t < tree)) {
+                        visible += 1;
+                    }
+
+                    let score: u32 = lines
+                        .iter()
+                        .map(|line| {
+                            match line.iter().position(|t| *t >= tree) {
+                                Some(i) => i as u32 + 1,
+                                None => line.len() as u32,
+                            }
+                        })
+                        .product();
+                    best = best.max(score);
+                }
+            }
+
+            assert_eq!(y22d08(&input, 1), visible);
+            assert_eq!(y22d08(&input, 2), best);
+        }
+    }
+
     #[test]
     fn the_solution() {
         let contents = fs::read_to_string("input/2022/day08.txt").unwrap();
@@ -25,7 +25,11 @@
 //! [`std::collections::HashSet`] with tuples of `x`,`y` coordinates so that
 //! at the end we can just return the size of the set to get the total number
 //! of locations visited.
+//!
+//! To see the rope wiggle around [`y22d09_render`] sends the knots (and the
+//! trail left by the tail) to a [`crate::render::Render`] after every step.
 
+use crate::render::{Frame, NoRender, Render};
 use std::collections::HashSet;
 
 /// The solution for the day nine challenge.
@@ -56,7 +60,35 @@ use std::collections::HashSet;
 /// assert_eq!(y22d09(input, 3), 4);
 /// ```
 pub fn y22d09(input: &str, number_of_knots: u32) -> u32 {
+    y22d09_render(input, number_of_knots, &mut NoRender)
+}
+
+/// The solution for the day nine challenge, with visualization.
+///
+/// This is the same as [`y22d09`] but it also renders the rope after every
+/// step. Since the knots all follow the head they can never leave the area
+/// that the head moves around in, so we compute that first and use it as the
+/// size of the frames.
+///
+/// # Example
+/// ```rust
+/// # use aoc::y22d09::y22d09_render;
+/// # use aoc::render::NoRender;
+/// // probably read this from the input file...
+/// let input = "U 2\nR 2\nU 2\nD 3\nL 4";
+/// assert_eq!(y22d09_render(input, 3, &mut NoRender), 4);
+/// ```
+pub fn y22d09_render(
+    input: &str,
+    number_of_knots: u32,
+    render: &mut impl Render,
+) -> u32 {
     let lines: Vec<_> = input.lines().collect();
+    let bounds = if render.enabled() {
+        head_bounds(&lines)
+    } else {
+        (0, 0, 0, 0)
+    };
     let mut visited = HashSet::new();
     let mut knots = Vec::new();
 
@@ -97,12 +129,77 @@ pub fn y22d09(input: &str, number_of_knots: u32) -> u32 {
                     visited.insert(knots[i]);
                 }
             }
+
+            render.frame_with(|| rope_frame(&knots, &visited, bounds));
         }
     }
 
+    render.finish(&|| rope_frame(&knots, &visited, bounds));
+
     visited.len() as u32
 }
 
+/// Computes the area (`min_x`, `max_x`, `min_y`, `max_y`) that the head of
+/// the rope moves around in.
+fn head_bounds(lines: &[&str]) -> (i32, i32, i32, i32) {
+    let (mut x, mut y) = (0, 0);
+    let mut bounds = (0, 0, 0, 0);
+
+    for line in lines {
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        let steps: i32 = parts[1].parse().unwrap();
+
+        match parts[0] {
+            "U" => y += steps,
+            "D" => y -= steps,
+            "L" => x -= steps,
+            "R" => x += steps,
+            _ => panic!("Invalid direction!"),
+        }
+
+        bounds = (
+            bounds.0.min(x),
+            bounds.1.max(x),
+            bounds.2.min(y),
+            bounds.3.max(y),
+        );
+    }
+
+    bounds
+}
+
+/// Draws the rope: the head is red, the rest of the knots are white and the
+/// positions that the tail has already visited are dim green. The "Y" axis
+/// is flipped so that "up" in the instructions is up in the frame.
+fn rope_frame(
+    knots: &[(i32, i32)],
+    visited: &HashSet<(i32, i32)>,
+    bounds: (i32, i32, i32, i32),
+) -> Frame {
+    let (min_x, max_x, min_y, max_y) = bounds;
+    let mut frame = Frame::new(
+        (max_x - min_x + 1) as usize,
+        (max_y - min_y + 1) as usize,
+        [15, 15, 35],
+    );
+
+    let mut draw = |(x, y): (i32, i32), color| {
+        frame.set((x - min_x) as usize, (max_y - y) as usize, color);
+    };
+
+    for position in visited {
+        draw(*position, [40, 110, 40]);
+    }
+
+    for knot in knots.iter().skip(1).rev() {
+        draw(*knot, [230, 230, 230]);
+    }
+
+    draw(knots[0], [220, 40, 40]);
+
+    frame
+}
+
 /// Given an updated head position and the current tail position calculate if
 /// tail needs to move and return its updated position.
 fn reconcile_tail(hx: i32, hy: i32, tx: i32, ty: i32) -> (i32, i32) {
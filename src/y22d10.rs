@@ -39,7 +39,12 @@
 //! register plus or minus one, and so if the current column falls into the
 //! range of `x - 1` or `x + 1` then we draw the `#` character, otherwise the
 //! `.` character.
+//!
+//! The drawing is a handful of capital letters in the standard Advent of Code
+//! font, so we can also run it through [`crate::util::ocr`] to get the answer
+//! as text (which is what actually needs to be submitted).
 
+use crate::util::ocr::ocr;
 use std::cmp::Ordering;
 
 /// The solution for part one of the day ten challenge.
@@ -137,6 +142,23 @@ pub fn y22d10p2(input: &str) -> String {
     output.trim().to_string()
 }
 
+/// The decoded solution for part two of the day ten challenge.
+///
+/// This draws the screen exactly like [`y22d10p2`] and then reads the letters
+/// back out of the drawing. Returns `None` if the drawing doesn't contain
+/// (only) recognizable letters.
+///
+/// # Example
+/// ```rust
+/// # use aoc::y22d10::y22d10p2_ocr;
+/// // probably read this from the input file...
+/// let input = "noop\n".repeat(240);
+/// assert_eq!(y22d10p2_ocr(&input), None);
+/// ```
+pub fn y22d10p2_ocr(input: &str) -> Option<String> {
+    ocr(&y22d10p2(input))
+}
+
 /// This function computes the state of the register `x` at each cycle as
 /// described above and by the challenge prompt. Simply it starts at value `1`
 /// and then depending on the instruction pushes either one or two states onto
@@ -205,6 +227,7 @@ mod tests {
                 "#######.......#######.......#######.....",
             )
         );
+        assert_eq!(y22d10p2_ocr(input), None);
     }
 
     #[test]
@@ -221,7 +244,8 @@ mod tests {
                 "#....#.#..###..#..#.#....###..#..#..#...\n",
                 "#....#.#..#.#..#..#.#....#....#..#.#....\n",
                 "####.#..#.#..#.#..#.####.#.....##..####.",
-            ) // EKRHEPUZ
+            )
         );
+        assert_eq!(y22d10p2_ocr(&contents), Some("EKRHEPUZ".to_string()));
     }
 }
@@ -37,8 +37,13 @@
 //! and computing the shortest path to the end for all of them and then taking
 //! the shortest of those as I was currently doing). I switched my solution to
 //! use these two strategies and now it runs significantly faster.
+//!
+//! Since it's interesting to compare them, the (cleaned up) depth-first
+//! search and Dijkstra versions are still around as [`y22d12_dfs`] and
+//! [`y22d12_dijkstra`].
 
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
 
 /// The solution for the day twelve challenge.
 ///
@@ -259,6 +264,179 @@ pub fn y22d12(input: &str, part: u32) -> Option<u32> {
     None
 }
 
+/// The solution for the day twelve challenge using Dijkstra's algorithm.
+///
+/// This is (a cleaned up version of) the way that I originally solved the
+/// puzzle. It searches forward from the start to the end keeping a
+/// [`std::collections::BinaryHeap`] of the closest nodes that still need to
+/// be visited. I used to run the search from every possible starting position
+/// (height `a`) in part `2`, which was really slow, so now it searches
+/// backwards from the end (like the main solution) until it reaches any of
+/// them.
+///
+/// # Example
+/// ```rust
+/// # use aoc::y22d12::y22d12_dijkstra;
+/// // probably read this from the input file...
+/// let mut input = "Sbcdef\nlkjihg\nmnopqr\nxwvuts\nyEzzzz\nzzzzza";
+/// assert_eq!(y22d12_dijkstra(input, 1), Some(25));
+/// assert_eq!(y22d12_dijkstra(input, 2), Some(25));
+/// ```
+pub fn y22d12_dijkstra(input: &str, part: u32) -> Option<u32> {
+    let (grid, start, end) = parse_heightmap(input);
+
+    if part == 1 {
+        return dijkstra(
+            &grid,
+            start,
+            |node| node == end,
+            |from, to| to <= from + 1,
+        );
+    }
+
+    // we're going backwards so we can go down at most one step
+    dijkstra(
+        &grid,
+        end,
+        |node| grid[&node] == 0,
+        |from, to| from <= to + 1,
+    )
+}
+
+/// The solution for the day twelve challenge using a depth-first search.
+///
+/// My original depth-first search tried every possible path which never
+/// finished on the real input. This version remembers the shortest distance
+/// at which it has reached every node and stops exploring a path as soon as
+/// it reaches a node that it has already seen at the same or a shorter
+/// distance. Like the main solution it searches backwards from the end so
+/// that part `2` gets the distances to all of the possible starting
+/// positions at once.
+///
+/// # Example
+/// ```rust
+/// # use aoc::y22d12::y22d12_dfs;
+/// // probably read this from the input file...
+/// let mut input = "Sbcdef\nlkjihg\nmnopqr\nxwvuts\nyEzzzz\nzzzzza";
+/// assert_eq!(y22d12_dfs(input, 1), Some(25));
+/// assert_eq!(y22d12_dfs(input, 2), Some(25));
+/// ```
+pub fn y22d12_dfs(input: &str, part: u32) -> Option<u32> {
+    let (grid, start, end) = parse_heightmap(input);
+    let mut shortest = HashMap::new();
+    let mut to_visit = vec![(end, 0)];
+
+    while let Some((node, distance)) = to_visit.pop() {
+        if shortest.get(&node).is_some_and(|d| *d <= distance) {
+            continue;
+        }
+
+        shortest.insert(node, distance);
+
+        for neighbor in neighbors(&grid, node) {
+            // we're going backwards so we can go down at most one step
+            if grid[&node] <= grid[&neighbor] + 1 {
+                to_visit.push((neighbor, distance + 1));
+            }
+        }
+    }
+
+    if part == 1 {
+        return shortest.get(&start).copied();
+    }
+
+    grid.iter()
+        .filter(|(_, height)| **height == 0)
+        .filter_map(|(position, _)| shortest.get(position).copied())
+        .min()
+}
+
+/// A grid of heights (from `0` to `25`).
+type Heightmap = HashMap<(usize, usize), u32>;
+
+/// Parses the input into a grid of heights (from `0` to `25`) and returns it
+/// along with the start and end positions.
+fn parse_heightmap(input: &str) -> (Heightmap, (usize, usize), (usize, usize)) {
+    let mut grid = HashMap::new();
+    let mut start = (0, 0);
+    let mut end = (0, 0);
+
+    for (y, line) in input.lines().enumerate() {
+        for (x, c) in line.chars().enumerate() {
+            let height = match c {
+                'S' => {
+                    start = (x, y);
+                    'a'
+                }
+                'E' => {
+                    end = (x, y);
+                    'z'
+                }
+                _ => c,
+            };
+
+            grid.insert((x, y), height as u32 - 'a' as u32);
+        }
+    }
+
+    (grid, start, end)
+}
+
+/// Returns the (up to four) positions next to the given one that are on the
+/// grid.
+fn neighbors(grid: &Heightmap, (x, y): (usize, usize)) -> Vec<(usize, usize)> {
+    let mut neighbors = vec![(x + 1, y), (x, y + 1)];
+
+    if x > 0 {
+        neighbors.push((x - 1, y));
+    }
+
+    if y > 0 {
+        neighbors.push((x, y - 1));
+    }
+
+    neighbors.retain(|n| grid.contains_key(n));
+    neighbors
+}
+
+/// Finds the length of the shortest path from the start to any node that is
+/// an end using Dijkstra's algorithm, where we can only step between heights
+/// that `can_step` allows (from the height we're at to the next one).
+fn dijkstra(
+    grid: &Heightmap,
+    start: (usize, usize),
+    is_end: impl Fn((usize, usize)) -> bool,
+    can_step: fn(u32, u32) -> bool,
+) -> Option<u32> {
+    let mut distances = HashMap::from([(start, 0)]);
+    let mut heap = BinaryHeap::from([Reverse((0, start))]);
+
+    while let Some(Reverse((distance, node))) = heap.pop() {
+        if is_end(node) {
+            return Some(distance);
+        }
+
+        if distance > distances[&node] {
+            // we already found a shorter way here
+            continue;
+        }
+
+        for neighbor in neighbors(grid, node) {
+            if !can_step(grid[&node], grid[&neighbor]) {
+                continue;
+            }
+
+            let next = distance + 1;
+            if distances.get(&neighbor).is_none_or(|d| next < *d) {
+                distances.insert(neighbor, next);
+                heap.push(Reverse((next, neighbor)));
+            }
+        }
+    }
+
+    None
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -269,6 +447,10 @@ mod tests {
         let input = "Sabqponm\nabcryxxl\naccszExk\nacctuvwj\nabdefghi\n";
         assert_eq!(y22d12(input, 1), Some(31));
         assert_eq!(y22d12(input, 2), Some(29));
+        assert_eq!(y22d12_dijkstra(input, 1), Some(31));
+        assert_eq!(y22d12_dijkstra(input, 2), Some(29));
+        assert_eq!(y22d12_dfs(input, 1), Some(31));
+        assert_eq!(y22d12_dfs(input, 2), Some(29));
     }
 
     #[test]
@@ -277,5 +459,9 @@ mod tests {
 
         assert_eq!(y22d12(&contents, 1).unwrap(), 370);
         assert_eq!(y22d12(&contents, 2).unwrap(), 363);
+        assert_eq!(y22d12_dijkstra(&contents, 1).unwrap(), 370);
+        assert_eq!(y22d12_dijkstra(&contents, 2).unwrap(), 363);
+        assert_eq!(y22d12_dfs(&contents, 1).unwrap(), 370);
+        assert_eq!(y22d12_dfs(&contents, 2).unwrap(), 363);
     }
 }
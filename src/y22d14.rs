@@ -24,7 +24,12 @@
 //! that extends forever in the "X" direction two spaces below the lowest
 //! recorded rock and so when there is no more space for the sand to fall as
 //! it's reached the top we're done.
+//!
+//! Watching the sand pile up is the fun part, so [`y22d14_render`] sends the
+//! cave to a [`crate::render::Render`] every time a unit of sand comes to
+//! rest.
 
+use crate::render::{Frame, NoRender, Render};
 use std::collections::{BinaryHeap, HashSet};
 
 /// The solution for the day fourteen challenge.
@@ -63,6 +68,25 @@ use std::collections::{BinaryHeap, HashSet};
 /// assert_eq!(y22d14(input, 2), 54);
 /// ```
 pub fn y22d14(input: &str, part: u32) -> u32 {
+    y22d14_render(input, part, &mut NoRender)
+}
+
+/// The solution for the day fourteen challenge, with visualization.
+///
+/// This is the same as [`y22d14`] but it also renders the cave each time that
+/// a unit of sand comes to rest. The rendered area is just wide enough to
+/// contain all of the rocks in part one, and all of the sand that could
+/// possibly fit on the floor in part two.
+///
+/// # Example
+/// ```rust
+/// # use aoc::y22d14::y22d14_render;
+/// # use aoc::render::NoRender;
+/// // probably read this from the input file...
+/// let input = "499,4 -> 499,5 -> 496,5\n503,4 -> 502,4 -> 502,7 -> 494,7";
+/// assert_eq!(y22d14_render(input, 1, &mut NoRender), 19);
+/// ```
+pub fn y22d14_render(input: &str, part: u32, render: &mut impl Render) -> u32 {
     let lines: Vec<_> = input.lines().collect();
     let mut occupied = HashSet::new();
     let mut lowest = BinaryHeap::new();
@@ -107,6 +131,12 @@ pub fn y22d14(input: &str, part: u32) -> u32 {
         lowest.pop().unwrap() + 2
     };
 
+    let rocks = if render.enabled() {
+        occupied.clone()
+    } else {
+        HashSet::new()
+    };
+
     let mut stop = false;
     let mut total = 0;
     while !stop {
@@ -151,9 +181,13 @@ pub fn y22d14(input: &str, part: u32) -> u32 {
         }
 
         occupied.insert(sand);
-        total += 1
+        total += 1;
+
+        render.frame_with(|| cave_frame(&occupied, &rocks, floor, part));
     }
 
+    render.finish(&|| cave_frame(&occupied, &rocks, floor, part));
+
     if part == 1 {
         total - 1
     } else {
@@ -161,6 +195,43 @@ pub fn y22d14(input: &str, part: u32) -> u32 {
     }
 }
 
+/// Draws the cave: rocks (and the floor in part two) are gray and sand is,
+/// well, sand colored.
+fn cave_frame(
+    occupied: &HashSet<(usize, usize)>,
+    rocks: &HashSet<(usize, usize)>,
+    floor: usize,
+    part: u32,
+) -> Frame {
+    let (min_x, max_x) = if part == 1 {
+        (
+            rocks
+                .iter()
+                .map(|(x, _)| *x)
+                .min()
+                .unwrap()
+                .saturating_sub(1),
+            rocks.iter().map(|(x, _)| *x).max().unwrap() + 1,
+        )
+    } else {
+        // the sand can't spread out any further than a triangle from the
+        // source down to the floor
+        // (which can be wider than the cave is to the left of the source)
+        (500usize.saturating_sub(floor + 1), 500 + floor + 1)
+    };
+
+    Frame::from_fn(max_x - min_x + 1, floor + 1, |x, y| {
+        let point = (x + min_x, y);
+        if rocks.contains(&point) || (part == 2 && y == floor) {
+            [110, 110, 110]
+        } else if occupied.contains(&point) {
+            [237, 201, 120]
+        } else {
+            [20, 20, 30]
+        }
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -177,6 +248,20 @@ mod tests {
         assert_eq!(y22d14(input, 2), 93);
     }
 
+    #[test]
+    fn test_cave_frame() {
+        let rocks = HashSet::from([(499, 1), (500, 1), (501, 1)]);
+
+        // part two's triangle is cut off at the left edge of the cave
+        let frame = cave_frame(&rocks, &rocks, 600, 2);
+        assert_eq!((frame.width(), frame.height()), (1102, 601));
+        assert_eq!(frame.get(500, 1), Some([110, 110, 110]));
+
+        let rocks = HashSet::from([(0, 3), (4, 3)]);
+        let frame = cave_frame(&HashSet::new(), &rocks, 5, 1);
+        assert_eq!((frame.width(), frame.height()), (6, 6));
+    }
+
     #[test]
     fn the_solution() {
         let contents = fs::read_to_string("input/2022/day14.txt").unwrap();
@@ -93,6 +93,7 @@ pub fn y23d09(input: &str, part: u32) -> i32 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::generate;
     use std::fs;
 
     #[test]
@@ -103,6 +104,37 @@ mod tests {
         assert_eq!(y23d09(input, 2), 2);
     }
 
+    #[test]
+    fn properties() {
+        for input in generate::cases("y23d09", 100, 10) {
+            let (mut next, mut previous) = (0, 0);
+            for line in input.lines() {
+                let numbers: Vec<i32> = line
+                    .split_whitespace()
+                    .map(|n| n.parse().unwrap())
+                    .collect();
+                next += extrapolate(&numbers);
+                let reversed: Vec<_> = numbers.into_iter().rev().collect();
+                previous += extrapolate(&reversed);
+            }
+
+            assert_eq!(y23d09(&input, 1), next);
+            assert_eq!(y23d09(&input, 2), previous);
+        }
+    }
+
+    /// Reference version: the next value is the last value plus the next
+    /// value of the differences (and going backwards is the same thing on
+    /// the reversed sequence).
+    fn extrapolate(numbers: &[i32]) -> i32 {
+        if numbers.iter().all(|n| *n == 0) {
+            return 0;
+        }
+
+        let diffs: Vec<_> = numbers.windows(2).map(|w| w[1] - w[0]).collect();
+        numbers[numbers.len() - 1] + extrapolate(&diffs)
+    }
+
     #[test]
     fn the_solution() {
         let contents = fs::read_to_string("input/2023/day09.txt").unwrap();
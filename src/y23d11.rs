@@ -109,6 +109,7 @@ pub fn y23d11(input: &str, modifier: i64) -> i64 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::generate;
     use std::fs;
 
     #[test]
@@ -131,6 +132,58 @@ mod tests {
         assert_eq!(y23d11(input, 100), 8410);
     }
 
+    #[test]
+    fn properties() {
+        for input in generate::cases("y23d11", 100, 12) {
+            let rows: Vec<Vec<char>> =
+                input.lines().map(|line| line.chars().collect()).collect();
+
+            for expansion in [2, 10, 1000000] {
+                // brute force: give every row and column its expanded
+                // coordinate and then add up all of the distances
+                let coordinates = |empty: Vec<bool>| {
+                    let mut position = 0;
+                    empty
+                        .into_iter()
+                        .map(|empty| {
+                            let current = position;
+                            position += if empty { expansion } else { 1 };
+                            current
+                        })
+                        .collect::<Vec<i64>>()
+                };
+                let ys = coordinates(
+                    rows.iter().map(|row| !row.contains(&'#')).collect(),
+                );
+                let xs = coordinates(
+                    (0..rows[0].len())
+                        .map(|x| rows.iter().all(|row| row[x] != '#'))
+                        .collect(),
+                );
+
+                let galaxies: Vec<_> = rows
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(y, row)| {
+                        row.iter()
+                            .enumerate()
+                            .filter(|(_, c)| **c == '#')
+                            .map(move |(x, _)| (x, y))
+                    })
+                    .collect();
+                let mut total = 0;
+                for (i, (ax, ay)) in galaxies.iter().enumerate() {
+                    for (bx, by) in &galaxies[i + 1..] {
+                        total += (xs[*ax] - xs[*bx]).abs()
+                            + (ys[*ay] - ys[*by]).abs();
+                    }
+                }
+
+                assert_eq!(y23d11(&input, expansion), total);
+            }
+        }
+    }
+
     #[test]
     fn the_solution() {
         let contents = fs::read_to_string("input/2023/day11.txt").unwrap();
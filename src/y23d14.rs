@@ -20,7 +20,11 @@
 //! needing to run one billion (expensive) iterations of a loop we can instead
 //! find a cycle that we are sure that there will be because of the way that
 //! the input is crafted.
+//!
+//! To watch the rocks roll around [`y23d14_render`] sends the platform to a
+//! [`crate::render::Render`] after every tilt.
 
+use crate::render::{Frame, NoRender, Render};
 use std::collections::HashMap;
 
 /// The solution for the day fourteen challenge.
@@ -44,6 +48,23 @@ use std::collections::HashMap;
 /// assert_eq!(y23d14(input, 2), 1);
 /// ```
 pub fn y23d14(input: &str, part: u32) -> i32 {
+    y23d14_render(input, part, &mut NoRender)
+}
+
+/// The solution for the day fourteen challenge, with visualization.
+///
+/// This is the same as [`y23d14`] but it also renders the platform before we
+/// start and then after every tilt (until we find the loop in part two).
+///
+/// # Example
+/// ```rust
+/// # use aoc::y23d14::y23d14_render;
+/// # use aoc::render::NoRender;
+/// // probably read this from the input file...
+/// let input = "...\n.#.\nO..";
+/// assert_eq!(y23d14_render(input, 1, &mut NoRender), 3);
+/// ```
+pub fn y23d14_render(input: &str, part: u32, render: &mut impl Render) -> i32 {
     let mut total = 0;
     let mut map = HashMap::new();
     let mut seen = HashMap::new();
@@ -68,15 +89,20 @@ pub fn y23d14(input: &str, part: u32) -> i32 {
 
     cols += 1;
 
+    render.frame_with(|| platform_frame(rows, cols, &map));
+
     if part == 1 {
         tilt('N', rows, cols, &mut map);
+
+        render.frame_with(|| platform_frame(rows, cols, &map));
     } else {
         let cycles = 1000000000;
         for cycle in 1..cycles + 1 {
-            tilt('N', rows, cols, &mut map);
-            tilt('W', rows, cols, &mut map);
-            tilt('S', rows, cols, &mut map);
-            tilt('E', rows, cols, &mut map);
+            for dir in ['N', 'W', 'S', 'E'] {
+                tilt(dir, rows, cols, &mut map);
+
+                render.frame_with(|| platform_frame(rows, cols, &map));
+            }
 
             let grid = map_to_string(rows, cols, &map);
             if let Some(seen_at) = seen.insert(grid, cycle) {
@@ -87,6 +113,8 @@ pub fn y23d14(input: &str, part: u32) -> i32 {
         }
     }
 
+    render.finish(&|| platform_frame(rows, cols, &map));
+
     for ((_, y), c) in map {
         if c == 'O' {
             total += rows - y;
@@ -233,6 +261,22 @@ fn map_to_string(
     s
 }
 
+/// Draws the platform: cube-shaped rocks are gray and rounded rocks are
+/// white.
+fn platform_frame(
+    rows: i32,
+    cols: i32,
+    map: &HashMap<(i32, i32), char>,
+) -> Frame {
+    Frame::from_fn(cols as usize, rows as usize, |x, y| {
+        match map.get(&(x as i32, y as i32)) {
+            Some('#') => [110, 110, 110],
+            Some('O') => [230, 230, 230],
+            _ => [15, 15, 35],
+        }
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -21,7 +21,16 @@
 //! individual tile) so I switched to a queuing implementation. It's not super
 //! fast for the second part (where we need to try all of the possible starting
 //! points and directions) but it's not so slow that it should be thrown away.
+//! The recursive approach is still around as [`y23d16_recursive`], but it
+//! now only recurses when a splitter actually splits the beam and it keeps
+//! track of where the beam has already been which makes it plenty fast.
+//!
+//! The queue also makes it easy to watch the beam spread out: every time that
+//! we've processed all of the tiles that were in the queue at the start of a
+//! "wave" [`y23d16_render`] sends the contraption to a
+//! [`crate::render::Render`].
 
+use crate::render::{Frame, NoRender, Render};
 use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
 
 /// The solution for the day sixteen challenge.
@@ -49,6 +58,24 @@ use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
 /// assert_eq!(y23d16(input, 2), 41);
 /// ```
 pub fn y23d16(input: &str, part: u32) -> u32 {
+    y23d16_render(input, part, &mut NoRender)
+}
+
+/// The solution for the day sixteen challenge, with visualization.
+///
+/// This is the same as [`y23d16`] but it also renders the beam as it spreads
+/// through the contraption. In part two that means that we render the beam
+/// from every starting point, one after the other.
+///
+/// # Example
+/// ```rust
+/// # use aoc::y23d16::y23d16_render;
+/// # use aoc::render::NoRender;
+/// // probably read this from the input file...
+/// let input = "..\\\n...";
+/// assert_eq!(y23d16_render(input, 1, &mut NoRender), 4);
+/// ```
+pub fn y23d16_render(input: &str, part: u32, render: &mut impl Render) -> u32 {
     let mut grid = HashMap::new();
     let lines: Vec<_> = input.lines().collect();
     let rows = lines.len();
@@ -67,20 +94,28 @@ pub fn y23d16(input: &str, part: u32) -> u32 {
 
     cols += 1;
 
-    if part == 1 {
-        return send_light((0, 0), 'R', &grid, rows, cols);
-    }
+    let mut starts = vec![((0, 0), 'R')];
+    if part == 2 {
+        starts.clear();
+        for x in 0..cols {
+            starts.push(((x, 0), 'D'));
+            starts.push(((x, rows - 1), 'U'));
+        }
 
-    for x in 0..cols {
-        heap.push(send_light((x, 0), 'D', &grid, rows, cols));
-        heap.push(send_light((x, rows - 1), 'U', &grid, rows, cols));
+        for y in 0..rows {
+            starts.push(((0, y), 'R'));
+            starts.push(((cols - 1, y), 'L'));
+        }
     }
 
-    for y in 0..rows {
-        heap.push(send_light((0, y), 'R', &grid, rows, cols));
-        heap.push(send_light((cols - 1, y), 'L', &grid, rows, cols));
+    // the tiles that the last beam energized (for the final frame)
+    let mut last = HashSet::new();
+    for (start, direction) in starts {
+        last = send_light(start, direction, &grid, rows, cols, render);
+        heap.push(last.len() as u32);
     }
 
+    render.finish(&|| beam_frame(&grid, rows, cols, &last, &VecDeque::new()));
     heap.pop().unwrap()
 }
 
@@ -89,24 +124,37 @@ pub fn y23d16(input: &str, part: u32) -> u32 {
 /// using a [`std::collections::VecDeque`] to keep track of the tiles that it
 /// still needs to visit while maintaining a [`std::collections::HashSet`] of
 /// energized tiles and a vector of tiles that it has already visited (in order
-/// to avoid infinite loops).
+/// to avoid infinite loops). We also keep track of how many tiles are left in
+/// the current "wave" so that we know when to render the next frame. Returns
+/// the energized tiles.
 fn send_light(
     start: (usize, usize),
     direction: char,
     grid: &HashMap<(usize, usize), char>,
     rows: usize,
     cols: usize,
-) -> u32 {
+    render: &mut impl Render,
+) -> HashSet<(usize, usize)> {
     let mut energized = HashSet::new();
     let mut queue = VecDeque::new();
     let mut visited = Vec::new();
+    let mut wave = 1;
 
     queue.push_back((start, direction));
 
     loop {
+        if wave == 0 {
+            render.frame_with(|| {
+                beam_frame(grid, rows, cols, &energized, &queue)
+            });
+
+            wave = queue.len();
+        }
+
         match queue.pop_front() {
             None => break,
             Some(((x, y), direction)) => {
+                wave -= 1;
                 let tile = grid.get(&(x, y)).unwrap();
                 energized.insert((x, y));
 
@@ -157,7 +205,152 @@ fn send_light(
         }
     }
 
-    energized.len().try_into().unwrap()
+    energized
+}
+
+/// The solution for the day sixteen challenge using recursion.
+///
+/// This works exactly like [`y23d16`] except that each beam is followed in a
+/// loop until it either leaves the grid or reaches a tile that it has already
+/// entered from the same direction; when a splitter splits the beam we
+/// recurse to follow the second half.
+///
+/// # Example
+/// ```rust
+/// # use aoc::y23d16::y23d16_recursive;
+/// // probably read this from the input file...
+/// let input = concat!(
+///     "......|...\\..\\...\n",
+///     "..../........|...\n",
+///     "....\\.-.../......\n",
+///     "......|....../...\n",
+///     ".................",
+/// );
+/// assert_eq!(y23d16_recursive(input, 1), 41);
+/// assert_eq!(y23d16_recursive(input, 2), 41);
+/// ```
+pub fn y23d16_recursive(input: &str, part: u32) -> u32 {
+    let mut grid = HashMap::new();
+    let lines: Vec<_> = input.lines().collect();
+    let rows = lines.len();
+    let cols = lines[0].len();
+
+    for (y, line) in lines.iter().enumerate() {
+        for (x, c) in line.chars().enumerate() {
+            grid.insert((x, y), c);
+        }
+    }
+
+    let energized = |start, direction| {
+        let mut seen = HashSet::new();
+        follow_beam(start, direction, &grid, rows, cols, &mut seen);
+
+        let tiles: HashSet<_> = seen.iter().map(|(tile, _)| tile).collect();
+        tiles.len() as u32
+    };
+
+    if part == 1 {
+        return energized((0, 0), 'R');
+    }
+
+    let mut starts = Vec::new();
+    for x in 0..cols {
+        starts.push(((x, 0), 'D'));
+        starts.push(((x, rows - 1), 'U'));
+    }
+
+    for y in 0..rows {
+        starts.push(((0, y), 'R'));
+        starts.push(((cols - 1, y), 'L'));
+    }
+
+    starts
+        .into_iter()
+        .map(|(start, direction)| energized(start, direction))
+        .max()
+        .unwrap()
+}
+
+/// Follows a beam that enters the given tile moving in the given direction,
+/// recording every tile and direction in `seen`.
+fn follow_beam(
+    mut position: (usize, usize),
+    mut direction: char,
+    grid: &HashMap<(usize, usize), char>,
+    rows: usize,
+    cols: usize,
+    seen: &mut HashSet<((usize, usize), char)>,
+) {
+    loop {
+        if !seen.insert((position, direction)) {
+            // we've already followed the beam from here
+            return;
+        }
+
+        let directions = match (grid[&position], direction) {
+            ('-', 'U' | 'D') => vec!['L', 'R'],
+            ('|', 'L' | 'R') => vec!['U', 'D'],
+            ('/', 'R') | ('\\', 'L') => vec!['U'],
+            ('/', 'L') | ('\\', 'R') => vec!['D'],
+            ('/', 'U') | ('\\', 'D') => vec!['R'],
+            ('/', 'D') | ('\\', 'U') => vec!['L'],
+            _ => vec![direction],
+        };
+
+        for split in directions.iter().skip(1) {
+            if let Some(next) = step(position, *split, rows, cols) {
+                follow_beam(next, *split, grid, rows, cols, seen);
+            }
+        }
+
+        direction = directions[0];
+        match step(position, direction, rows, cols) {
+            Some(next) => position = next,
+            None => return,
+        }
+    }
+}
+
+/// Returns the next tile in the given direction if it's still on the grid.
+fn step(
+    (x, y): (usize, usize),
+    direction: char,
+    rows: usize,
+    cols: usize,
+) -> Option<(usize, usize)> {
+    match direction {
+        'R' if x < cols - 1 => Some((x + 1, y)),
+        'L' if x > 0 => Some((x - 1, y)),
+        'D' if y < rows - 1 => Some((x, y + 1)),
+        'U' if y > 0 => Some((x, y - 1)),
+        _ => None,
+    }
+}
+
+/// Draws the contraption: mirrors and splitters are gray, energized tiles are
+/// orange, and the tiles that the beam is about to enter are white.
+fn beam_frame(
+    grid: &HashMap<(usize, usize), char>,
+    rows: usize,
+    cols: usize,
+    energized: &HashSet<(usize, usize)>,
+    queue: &VecDeque<((usize, usize), char)>,
+) -> Frame {
+    let mut frame = Frame::from_fn(cols, rows, |x, y| {
+        if energized.contains(&(x, y)) {
+            [255, 140, 0]
+        } else if grid.get(&(x, y)) == Some(&'.') {
+            [15, 15, 35]
+        } else {
+            [110, 110, 110]
+        }
+    });
+
+    for ((x, y), _) in queue {
+        frame.set(*x, *y, [255, 255, 255]);
+    }
+
+    frame
 }
 
 #[cfg(test)]
@@ -202,7 +395,10 @@ mod tests {
             ((6, 3), '/'),
         ]);
 
-        assert_eq!(send_light((0, 0), 'R', &grid, 4, 7), 28);
+        assert_eq!(
+            send_light((0, 0), 'R', &grid, 4, 7, &mut NoRender).len(),
+            28
+        );
     }
 
     #[test]
@@ -222,6 +418,8 @@ mod tests {
 
         assert_eq!(y23d16(input, 1), 46);
         assert_eq!(y23d16(input, 2), 51);
+        assert_eq!(y23d16_recursive(input, 1), 46);
+        assert_eq!(y23d16_recursive(input, 2), 51);
     }
 
     #[test]
@@ -231,5 +429,7 @@ mod tests {
 
         assert_eq!(y23d16(&contents, 1), 7236);
         assert_eq!(y23d16(&contents, 2), 7521);
+        assert_eq!(y23d16_recursive(&contents, 1), 7236);
+        assert_eq!(y23d16_recursive(&contents, 2), 7521);
     }
 }
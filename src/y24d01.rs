@@ -120,6 +120,7 @@ pub fn y24d01p2(input: &str) -> u32 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::generate;
     use std::fs;
 
     #[test]
@@ -129,6 +130,31 @@ mod tests {
         assert_eq!(y24d01p2(input), 31);
     }
 
+    #[test]
+    fn properties() {
+        for input in generate::cases("y24d01", 100, 20) {
+            let (mut left, mut right): (Vec<i32>, Vec<i32>) = input
+                .lines()
+                .map(|line| {
+                    let (l, r) = line.split_once("   ").unwrap();
+                    (l.parse::<i32>().unwrap(), r.parse::<i32>().unwrap())
+                })
+                .unzip();
+
+            let similarity: i32 = left
+                .iter()
+                .map(|l| l * right.iter().filter(|r| *r == l).count() as i32)
+                .sum();
+            assert_eq!(y24d01p2(&input) as i32, similarity);
+
+            left.sort();
+            right.sort();
+            let distance: i32 =
+                left.iter().zip(&right).map(|(l, r)| (l - r).abs()).sum();
+            assert_eq!(y24d01p1(&input), distance);
+        }
+    }
+
     #[test]
     fn the_solution() {
         let contents = fs::read_to_string("input/2024/day01.txt").unwrap();
@@ -114,6 +114,7 @@ fn is_safe(nums: &[u32]) -> bool {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::generate;
     use std::fs;
 
     #[test]
@@ -155,6 +156,42 @@ mod tests {
         assert_eq!(y24d02(input, 2), 4);
     }
 
+    #[test]
+    fn properties() {
+        // a report is safe if every step goes the same way by one to three
+        let safe = |levels: &[u32]| {
+            let steps: Vec<i64> = levels
+                .windows(2)
+                .map(|w| w[1] as i64 - w[0] as i64)
+                .collect();
+            steps.iter().all(|s| (1..=3).contains(s))
+                || steps.iter().all(|s| (-3..=-1).contains(s))
+        };
+
+        for input in generate::cases("y24d02", 100, 10) {
+            let reports: Vec<Vec<u32>> = input
+                .lines()
+                .map(|l| l.split(' ').map(|n| n.parse().unwrap()).collect())
+                .collect();
+
+            let part1 = reports.iter().filter(|r| safe(r)).count() as u32;
+            let part2 = reports
+                .iter()
+                .filter(|r| {
+                    (0..r.len()).any(|i| {
+                        let mut dampened = r.to_vec();
+                        dampened.remove(i);
+                        safe(&dampened)
+                    })
+                })
+                .count() as u32;
+
+            assert_eq!(y24d02(&input, 1), part1);
+            assert_eq!(y24d02(&input, 2), part2);
+            assert!(part2 >= part1);
+        }
+    }
+
     #[test]
     fn the_solution() {
         let contents = fs::read_to_string("input/2024/day02.txt").unwrap();
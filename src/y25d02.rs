@@ -106,6 +106,7 @@ fn is_invalid_pt2(num: u64) -> bool {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::generate;
     use std::fs;
 
     #[test]
@@ -137,6 +138,40 @@ mod tests {
         assert_eq!(y25d02(input, 2), 4174379265);
     }
 
+    #[test]
+    fn properties() {
+        for input in generate::cases("y25d02", 100, 12) {
+            // a number made of something repeated twice is also made of
+            // something repeated at least twice
+            assert!(y25d02(&input, 2) >= y25d02(&input, 1));
+
+            // brute force: try building each number by repeating its prefixes
+            let (mut twice, mut repeated) = (0, 0);
+            for range in input.trim().split(',') {
+                let (start, end) = range.split_once('-').unwrap();
+                let start: u64 = start.parse().unwrap();
+                let end: u64 = end.parse().unwrap();
+
+                for id in start..=end {
+                    let s = id.to_string();
+                    if s.len() % 2 == 0 && s[..s.len() / 2].repeat(2) == s {
+                        twice += id;
+                    }
+
+                    if (1..s.len()).any(|len| {
+                        s.len() % len == 0
+                            && s[..len].repeat(s.len() / len) == s
+                    }) {
+                        repeated += id;
+                    }
+                }
+            }
+
+            assert_eq!(y25d02(&input, 1), twice);
+            assert_eq!(y25d02(&input, 2), repeated);
+        }
+    }
+
     #[test]
     fn the_solution() {
         let contents = fs::read_to_string("input/2025/day02.txt").unwrap();